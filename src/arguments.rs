@@ -16,15 +16,24 @@
 
 //! Provides the command's arguments and implements a method for parsing them.
 
+use std::collections::HashSet;
+use std::ffi::OsStr;
 use std::fmt::Display;
-use std::num::IntErrorKind;
+use std::hash::BuildHasher;
+use std::io::Write;
+use std::num::{IntErrorKind, NonZero};
+use std::os::unix::ffi::OsStrExt;
 use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use carp::{ArgumentOrPositional, Parser};
+use time::format_description::well_known::Rfc3339;
+use time::{Date, OffsetDateTime};
 
 use self::model::{
-    Arguments, ColorChoice, ListArguments, ModeVisibility, SizeVisibility, SortOrder, SubCommand, TimeVisibility,
-    TreeArguments,
+    Arguments, BlockSize, Collation, ColorChoice, ColumnKind, CompletionsArguments, DirectorySize, ListArguments,
+    ModeVisibility, OutputFormat, PagerChoice, Quoting, Shell, SizeVisibility, SortOrder, SubCommand, TimeStyle,
+    TimeVisibility, TreeArguments, TreeColor, TreeStyle,
 };
 use crate::arguments::schema::{
     ArgumentSchema, ArgumentSchemaBuilder, CommandSchema, CommandSchemaBuilder, ValueSchema, ValueSchemaBuilder,
@@ -39,24 +48,42 @@ pub mod schema;
 pub const SCHEMA: CommandSchema<'static> = {
     const PATHS_VALUE: ValueSchema<'static> =
         ValueSchemaBuilder::new("PATHS").about("The paths to display").list().build();
+    const PATTERN_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("PATTERN").required().build();
     const PATH_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("PATH").about("The path").required().build();
-    const COLOR_VALUE: ValueSchema<'static> =
-        ValueSchemaBuilder::new("CHOICE").required().default("auto").options(&["auto", "always", "never"]).build();
+    const COLOR_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("CHOICE")
+        .required()
+        .default("auto")
+        .options(&["auto", "always", "never", "html"])
+        .build();
+    const WIDTH_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("COLUMNS").required().build();
     const SORT_ORDER_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("ORDER")
         .required()
         .list()
         .default("directories,files,name")
         .options(&[
+            "none",
+            "first-seen",
             "name",
+            "length",
+            "extension",
             "accessed",
             "created",
             "modified",
+            "ctime",
             "size",
+            "total-size",
+            "depth",
             "files",
+            "files-first",
             "symlinks",
             "directories",
             "hidden",
             "reverse-*",
+            "size-desc",
+            "recent",
+            "alpha",
+            "random",
+            "ext-size",
         ])
         .build();
 
@@ -64,16 +91,89 @@ pub const SCHEMA: CommandSchema<'static> = {
         ArgumentSchemaBuilder::new("help", "Shows the command's usage").short('h').build();
     const COLOR_ARGUMENT: ArgumentSchema<'static> =
         ArgumentSchemaBuilder::new("color", "Determines whether to output using color").value(COLOR_VALUE).build();
+    const WIDTH_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("width", "Wrap output to a number of columns, overriding the terminal's width")
+            .value(WIDTH_VALUE)
+            .build();
     const ALL_ARGUMENT: ArgumentSchema<'static> =
         ArgumentSchemaBuilder::new("all", "Include hidden files and directories").short('a').build();
+    const ALMOST_ALL_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("almost-all", "Same as `--all`, as the `.` and `..` entries are never listed")
+            .short('A')
+            .build();
+    const HIDE_PATTERN_ARGUMENT: ArgumentSchema<'static> = ArgumentSchemaBuilder::new(
+        "hide-pattern",
+        "Hide entries with names matching a glob pattern, even alongside `--all`",
+    )
+    .value(PATTERN_VALUE)
+    .build();
+    const NAME_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("name", "Only show entries with names matching a glob pattern, such as `*.log`")
+            .value(PATTERN_VALUE)
+            .build();
+    const PATHS_FROM_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("paths-from", "Read additional paths from a file, one per line")
+            .value(FILE_VALUE)
+            .build();
+    const SORT_PATHS_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("sort-paths", "List the given paths in sorted order rather than the order given")
+            .build();
     const EXCLUDE_ARGUMENT: ArgumentSchema<'static> =
         ArgumentSchemaBuilder::new("exclude", "Exclude a directory from output").short('e').value(PATH_VALUE).build();
+    const EXCLUDE_FROM_ARGUMENT: ArgumentSchema<'static> = ArgumentSchemaBuilder::new(
+        "exclude-from",
+        "Exclude the paths listed in a file, one per line, ignoring blank lines and `#` comments",
+    )
+    .value(FILE_VALUE)
+    .build();
     const INCLUDE_ARGUMENT: ArgumentSchema<'static> =
         ArgumentSchemaBuilder::new("include", "Include a directory in the output").short('i').value(PATH_VALUE).build();
+    const GITIGNORE_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("gitignore", "Exclude entries matched by `.gitignore` files").build();
     const RESOLVE_SYMLINKS_ARGUMENT: ArgumentSchema<'static> =
         ArgumentSchemaBuilder::new("resolve-symlinks", "Fully resolve symbolic link paths").short('r').build();
-    const SORT_ARGUMENT: ArgumentSchema<'static> =
-        ArgumentSchemaBuilder::new("sort", "Control how entries are sorted").value(SORT_ORDER_VALUE).build();
+    const MIME_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("mime", "Color files based on their contents, such as images and executables")
+            .build();
+    const QUOTING_VALUE: ValueSchema<'static> =
+        ValueSchemaBuilder::new("CHOICE").required().default("safe").options(&["safe", "literal", "escape"]).build();
+    const QUOTING_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("quoting", "Control how control characters within names are written")
+            .value(QUOTING_VALUE)
+            .build();
+    const ESCAPE_TABS_ARGUMENT: ArgumentSchema<'static> = ArgumentSchemaBuilder::new(
+        "escape-tabs",
+        "Write tab characters within names as `\\t`, leaving any other control characters as they are",
+    )
+    .build();
+    const ALLOW_NAME_ANSI_ARGUMENT: ArgumentSchema<'static> = ArgumentSchemaBuilder::new(
+        "allow-name-ansi",
+        "Write ANSI escape sequences within names as they are, rather than escaping them",
+    )
+    .build();
+    const MAX_NAME_WIDTH_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("max-name-width", "Truncate names that are wider than the given number of columns")
+            .value(COUNT_VALUE)
+            .build();
+    const SORT_ARGUMENT: ArgumentSchema<'static> = ArgumentSchemaBuilder::new(
+        "sort",
+        "Control how entries are sorted, where `none` and presets must be given alone",
+    )
+    .value(SORT_ORDER_VALUE)
+    .build();
+    const SEED_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("SEED").required().build();
+    const SEED_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("seed", "Shuffle entries the same way each time when using `--sort random`")
+            .value(SEED_VALUE)
+            .build();
+    const COLLATION_VALUE: ValueSchema<'static> =
+        ValueSchemaBuilder::new("CHOICE").required().default("bytes").options(&["bytes", "unicode", "locale"]).build();
+    const COLLATION_ARGUMENT: ArgumentSchema<'static> = ArgumentSchemaBuilder::new(
+        "collation",
+        "Control how names are compared when sorting by name, where unicode and locale both use the CLDR root order",
+    )
+    .value(COLLATION_VALUE)
+    .build();
 
     const MODE_VALUE: ValueSchema<'static> =
         ValueSchemaBuilder::new("CHOICE").required().default("hide").options(&["hide", "show", "extended"]).build();
@@ -84,24 +184,181 @@ pub const SCHEMA: CommandSchema<'static> = {
         .build();
     const TIME_VALUE: ValueSchema<'static> =
         ValueSchemaBuilder::new("CHOICE").required().default("hide").options(&["hide", "simple", "iso8601"]).build();
+    const TIME_STYLE_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("STYLE")
+        .required()
+        .default("default")
+        .options(&["default", "long-iso", "full-iso", "weekday"])
+        .build();
+    const COLUMNS_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("COLUMNS")
+        .required()
+        .list()
+        .options(&[
+            "mode", "size", "blocks", "disk", "created", "accessed", "modified", "changed", "user", "group", "context",
+            "name",
+        ])
+        .build();
     const DEPTH_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("DEPTH").required().build();
+    const FILE_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("FILE").required().build();
+    const COUNT_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("COUNT").required().build();
+    const DATE_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("DATE").required().build();
 
     const MODE_ARGUMENT: ArgumentSchema<'static> =
         ArgumentSchemaBuilder::new("mode", "Control how entry modes are shown").short('m').value(MODE_VALUE).build();
     const SIZE_ARGUMENT: ArgumentSchema<'static> =
         ArgumentSchemaBuilder::new("size", "Control how entry sizes are shown").short('s').value(SIZE_VALUE).build();
+    const HUMAN_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("human", "Show entry sizes in base 2, same as `--size base-2`").short('H').build();
+    const SI_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("si", "Show entry sizes in base 10, same as `--size base-10`").build();
+    const ZERO_PAD_ARGUMENT: ArgumentSchema<'static> = ArgumentSchemaBuilder::new(
+        "zero-pad",
+        "Pad simple entry sizes with leading zeros, so that they can be sorted as text",
+    )
+    .build();
+    const BLOCK_SIZE_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("UNIT").required().default("1K").build();
+    const DIR_SIZE_VALUE: ValueSchema<'static> =
+        ValueSchemaBuilder::new("CHOICE").required().default("hide").options(&["hide", "child-count"]).build();
+    const DIR_SIZE_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("dir-size", "Control what is shown in place of a directory's size")
+            .value(DIR_SIZE_VALUE)
+            .build();
+    const TOTAL_ARGUMENT: ArgumentSchema<'static> = ArgumentSchemaBuilder::new(
+        "total",
+        "Show the total size of each listing's entries beneath them, in the same format as their sizes",
+    )
+    .build();
+    const BLOCKS_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("blocks", "Show the number of blocks allocated to each entry").build();
+    const BLOCK_SIZE_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("block-size", "Control the unit that blocks are counted in, such as `512` or `1K`")
+            .value(BLOCK_SIZE_VALUE)
+            .build();
+    const SPARSE_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("sparse", "Show the disk usage of each entry, marking sparse files with `*`")
+            .build();
     const CREATED_ARGUMENT: ArgumentSchema<'static> =
         ArgumentSchemaBuilder::new("created", "Control how creation dates are shown").value(TIME_VALUE).build();
     const ACCESSED_ARGUMENT: ArgumentSchema<'static> =
         ArgumentSchemaBuilder::new("accessed", "Control how access dates are shown").value(TIME_VALUE).build();
     const MODIFIED_ARGUMENT: ArgumentSchema<'static> =
         ArgumentSchemaBuilder::new("modified", "Control how modification dates are shown").value(TIME_VALUE).build();
+    const CHANGED_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("changed", "Control how status change dates are shown").value(TIME_VALUE).build();
+    const TIME_STYLE_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("time-style", "Control the format of dates shown in the simple format")
+            .value(TIME_STYLE_VALUE)
+            .build();
+    const SINCE_ARGUMENT: ArgumentSchema<'static> = ArgumentSchemaBuilder::new(
+        "since",
+        "Only show entries modified at or after the given date, such as `2024-01-01` or `2024-01-01T12:00:00Z`",
+    )
+    .value(DATE_VALUE)
+    .build();
+    const UNTIL_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("until", "Only show entries modified before the given date")
+            .value(DATE_VALUE)
+            .build();
     const USER_ARGUMENT: ArgumentSchema<'static> =
         ArgumentSchemaBuilder::new("user", "Show all entry user names").build();
     const GROUP_ARGUMENT: ArgumentSchema<'static> =
         ArgumentSchemaBuilder::new("group", "Show all entry group names").build();
+    const CONTEXT_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("context", "Show the SELinux security context of each entry").short('Z').build();
+    const COLUMNS_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("columns", "Control which columns are shown and in what order")
+            .value(COLUMNS_VALUE)
+            .build();
+    const LINK_TARGET_META_ARGUMENT: ArgumentSchema<'static> = ArgumentSchemaBuilder::new(
+        "link-target-meta",
+        "Show the metadata of symbolic link targets rather than the links themselves",
+    )
+    .build();
+    const HEADER_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("header", "Show a header row that names each column").build();
     const DEPTH_ARGUMENT: ArgumentSchema<'static> =
-        ArgumentSchemaBuilder::new("depth", "Control how deep to traverse").short('d').value(DEPTH_VALUE).build();
+        ArgumentSchemaBuilder::new("depth", "Control how deep to traverse, where 0 shows only the given paths")
+            .short('d')
+            .value(DEPTH_VALUE)
+            .build();
+    const RECURSIVE_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("recursive", "List the contents of every directory recursively").build();
+    const TREE_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("tree", "List the contents of every directory as a tree beside their metadata")
+            .build();
+    const NO_HEADERS_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("no-headers", "Do not name each listed directory above its contents").build();
+    const NO_SEPARATORS_ARGUMENT: ArgumentSchema<'static> = ArgumentSchemaBuilder::new(
+        "no-separators",
+        "Do not separate the contents of each listed directory with a blank line",
+    )
+    .build();
+    const FIND_HARDLINKS_ARGUMENT: ArgumentSchema<'static> = ArgumentSchemaBuilder::new(
+        "find-hardlinks",
+        "Number each group of listed files that are hard links to the same file",
+    )
+    .build();
+    const DIR_ONLY_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("dir-only", "List directories themselves, rather than their contents")
+            .short('D')
+            .build();
+    const RELATIVE_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("relative", "Show entry paths relative to the current directory").build();
+    const MAX_ENTRIES_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("max-entries", "Limit how many entries are shown").value(COUNT_VALUE).build();
+    const NO_ROOT_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("no-root", "Omit the listed paths, starting the tree with their contents").build();
+    const LEAVES_ONLY_ARGUMENT: ArgumentSchema<'static> = ArgumentSchemaBuilder::new(
+        "leaves-only",
+        "Only show entries without children, such as files, by their full paths",
+    )
+    .build();
+    const FULL_PATH_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("full-path", "Show each entry's path relative to its root").build();
+    const FLAT_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("flat", "Show each entry's path relative to its root without any branches").build();
+    const STATS_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("stats", "Show the total number and size of shown entries after the tree").build();
+    const COUNTS_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("counts", "Show the number of entries within each directory after its name").build();
+    const PROGRESS_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("progress", "Report how many entries have been scanned while traversing").build();
+    const SET_TITLE_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("set-title", "Set the terminal's title to the listed paths").build();
+    const TREE_COLOR_VALUE: ValueSchema<'static> =
+        ValueSchemaBuilder::new("CHOICE").required().default("default").options(&["default", "cycle"]).build();
+    const TREE_COLOR_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("tree-color", "Control how tree branches are colored")
+            .value(TREE_COLOR_VALUE)
+            .build();
+    const ASCII_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("ascii", "Draw tree branches using only ASCII characters").build();
+    const INDENT_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("indent", "Indent each level of the tree with spaces instead of branches").build();
+    const NARROW_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("narrow", "Indent each level of the tree by a single column").build();
+    const OUTPUT_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("output", "Write into a file instead of the terminal")
+            .short('o')
+            .value(FILE_VALUE)
+            .build();
+    const PAGER_VALUE: ValueSchema<'static> =
+        ValueSchemaBuilder::new("CHOICE").required().default("never").options(&["auto", "always", "never"]).build();
+    const PAGER_ARGUMENT: ArgumentSchema<'static> = ArgumentSchemaBuilder::new(
+        "pager",
+        "Control whether output is shown through `$PAGER`, where auto only does so if it would not fit on screen",
+    )
+    .value(PAGER_VALUE)
+    .build();
+    const FORMAT_VALUE: ValueSchema<'static> =
+        ValueSchemaBuilder::new("FORMAT").required().default("default").options(&["default", "csv"]).build();
+    const FORMAT_ARGUMENT: ArgumentSchema<'static> = ArgumentSchemaBuilder::new(
+        "format",
+        "Control how entries are written, where csv writes their path, size, mode, owner, and modification time",
+    )
+    .value(FORMAT_VALUE)
+    .build();
+    const PRINT0_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("print0", "Print only entry paths, each followed by a null byte").short('0').build();
 
     const LIST_COMMAND: CommandSchema<'static> =
         CommandSchemaBuilder::new("list", "List the contents of one or more directories")
@@ -109,18 +366,64 @@ pub const SCHEMA: CommandSchema<'static> = {
             .arguments(&[
                 HELP_ARGUMENT,
                 COLOR_ARGUMENT,
+                WIDTH_ARGUMENT,
                 ALL_ARGUMENT,
+                ALMOST_ALL_ARGUMENT,
+                HIDE_PATTERN_ARGUMENT,
+                NAME_ARGUMENT,
+                PATHS_FROM_ARGUMENT,
+                SORT_PATHS_ARGUMENT,
                 EXCLUDE_ARGUMENT,
+                EXCLUDE_FROM_ARGUMENT,
                 INCLUDE_ARGUMENT,
+                GITIGNORE_ARGUMENT,
                 RESOLVE_SYMLINKS_ARGUMENT,
+                MIME_ARGUMENT,
+                QUOTING_ARGUMENT,
+                ESCAPE_TABS_ARGUMENT,
+                ALLOW_NAME_ANSI_ARGUMENT,
+                MAX_NAME_WIDTH_ARGUMENT,
                 SORT_ARGUMENT,
+                SEED_ARGUMENT,
+                COLLATION_ARGUMENT,
                 MODE_ARGUMENT,
                 SIZE_ARGUMENT,
+                HUMAN_ARGUMENT,
+                SI_ARGUMENT,
+                ZERO_PAD_ARGUMENT,
+                DIR_SIZE_ARGUMENT,
+                TOTAL_ARGUMENT,
+                BLOCKS_ARGUMENT,
+                BLOCK_SIZE_ARGUMENT,
+                SPARSE_ARGUMENT,
                 CREATED_ARGUMENT,
                 ACCESSED_ARGUMENT,
                 MODIFIED_ARGUMENT,
+                CHANGED_ARGUMENT,
+                TIME_STYLE_ARGUMENT,
+                SINCE_ARGUMENT,
+                UNTIL_ARGUMENT,
                 USER_ARGUMENT,
                 GROUP_ARGUMENT,
+                CONTEXT_ARGUMENT,
+                COLUMNS_ARGUMENT,
+                HEADER_ARGUMENT,
+                LINK_TARGET_META_ARGUMENT,
+                RECURSIVE_ARGUMENT,
+                TREE_ARGUMENT,
+                DEPTH_ARGUMENT,
+                NO_HEADERS_ARGUMENT,
+                NO_SEPARATORS_ARGUMENT,
+                FIND_HARDLINKS_ARGUMENT,
+                DIR_ONLY_ARGUMENT,
+                RELATIVE_ARGUMENT,
+                MAX_ENTRIES_ARGUMENT,
+                PROGRESS_ARGUMENT,
+                SET_TITLE_ARGUMENT,
+                PRINT0_ARGUMENT,
+                FORMAT_ARGUMENT,
+                PAGER_ARGUMENT,
+                OUTPUT_ARGUMENT,
             ])
             .build();
 
@@ -130,17 +433,60 @@ pub const SCHEMA: CommandSchema<'static> = {
             .arguments(&[
                 HELP_ARGUMENT,
                 COLOR_ARGUMENT,
+                WIDTH_ARGUMENT,
                 ALL_ARGUMENT,
+                ALMOST_ALL_ARGUMENT,
+                HIDE_PATTERN_ARGUMENT,
+                NAME_ARGUMENT,
+                PATHS_FROM_ARGUMENT,
+                SORT_PATHS_ARGUMENT,
                 INCLUDE_ARGUMENT,
                 EXCLUDE_ARGUMENT,
+                EXCLUDE_FROM_ARGUMENT,
+                GITIGNORE_ARGUMENT,
                 RESOLVE_SYMLINKS_ARGUMENT,
+                MIME_ARGUMENT,
+                QUOTING_ARGUMENT,
+                ESCAPE_TABS_ARGUMENT,
+                ALLOW_NAME_ANSI_ARGUMENT,
+                MAX_NAME_WIDTH_ARGUMENT,
                 SORT_ARGUMENT,
+                SEED_ARGUMENT,
+                COLLATION_ARGUMENT,
                 DEPTH_ARGUMENT,
+                MAX_ENTRIES_ARGUMENT,
+                PROGRESS_ARGUMENT,
+                SET_TITLE_ARGUMENT,
+                NARROW_ARGUMENT,
+                TREE_COLOR_ARGUMENT,
+                ASCII_ARGUMENT,
+                INDENT_ARGUMENT,
+                NO_ROOT_ARGUMENT,
+                LEAVES_ONLY_ARGUMENT,
+                FULL_PATH_ARGUMENT,
+                FLAT_ARGUMENT,
+                STATS_ARGUMENT,
+                COUNTS_ARGUMENT,
+                PAGER_ARGUMENT,
+                OUTPUT_ARGUMENT,
             ])
             .build();
 
-    const SUBCOMMAND_VALUE: ValueSchema<'static> =
-        ValueSchemaBuilder::new("SUBCOMMAND").options(&[LIST_COMMAND.name, TREE_COMMAND.name]).build();
+    const SHELL_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("SHELL")
+        .about("The shell to generate completions for")
+        .required()
+        .options(&["bash"])
+        .build();
+
+    const COMPLETIONS_COMMAND: CommandSchema<'static> =
+        CommandSchemaBuilder::new("completions", "Generate a shell completion script")
+            .positionals(&[SHELL_VALUE])
+            .arguments(&[HELP_ARGUMENT])
+            .build();
+
+    const SUBCOMMAND_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("SUBCOMMAND")
+        .options(&[LIST_COMMAND.name, TREE_COMMAND.name, COMPLETIONS_COMMAND.name])
+        .build();
 
     const HELP_WITH_SUBCOMMAND_ARGUMENT: ArgumentSchema<'static> =
         ArgumentSchemaBuilder::new("help", "Shows the command (or a sub-command)'s usage")
@@ -148,16 +494,21 @@ pub const SCHEMA: CommandSchema<'static> = {
             .value(SUBCOMMAND_VALUE)
             .build();
     const VERSION_ARGUMENT: ArgumentSchema<'static> =
-        ArgumentSchemaBuilder::new("version", "Shows the command's version").short('V').build();
+        ArgumentSchemaBuilder::new("version", "Shows the command's version, including build details if repeated")
+            .short('V')
+            .build();
+    const VERBOSE_ARGUMENT: ArgumentSchema<'static> =
+        ArgumentSchemaBuilder::new("verbose", "Include build details when showing the command's version").build();
 
-    CommandSchemaBuilder::new(env!("CARGO_BIN_NAME"), env!("CARGO_PKG_DESCRIPTION"))
+    CommandSchemaBuilder::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_DESCRIPTION"))
         .version(env!("CARGO_PKG_VERSION"))
-        .arguments(&[HELP_WITH_SUBCOMMAND_ARGUMENT, VERSION_ARGUMENT, COLOR_ARGUMENT])
-        .commands(&[LIST_COMMAND, TREE_COMMAND])
+        .arguments(&[HELP_WITH_SUBCOMMAND_ARGUMENT, VERSION_ARGUMENT, VERBOSE_ARGUMENT, COLOR_ARGUMENT, WIDTH_ARGUMENT])
+        .commands(&[LIST_COMMAND, TREE_COMMAND, COMPLETIONS_COMMAND])
 }
 .build();
 
 /// A result of trying to parse the application's command-line arguments.
+#[expect(clippy::large_enum_variant, reason = "only one result is ever created per invocation")]
 pub enum ParseResult {
     /// The arguments were successfully parsed.
     Ok(Arguments),
@@ -177,9 +528,251 @@ fn exit_and_print(code: u8, display: impl Display) -> ParseResult {
     ParseResult::Exit(code)
 }
 
+/// Return an exiting [`ParseResult`] for an unexpected argument, suggesting the current command's closest argument.
+fn exit_unexpected(arguments: &Arguments, argument: ArgumentOrPositional<&str>) -> ParseResult {
+    let suggestion = match argument {
+        ArgumentOrPositional::Argument(carp::Argument::Long(name)) => {
+            let schema = arguments.current_schema();
+
+            self::suggest(name, schema.arguments.unwrap_or_default().iter().map(|argument| argument.long))
+        }
+        _ => None,
+    };
+    let message = suggestion.map_or_else(
+        || format!("unexpected argument `{argument}`"),
+        |name| format!("unexpected argument `{argument}`, did you mean `--{name}`?"),
+    );
+
+    self::exit_and_print(ERROR_CLI_USAGE, message)
+}
+
 /// Parses the application's command-line arguments from its invocation.
+#[must_use]
 pub fn parse_arguments() -> ParseResult {
-    let arguments: Box<[_]> = std::env::args().skip(1).collect();
+    self::parse_arguments_from(std::env::args().skip(1))
+}
+
+/// Parses the application's command-line arguments from the given strings, which should not include the program name.
+///
+/// # Examples
+///
+/// ```
+/// use fvr::arguments::{ParseResult, parse_arguments_from};
+///
+/// let ParseResult::Ok(arguments) = parse_arguments_from(["tree", "--depth", "2", "."]) else {
+///     unreachable!()
+/// };
+/// let tree_arguments = arguments.command.as_ref().and_then(|command| command.as_tree());
+///
+/// assert_eq!(tree_arguments.and_then(|tree_arguments| tree_arguments.max_depth), Some(2));
+/// ```
+///
+/// Exclusions may also be read from a file, which ignores blank lines and comments.
+///
+/// ```
+/// use fvr::arguments::{ParseResult, parse_arguments_from};
+///
+/// let file = std::env::temp_dir().join(format!("fvr-exclude-from-{}", std::process::id()));
+///
+/// std::fs::write(&file, "# Build outputs\n\ntarget\n  # src\nCargo.toml\n")?;
+///
+/// let result = parse_arguments_from(["list", "--exclude-from", &file.to_string_lossy(), "."]);
+///
+/// std::fs::remove_file(&file)?;
+///
+/// let ParseResult::Ok(arguments) = result else { unreachable!() };
+/// let excluded = arguments.command.as_ref().and_then(|command| command.as_list()?.excluded.as_ref());
+///
+/// let [manifest, source] = [std::fs::canonicalize("Cargo.toml")?, std::fs::canonicalize("src")?];
+///
+/// assert!(excluded.is_some_and(|excluded| excluded.contains(&*manifest) && !excluded.contains(&*source)));
+///
+/// let result = parse_arguments_from(["list", "--exclude-from", &file.to_string_lossy(), "."]);
+///
+/// assert!(matches!(result, ParseResult::Exit(code) if code != 0));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// Sort orders are chained in the order they are given, so files may be grouped before being sorted by size.
+///
+/// ```
+/// use fvr::arguments::model::SortOrder;
+/// use fvr::arguments::{ParseResult, parse_arguments_from};
+///
+/// let ParseResult::Ok(arguments) =
+///     parse_arguments_from(["list", "--sort", "files-first,size", "."])
+/// else {
+///     unreachable!()
+/// };
+/// let list_arguments = arguments.command.as_ref().and_then(|command| command.as_list());
+/// let expected = SortOrder::Files.then(SortOrder::Directories).then(SortOrder::Size);
+///
+/// assert_eq!(
+///     list_arguments.and_then(|list_arguments| list_arguments.sorting.as_ref()),
+///     Some(&expected)
+/// );
+/// ```
+///
+/// The `mtime` and `atime` sort orders are accepted as aliases of `modified` and `accessed`, including when reversed.
+///
+/// ```
+/// use fvr::arguments::model::SortOrder;
+/// use fvr::arguments::{ParseResult, parse_arguments_from};
+///
+/// for (order, expected) in
+///     [("mtime", SortOrder::Modified), ("reverse-atime", SortOrder::Accessed.reverse())]
+/// {
+///     let ParseResult::Ok(arguments) = parse_arguments_from(["tree", "--sort", order, "."])
+///     else {
+///         unreachable!()
+///     };
+///     let tree_arguments = arguments.command.as_ref().and_then(|command| command.as_tree());
+///
+///     assert_eq!(
+///         tree_arguments.and_then(|tree_arguments| tree_arguments.sorting.as_ref()),
+///         Some(&expected)
+///     );
+/// }
+/// ```
+///
+/// Presets expand into complete sort orders, including a tiebreak, and cannot be combined with other sort orders.
+///
+/// ```
+/// use fvr::arguments::model::SortOrder;
+/// use fvr::arguments::{ParseResult, parse_arguments_from};
+///
+/// for (preset, expected) in [
+///     ("size-desc", SortOrder::Size.reverse().then(SortOrder::Name)),
+///     ("recent", SortOrder::Modified.then(SortOrder::Name)),
+///     ("alpha", SortOrder::Name),
+///     ("ext-size", SortOrder::Extension.then(SortOrder::Size.reverse()).then(SortOrder::Name)),
+/// ] {
+///     let ParseResult::Ok(arguments) = parse_arguments_from(["list", "--sort", preset, "."]) else {
+///         unreachable!()
+///     };
+///     let list_arguments = arguments.command.as_ref().and_then(|command| command.as_list());
+///
+///     assert_eq!(
+///         list_arguments.and_then(|list_arguments| list_arguments.sorting.as_ref()),
+///         Some(&expected)
+///     );
+/// }
+///
+/// let result = parse_arguments_from(["list", "--sort", "directories,recent", "."]);
+///
+/// assert!(matches!(result, ParseResult::Exit(code) if code != 0));
+/// ```
+///
+/// Sorting may be skipped entirely, visiting entries in the order that the file system returns them.
+///
+/// ```
+/// use fvr::arguments::{ParseResult, parse_arguments_from};
+///
+/// for order in ["none", "first-seen"] {
+///     let ParseResult::Ok(arguments) = parse_arguments_from(["tree", "--sort", order, "."])
+///     else {
+///         unreachable!()
+///     };
+///     let tree_arguments = arguments.command.as_ref().and_then(|command| command.as_tree());
+///
+///     assert!(tree_arguments.is_some_and(
+///         |tree_arguments| tree_arguments.unsorted && tree_arguments.sorting.is_none()
+///     ));
+/// }
+/// ```
+///
+/// Modification dates may be bounded by either a full RFC 3339 timestamp or a date alone, which refers to the start of
+/// that day in the local time zone.
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// use fvr::arguments::{ParseResult, parse_arguments_from};
+///
+/// let ParseResult::Ok(arguments) =
+///     parse_arguments_from(["list", "--since", "2024-01-01T00:00:00Z", "--until", "2024-01-01", "."])
+/// else {
+///     unreachable!()
+/// };
+/// let list_arguments = arguments.command.as_ref().and_then(|command| command.as_list()).unwrap();
+/// let midnight = UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+/// let day = Duration::from_secs(24 * 60 * 60);
+///
+/// assert_eq!(list_arguments.since, Some(midnight));
+/// assert!(list_arguments.until.is_some_and(|until| until > midnight - day && until < midnight + day));
+///
+/// for date in ["yesterday", "2024-13-01", "2024-01-01 00:00"] {
+///     assert!(matches!(parse_arguments_from(["list", "--since", date, "."]), ParseResult::Exit(code) if code != 0));
+/// }
+/// ```
+///
+/// An explicit width is used wherever output is wrapped, such as when writing help, in place of the terminal's width.
+///
+/// ```
+/// use fvr::arguments::{ParseResult, parse_arguments_from};
+/// use fvr::terminal::terminal_width;
+///
+/// let ParseResult::Ok(arguments) = parse_arguments_from(["--width", "40", "tree", "."]) else {
+///     unreachable!()
+/// };
+/// let width = terminal_width(arguments.width).unwrap_or(usize::MAX);
+/// let mut buffer = Vec::new();
+///
+/// fvr::arguments::schema::write_help(arguments.current_schema(), width, &mut buffer)?;
+///
+/// let expected = concat!(
+///     "      --width [COLUMNS]          Wrap output to a\n",
+///     "                                 number of columns,\n",
+///     "                                 overriding the\n",
+///     "                                 terminal's width\n",
+/// );
+///
+/// assert_eq!(width, 40);
+/// assert!(String::from_utf8_lossy(&buffer).contains(expected));
+/// assert!(matches!(parse_arguments_from(["--width", "0", "list"]), ParseResult::Exit(code) if code != 0));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// Paths that are given more than once are only listed once, and a warning is written to standard error.
+///
+/// ```
+/// use fvr::arguments::{ParseResult, parse_arguments_from};
+///
+/// let ParseResult::Ok(arguments) = parse_arguments_from(["list", "src", "Cargo.toml", "src"])
+/// else {
+///     unreachable!()
+/// };
+/// let list_arguments = arguments.command.as_ref().and_then(|command| command.as_list());
+///
+/// assert_eq!(list_arguments.map(|list_arguments| list_arguments.paths.len()), Some(2));
+/// ```
+///
+/// A path that is both included and excluded is never shown, so a warning is written to standard error for it, as
+/// described by [`write_overlap_warnings`].
+///
+/// ```
+/// use fvr::arguments::{ParseResult, parse_arguments_from};
+///
+/// let ParseResult::Ok(arguments) = parse_arguments_from(["tree", "-i", "src", "-e", "src", "."])
+/// else {
+///     unreachable!()
+/// };
+/// let tree_arguments = arguments.command.as_ref().and_then(|command| command.as_tree());
+/// let source = std::fs::canonicalize("src")?;
+///
+/// assert!(tree_arguments.is_some_and(|tree_arguments| {
+///     tree_arguments.included.as_ref().is_some_and(|included| included.contains(&*source))
+///         && tree_arguments.excluded.as_ref().is_some_and(|excluded| excluded.contains(&*source))
+/// }));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[must_use]
+pub fn parse_arguments_from<I, S>(arguments: I) -> ParseResult
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let arguments: Box<[String]> = arguments.into_iter().map(Into::into).collect();
     let mut parser = Parser::new(arguments.iter().map(String::as_str));
     let mut arguments = Arguments::default();
 
@@ -192,11 +785,14 @@ pub fn parse_arguments() -> ParseResult {
         }
     }
 
-    let Some(paths) = arguments.command.as_mut().map(|v| match v {
-        SubCommand::List(arguments) => &mut arguments.paths,
-        SubCommand::Tree(arguments) => &mut arguments.paths,
-    }) else {
-        return self::exit_and_print(ERROR_CLI_USAGE, "no sub-command was provided");
+    let (paths, included, excluded) = match arguments.command.as_mut() {
+        Some(SubCommand::List(arguments)) => (&mut arguments.paths, &arguments.included, &arguments.excluded),
+        Some(SubCommand::Tree(arguments)) => (&mut arguments.paths, &arguments.included, &arguments.excluded),
+        Some(SubCommand::Completions(CompletionsArguments { shell: Some(_) })) => return ParseResult::Ok(arguments),
+        Some(SubCommand::Completions(CompletionsArguments { shell: None })) => {
+            return self::exit_and_print(ERROR_CLI_USAGE, "missing shell");
+        }
+        None => return self::exit_and_print(ERROR_CLI_USAGE, "no sub-command was provided"),
     };
 
     if paths.is_empty() {
@@ -206,9 +802,61 @@ pub fn parse_arguments() -> ParseResult {
         }
     }
 
+    if let (Some(included), Some(excluded)) = (included, excluded) {
+        // Failing to warn should never prevent the command from running.
+        drop(self::write_overlap_warnings(&mut std::io::stderr(), included, excluded));
+    }
+
     ParseResult::Ok(arguments)
 }
 
+/// Writes a warning for every path that is both included and excluded, in sorted order.
+///
+/// Exclusion always takes precedence, so these paths are never shown, which is likely to be a mistake.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashSet;
+/// use std::path::Path;
+///
+/// use fvr::arguments::write_overlap_warnings;
+///
+/// let included = HashSet::from([Path::new("/usr").into(), Path::new("/tmp").into()]);
+/// let excluded = HashSet::from([Path::new("/tmp").into(), Path::new("/var").into()]);
+/// let mut buffer = Vec::new();
+///
+/// write_overlap_warnings(&mut buffer, &included, &excluded)?;
+///
+/// assert_eq!(
+///     String::from_utf8_lossy(&buffer),
+///     "warning: `/tmp` is both included and excluded, so it will not be shown\n"
+/// );
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+pub fn write_overlap_warnings<S>(
+    f: &mut impl Write,
+    included: &HashSet<Box<Path>, S>,
+    excluded: &HashSet<Box<Path>, S>,
+) -> std::io::Result<()>
+where
+    S: BuildHasher,
+{
+    let mut overlap: Vec<_> = included.intersection(excluded).collect();
+
+    overlap.sort_unstable();
+
+    for path in overlap {
+        writeln!(f, "warning: `{}` is both included and excluded, so it will not be shown", path.display())?;
+    }
+
+    Ok(())
+}
+
 /// Parses a single command-line argument.
 fn parse_argument<'p, I>(
     arguments: &mut Arguments,
@@ -221,70 +869,251 @@ where
     use carp::Argument::{Long, Short};
     use carp::ArgumentOrPositional::{Argument, Positional};
 
+    // Most arguments are shared between the list and tree sub-commands.
+    let listing = arguments.command.as_ref().is_some_and(SubCommand::is_list_or_tree);
+
     match argument {
         Argument(Short('h') | Long("help")) => Some(self::parse_help(arguments, parser)),
-        Argument(Short('V') | Long("version")) if arguments.command.is_none() => Some(self::parse_version()),
-        Argument(Long("color")) => self::parse_color(arguments, parser),
-        Argument(Short('a') | Long("all")) if arguments.command.is_some() => self::parse_all(arguments),
-        Argument(Short('r') | Long("resolve-symlinks")) if arguments.command.is_some() => {
-            self::parse_resolve_symlinks(arguments)
-        }
-        Argument(Long("sort")) if arguments.command.is_some() => self::parse_sort(arguments, parser),
-        Argument(Short('m') | Long("mode")) if arguments.command.as_ref().is_some_and(SubCommand::is_list) => {
-            self::parse_mode(arguments, parser)
-        }
-        Argument(Short('s') | Long("size")) if arguments.command.as_ref().is_some_and(SubCommand::is_list) => {
-            self::parse_size(arguments, parser)
+        Argument(Short('V') | Long("version" | "verbose")) if arguments.command.is_none() => {
+            Some(self::parse_version(parser, argument))
         }
-        Argument(Long("created")) if arguments.command.as_ref().is_some_and(SubCommand::is_list) => {
-            self::parse_time(arguments, parser, TimeSectionType::Created)
-        }
-        Argument(Long("accessed")) if arguments.command.as_ref().is_some_and(SubCommand::is_list) => {
-            self::parse_time(arguments, parser, TimeSectionType::Accessed)
-        }
-        Argument(Long("modified")) if arguments.command.as_ref().is_some_and(SubCommand::is_list) => {
-            self::parse_time(arguments, parser, TimeSectionType::Modified)
-        }
-        Argument(Short('u') | Long("user")) if arguments.command.as_ref().is_some_and(SubCommand::is_list) => {
-            self::parse_user(arguments)
-        }
-        Argument(Short('g') | Long("group")) if arguments.command.as_ref().is_some_and(SubCommand::is_list) => {
-            self::parse_group(arguments)
+        // This is intentionally left out of the schema, as it only exists for tooling.
+        Argument(Long("dump-schema")) if arguments.command.is_none() => Some(self::parse_dump_schema()),
+        Argument(Long("color")) => self::parse_color(arguments, parser),
+        Argument(Long("width")) => self::parse_width(arguments, parser),
+        Argument(Short('a' | 'A') | Long("all" | "almost-all")) if listing => self::parse_all(arguments),
+        Argument(Long("hide-pattern")) if listing => self::parse_hide_pattern(arguments, parser),
+        Argument(Long("name")) if listing => self::parse_name(arguments, parser),
+        Argument(Long("gitignore")) if listing => self::parse_gitignore(arguments),
+        Argument(Short('r') | Long("resolve-symlinks")) if listing => self::parse_resolve_symlinks(arguments),
+        Argument(Long("mime")) if listing => self::parse_mime(arguments),
+        Argument(Long("quoting")) if listing => self::parse_quoting(arguments, parser),
+        Argument(Long("escape-tabs")) if listing => self::parse_escape_tabs(arguments),
+        Argument(Long("allow-name-ansi")) if listing => self::parse_allow_name_ansi(arguments),
+        Argument(Long("max-name-width")) if listing => self::parse_max_name_width(arguments, parser),
+        Argument(Long("sort")) if listing => self::parse_sort(arguments, parser),
+        Argument(Long("seed")) if listing => self::parse_seed(arguments, parser),
+        Argument(Long("collation")) if listing => self::parse_collation(arguments, parser),
+        Argument(Long("paths-from")) if listing => self::parse_paths_from(arguments, parser),
+        Argument(Long("sort-paths")) if listing => self::parse_sort_paths(arguments),
+        Argument(Short('e') | Long("exclude")) if listing => self::parse_exclude(arguments, parser),
+        Argument(Long("exclude-from")) if listing => self::parse_exclude_from(arguments, parser),
+        Argument(Short('i') | Long("include")) if listing => self::parse_include(arguments, parser),
+        Argument(Short('o') | Long("output")) if listing => self::parse_output(arguments, parser),
+        Argument(Short('d') | Long("depth")) if listing => self::parse_depth(arguments, parser),
+        Argument(Long("max-entries")) if listing => self::parse_max_entries(arguments, parser),
+        Argument(Long("progress")) if listing => self::parse_progress(arguments),
+        Argument(Long("set-title")) if listing => self::parse_set_title(arguments),
+        Argument(Long("pager")) if listing => self::parse_pager(arguments, parser),
+        Argument(_) if arguments.command.as_ref().is_some_and(SubCommand::is_list) => {
+            self::parse_list_argument(arguments, parser, argument)
         }
-        Argument(Short('e') | Long("exclude")) if arguments.command.is_some() => self::parse_exclude(arguments, parser),
-        Argument(Short('i') | Long("include")) if arguments.command.is_some() => self::parse_include(arguments, parser),
-        Argument(Short('d') | Long("depth")) if arguments.command.as_ref().is_some_and(SubCommand::is_tree) => {
-            self::parse_depth(arguments, parser)
+        Argument(_) if arguments.command.as_ref().is_some_and(SubCommand::is_tree) => {
+            self::parse_tree_argument(arguments, parser, argument)
         }
         Positional(value) => self::parse_positional(arguments, value),
-        Argument(_) => Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("unexpected argument `{argument}`"))),
+        Argument(_) => Some(self::exit_unexpected(arguments, argument)),
+    }
+}
+
+/// Parses a single command-line argument that is specific to the list sub-command.
+fn parse_list_argument<'p, I>(
+    arguments: &mut Arguments,
+    parser: &mut Parser<&'p str, I>,
+    argument: ArgumentOrPositional<&'p str>,
+) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    use carp::Argument::{Long, Short};
+    use carp::ArgumentOrPositional::Argument;
+
+    match argument {
+        Argument(Short('m') | Long("mode")) => self::parse_mode(arguments, parser),
+        Argument(Short('s') | Long("size")) => self::parse_size(arguments, parser),
+        Argument(Short('H') | Long("human")) => self::parse_size_alias(arguments, SizeVisibility::Base2),
+        Argument(Long("si")) => self::parse_size_alias(arguments, SizeVisibility::Base10),
+        Argument(Long("zero-pad")) => self::parse_zero_pad(arguments),
+        Argument(Long("dir-size")) => self::parse_dir_size(arguments, parser),
+        Argument(Long("total")) => self::parse_total(arguments),
+        Argument(Long("blocks")) => self::parse_blocks(arguments),
+        Argument(Long("block-size")) => self::parse_block_size(arguments, parser),
+        Argument(Long("sparse")) => self::parse_sparse(arguments),
+        Argument(Long("created")) => self::parse_time(arguments, parser, TimeSectionType::Created),
+        Argument(Long("accessed")) => self::parse_time(arguments, parser, TimeSectionType::Accessed),
+        Argument(Long("modified")) => self::parse_time(arguments, parser, TimeSectionType::Modified),
+        Argument(Long("changed")) => self::parse_time(arguments, parser, TimeSectionType::Changed),
+        Argument(Long("time-style")) => self::parse_time_style(arguments, parser),
+        Argument(Long("since")) => self::parse_since(arguments, parser),
+        Argument(Long("until")) => self::parse_until(arguments, parser),
+        Argument(Short('u') | Long("user")) => self::parse_user(arguments),
+        Argument(Short('g') | Long("group")) => self::parse_group(arguments),
+        Argument(Short('Z') | Long("context")) => self::parse_context(arguments),
+        Argument(Long("columns")) => self::parse_columns(arguments, parser),
+        Argument(Long("header")) => self::parse_header(arguments),
+        Argument(Long("link-target-meta")) => self::parse_link_target_meta(arguments),
+        Argument(Long("recursive")) => self::parse_recursive(arguments),
+        Argument(Long("tree")) => self::parse_tree(arguments),
+        Argument(Long("no-headers")) => self::parse_no_headers(arguments),
+        Argument(Long("no-separators")) => self::parse_no_separators(arguments),
+        Argument(Long("find-hardlinks")) => self::parse_find_hardlinks(arguments),
+        Argument(Short('D') | Long("dir-only")) => self::parse_dir_only(arguments),
+        Argument(Long("relative")) => self::parse_relative(arguments),
+        Argument(Short('0') | Long("print0")) => self::parse_print0(arguments),
+        Argument(Long("format")) => self::parse_format(arguments, parser),
+        _ => Some(self::exit_unexpected(arguments, argument)),
+    }
+}
+
+/// Parses a single command-line argument that is specific to the tree sub-command.
+fn parse_tree_argument<'p, I>(
+    arguments: &mut Arguments,
+    parser: &mut Parser<&'p str, I>,
+    argument: ArgumentOrPositional<&'p str>,
+) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    use carp::Argument::Long;
+    use carp::ArgumentOrPositional::Argument;
+
+    match argument {
+        Argument(Long("narrow")) => self::parse_narrow(arguments),
+        Argument(Long("tree-color")) => self::parse_tree_color(arguments, parser),
+        Argument(Long("ascii")) => self::parse_tree_style(arguments, TreeStyle::Ascii),
+        Argument(Long("indent")) => self::parse_tree_style(arguments, TreeStyle::Indent),
+        Argument(Long("no-root")) => self::parse_no_root(arguments),
+        Argument(Long("leaves-only")) => self::parse_leaves_only(arguments),
+        Argument(Long("full-path")) => self::parse_full_path(arguments),
+        Argument(Long("flat")) => self::parse_flat(arguments),
+        Argument(Long("stats")) => self::parse_stats(arguments),
+        Argument(Long("counts")) => self::parse_counts(arguments),
+        _ => Some(self::exit_unexpected(arguments, argument)),
     }
 }
 
 /// Parses a single positional command-line argument.
 fn parse_positional(arguments: &mut Arguments, value: &str) -> Option<ParseResult> {
-    if let Some(command) = arguments.command.as_mut() {
-        let (SubCommand::List(ListArguments { paths, .. }) | SubCommand::Tree(TreeArguments { paths, .. })) = command;
+    if let Some(SubCommand::Completions(CompletionsArguments { shell })) = arguments.command.as_mut() {
+        if shell.is_some() {
+            return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("unexpected argument `{value}`")));
+        }
 
+        *shell = Some(match value {
+            "bash" => Shell::Bash,
+            _ => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("unsupported shell `{value}`"))),
+        });
+    } else if let Some(SubCommand::List(ListArguments { paths, .. }) | SubCommand::Tree(TreeArguments { paths, .. })) =
+        arguments.command.as_mut()
+    {
+        // Canonicalizing resolves symbolic links, so linked paths given on the command line are always followed.
         match Path::new(value).canonicalize().map(PathBuf::into_boxed_path) {
-            Ok(path) => {
-                if !paths.contains(&path) {
-                    paths.push(path);
+            // Duplicates are still only listed once, but are reported since they are likely to be a mistake.
+            Ok(path) if paths.contains(&path) => eprintln!("warning: `{value}` was given more than once"),
+            Ok(path) => paths.push(path),
+            // Patterns are usually expanded by the shell, but may still be given literally if they were quoted.
+            Err(error) if crate::files::glob::is_pattern(value) => {
+                let expanded = crate::files::glob::expand(value).unwrap_or_default();
+
+                if expanded.is_empty() {
+                    return Some(self::exit_and_print(ERROR_GENERIC, error));
+                }
+
+                for path in expanded {
+                    match path.canonicalize().map(PathBuf::into_boxed_path) {
+                        Ok(path) if !paths.contains(&path) => paths.push(path),
+                        Ok(_) => {}
+                        Err(error) => return Some(self::exit_and_print(ERROR_GENERIC, error)),
+                    }
                 }
             }
-            Err(error) => return Some(self::exit_and_print(ERROR_GENERIC, error)),
+            // Paths that cannot be resolved are kept as given, so that they are reported and skipped alongside any
+            // other unreadable path rather than preventing the readable paths from being listed.
+            Err(_) => paths.push(Box::from(Path::new(value))),
         }
     } else {
         arguments.command = Some(match value {
             "list" => SubCommand::List(ListArguments::default()),
             "tree" => SubCommand::Tree(TreeArguments::default()),
-            _ => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("unknown sub-command `{value}`"))),
+            "completions" => SubCommand::Completions(CompletionsArguments::default()),
+            _ => {
+                let message = self::suggest_command(value).map_or_else(
+                    || format!("unknown sub-command `{value}`"),
+                    |name| format!("unknown sub-command `{value}`, did you mean `{name}`?"),
+                );
+
+                return Some(self::exit_and_print(ERROR_CLI_USAGE, message));
+            }
         });
     }
 
     None
 }
 
+/// Returns the name of the sub-command that most closely resembles the given value, if any are close enough.
+fn suggest_command(value: &str) -> Option<&'static str> {
+    self::suggest(value, SCHEMA.commands?.iter().map(|command| command.name))
+}
+
+/// Returns the given name that most closely resembles the given value, if any are close enough.
+fn suggest<'n>(value: &str, names: impl IntoIterator<Item = &'n str>) -> Option<&'n str> {
+    /// The maximum number of edits allowed between the value and a suggested name.
+    const MAX_DISTANCE: usize = 2;
+
+    names
+        .into_iter()
+        .map(|name| (name, self::edit_distance(value, name)))
+        .filter(|(_, distance)| *distance <= MAX_DISTANCE)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(name, _)| name)
+}
+
+/// Returns the Levenshtein distance between the two given strings.
+fn edit_distance(lhs: &str, rhs: &str) -> usize {
+    let rhs = rhs.chars().collect::<Box<[_]>>();
+    let mut previous = (0 ..= rhs.len()).collect::<Box<[_]>>();
+    let mut current = vec![0; rhs.len() + 1].into_boxed_slice();
+
+    for (index, lhs_char) in lhs.chars().enumerate() {
+        current[0] = index + 1;
+
+        for (rhs_index, rhs_char) in rhs.iter().enumerate() {
+            let substitution = previous[rhs_index] + usize::from(lhs_char != *rhs_char);
+
+            current[rhs_index + 1] = substitution.min(previous[rhs_index + 1] + 1).min(current[rhs_index] + 1);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[rhs.len()]
+}
+
+/// Parses the value of a command-line argument.
+///
+/// Values may be given either as `--key=value` or as `--key value`, and both forms are treated identically. Short
+/// arguments additionally accept the remainder of their cluster as a value, such that `-d3` is read as `-d 3`. If the
+/// next argument is not a positional value, it is consumed and the value is considered missing.
+///
+/// # Errors
+///
+/// This function will return an error if the next argument could not be parsed.
+fn parse_value<'p, I>(parser: &mut Parser<&'p str, I>) -> Result<Option<&'p str>, impl Display + use<'p, I>>
+where
+    I: Iterator<Item = &'p str>,
+{
+    match parser.parse_next_assigned_value() {
+        Ok(None) => {}
+        result => return result,
+    }
+
+    match parser.parse_next() {
+        Ok(Some(ArgumentOrPositional::Positional(value))) => Ok(Some(value)),
+        Ok(Some(ArgumentOrPositional::Argument(_)) | None) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
 /// Parses the help command-line argument.
 fn parse_help<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> ParseResult
 where
@@ -297,15 +1126,54 @@ where
         drop(self::parse_positional(arguments, value));
     }
 
-    match self::schema::write_help(arguments.current_schema(), &mut std::io::stdout()) {
+    // Help is wrapped to the default width when it is not being written into a terminal, such as when it is piped.
+    let width = crate::terminal::terminal_width(arguments.width).unwrap_or(crate::terminal::DEFAULT_WIDTH);
+
+    match self::schema::write_help(arguments.current_schema(), width, &mut std::io::stdout()) {
         Ok(()) => ParseResult::Exit(SUCCESS),
         Err(error) => self::exit_and_print(ERROR_GENERIC, error),
     }
 }
 
 /// Parses the version command-line argument.
-fn parse_version() -> ParseResult {
-    self::exit_and_print(SUCCESS, format_args!("{} v{}", env!("CARGO_BIN_NAME"), env!("CARGO_PKG_VERSION")))
+///
+/// If immediately followed by another version argument or by a verbose argument, build details are also shown. A
+/// verbose argument may instead be given first, in which case it must be immediately followed by a version argument.
+fn parse_version<'p, I>(parser: &mut Parser<&'p str, I>, argument: ArgumentOrPositional<&'p str>) -> ParseResult
+where
+    I: Iterator<Item = &'p str>,
+{
+    use carp::Argument::{Long, Short};
+    use carp::ArgumentOrPositional::Argument;
+
+    let version = format!("{} v{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+    let next = parser.parse_next();
+
+    if argument == Argument(Long("verbose")) && !matches!(next, Ok(Some(Argument(Short('V') | Long("version"))))) {
+        return self::exit_and_print(ERROR_CLI_USAGE, "`--verbose` may only be given alongside `--version`");
+    }
+    if !matches!(next, Ok(Some(Argument(Short('V') | Long("version" | "verbose"))))) {
+        return self::exit_and_print(SUCCESS, version);
+    }
+
+    let build_date = env!("BUILD_TIMESTAMP")
+        .parse()
+        .ok()
+        .and_then(|timestamp| OffsetDateTime::from_unix_timestamp(timestamp).ok())
+        .and_then(|timestamp| timestamp.format(time::macros::format_description!("[year]-[month]-[day]")).ok());
+
+    self::exit_and_print(
+        SUCCESS,
+        format_args!("{version}\ncommit: {}\nbuilt: {}", env!("GIT_HASH"), build_date.as_deref().unwrap_or("unknown")),
+    )
+}
+
+/// Parses the dump schema command-line argument, writing the command's schema as JSON.
+fn parse_dump_schema() -> ParseResult {
+    match self::schema::write_json(SCHEMA, &mut std::io::stdout()) {
+        Ok(()) => ParseResult::Exit(SUCCESS),
+        Err(error) => self::exit_and_print(ERROR_GENERIC, error),
+    }
 }
 
 /// Parses the color command-line argument.
@@ -313,7 +1181,7 @@ fn parse_color<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>
 where
     I: Iterator<Item = &'p str>,
 {
-    let Some(choice) = (match parser.parse_next_assigned_value() {
+    let Some(choice) = (match self::parse_value(parser) {
         Ok(choice) => choice,
         Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
     }) else {
@@ -324,134 +1192,467 @@ where
         "auto" => ColorChoice::Auto,
         "always" => ColorChoice::Always,
         "never" => ColorChoice::Never,
+        "html" => ColorChoice::Html,
         v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid color choice '{v}'"))),
     };
 
     None
 }
 
-/// Parses the all command-line argument.
-fn parse_all(arguments: &mut Arguments) -> Option<ParseResult> {
-    let Some(command) = arguments.command.as_mut() else { unreachable!() };
+/// Parses the width command-line argument.
+fn parse_width<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(choice) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing width"));
+    };
 
-    match command {
-        SubCommand::List(arguments) => arguments.show_hidden = true,
-        SubCommand::Tree(arguments) => arguments.show_hidden = true,
-    }
+    arguments.width = Some(match choice.parse::<NonZero<usize>>() {
+        Ok(value) => value.get(),
+        Err(error) => {
+            return Some(self::exit_and_print(ERROR_CLI_USAGE, match error.kind() {
+                IntErrorKind::Empty => "missing width",
+                IntErrorKind::Zero | IntErrorKind::InvalidDigit => "width must be a non-zero positive integer",
+                IntErrorKind::PosOverflow => "width is too large",
+                IntErrorKind::NegOverflow => "width is too small",
+                _ => "invalid width",
+            }));
+        }
+    });
 
     None
 }
 
-/// Parses the resolve-symlinks command-line argument.
-fn parse_resolve_symlinks(arguments: &mut Arguments) -> Option<ParseResult> {
+/// Parses the all command-line argument.
+fn parse_all(arguments: &mut Arguments) -> Option<ParseResult> {
     let Some(command) = arguments.command.as_mut() else { unreachable!() };
 
     match command {
-        SubCommand::List(arguments) => arguments.resolve_symlinks = true,
-        SubCommand::Tree(arguments) => arguments.resolve_symlinks = true,
+        SubCommand::List(arguments) => arguments.show_hidden = true,
+        SubCommand::Tree(arguments) => arguments.show_hidden = true,
+        SubCommand::Completions(_) => unreachable!(),
     }
 
     None
 }
 
-/// Parses the sort command-line argument.
-fn parse_sort<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+/// Parses the hide-pattern command-line argument.
+fn parse_hide_pattern<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
 where
     I: Iterator<Item = &'p str>,
 {
-    let Some(SubCommand::List(ListArguments { sorting, .. }) | SubCommand::Tree(TreeArguments { sorting, .. })) =
-        arguments.command.as_mut()
-    else {
-        unreachable!();
-    };
-
-    let Some(orderings) = (match parser.parse_next_assigned_value() {
+    let Some(pattern) = (match self::parse_value(parser) {
         Ok(choice) => choice,
         Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
     }) else {
-        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing sort order"));
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing hidden pattern"));
     };
 
-    *sorting = None;
-
-    for string in orderings.split(',') {
-        let mut next = match string.trim_start_matches("reverse-") {
-            "name" => SortOrder::Name,
-            "accessed" => SortOrder::Accessed,
-            "created" => SortOrder::Created,
-            "modified" => SortOrder::Modified,
-            "size" => SortOrder::Size,
-            "files" => SortOrder::Files,
-            "symlinks" => SortOrder::Symlinks,
-            "directories" => SortOrder::Directories,
-            "hidden" => SortOrder::Hidden,
-            v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid sort order '{v}'"))),
-        };
-
-        if string.starts_with("reverse-") {
-            next = next.reverse();
-        }
+    let Some(
+        SubCommand::List(ListArguments { hide_patterns, .. }) | SubCommand::Tree(TreeArguments { hide_patterns, .. }),
+    ) = arguments.command.as_mut()
+    else {
+        unreachable!();
+    };
 
-        if let Some(current) = sorting.take().filter(|v| v.top() != &next) {
-            *sorting = Some(current.then(next));
-        } else {
-            *sorting = Some(next);
-        }
-    }
+    hide_patterns.push(Box::from(pattern));
 
     None
 }
 
-/// Parses the mode command-line argument.
-fn parse_mode<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+/// Parses the name command-line argument.
+fn parse_name<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
 where
     I: Iterator<Item = &'p str>,
 {
-    let Some(choice) = (match parser.parse_next_assigned_value() {
+    let Some(pattern) = (match self::parse_value(parser) {
         Ok(choice) => choice,
         Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
     }) else {
-        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing mode visibility"));
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing name pattern"));
     };
 
-    let Some(SubCommand::List(ListArguments { mode, .. })) = arguments.command.as_mut() else { unreachable!() };
-
-    *mode = match choice {
-        "hide" => ModeVisibility::Hide,
-        "show" => ModeVisibility::Show,
-        "extended" => ModeVisibility::Extended,
-        v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid mode visibility '{v}'"))),
+    let Some(
+        SubCommand::List(ListArguments { name_patterns, .. }) | SubCommand::Tree(TreeArguments { name_patterns, .. }),
+    ) = arguments.command.as_mut()
+    else {
+        unreachable!();
     };
 
+    name_patterns.push(Box::from(pattern));
+
     None
 }
 
-/// Parses the size command-line argument.
-fn parse_size<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+/// Parses the gitignore command-line argument.
+fn parse_gitignore(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(command) = arguments.command.as_mut() else { unreachable!() };
+
+    match command {
+        SubCommand::List(arguments) => arguments.gitignore = true,
+        SubCommand::Tree(arguments) => arguments.gitignore = true,
+        SubCommand::Completions(_) => unreachable!(),
+    }
+
+    None
+}
+
+/// Parses the resolve-symlinks command-line argument.
+fn parse_resolve_symlinks(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(command) = arguments.command.as_mut() else { unreachable!() };
+
+    match command {
+        SubCommand::List(arguments) => arguments.resolve_symlinks = true,
+        SubCommand::Tree(arguments) => arguments.resolve_symlinks = true,
+        SubCommand::Completions(_) => unreachable!(),
+    }
+
+    None
+}
+
+/// Parses the mime command-line argument.
+fn parse_mime(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(command) = arguments.command.as_mut() else { unreachable!() };
+
+    match command {
+        SubCommand::List(arguments) => arguments.mime = true,
+        SubCommand::Tree(arguments) => arguments.mime = true,
+        SubCommand::Completions(_) => unreachable!(),
+    }
+
+    None
+}
+
+/// Parses the quoting command-line argument.
+fn parse_quoting<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
 where
     I: Iterator<Item = &'p str>,
 {
-    let Some(choice) = (match parser.parse_next_assigned_value() {
+    let Some(choice) = (match self::parse_value(parser) {
         Ok(choice) => choice,
         Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
     }) else {
-        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing size visibility"));
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing quoting choice"));
     };
 
-    let Some(SubCommand::List(ListArguments { size, .. })) = arguments.command.as_mut() else { unreachable!() };
+    let Some(SubCommand::List(ListArguments { quoting, .. }) | SubCommand::Tree(TreeArguments { quoting, .. })) =
+        arguments.command.as_mut()
+    else {
+        unreachable!();
+    };
 
-    *size = match choice {
-        "hide" => SizeVisibility::Hide,
-        "simple" => SizeVisibility::Simple,
-        "base-2" => SizeVisibility::Base2,
-        "base-10" => SizeVisibility::Base10,
-        v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid size visibility '{v}'"))),
+    *quoting = match choice {
+        "safe" => Quoting::Safe,
+        "literal" => Quoting::Literal,
+        "escape" => Quoting::Escape,
+        v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid quoting choice '{v}'"))),
     };
 
     None
 }
 
-/// Parses the created, accessed, and/or modified command-line argument.
+/// Parses the escape-tabs command-line argument.
+fn parse_escape_tabs(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(command) = arguments.command.as_mut() else { unreachable!() };
+
+    match command {
+        SubCommand::List(arguments) => arguments.quoting = Quoting::Tabs,
+        SubCommand::Tree(arguments) => arguments.quoting = Quoting::Tabs,
+        SubCommand::Completions(_) => unreachable!(),
+    }
+
+    None
+}
+
+/// Parses the allow-name-ansi command-line argument.
+fn parse_allow_name_ansi(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(command) = arguments.command.as_mut() else { unreachable!() };
+
+    match command {
+        SubCommand::List(arguments) => arguments.quoting = Quoting::Raw,
+        SubCommand::Tree(arguments) => arguments.quoting = Quoting::Raw,
+        SubCommand::Completions(_) => unreachable!(),
+    }
+
+    None
+}
+
+/// Parses the max-name-width command-line argument.
+fn parse_max_name_width<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(choice) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing name width"));
+    };
+
+    let Some(
+        SubCommand::List(ListArguments { max_name_width, .. }) | SubCommand::Tree(TreeArguments { max_name_width, .. }),
+    ) = arguments.command.as_mut()
+    else {
+        unreachable!();
+    };
+
+    *max_name_width = Some(match choice.parse() {
+        Ok(value) => value,
+        Err(error) => {
+            return Some(self::exit_and_print(ERROR_CLI_USAGE, match error.kind() {
+                IntErrorKind::Empty => "missing name width",
+                IntErrorKind::Zero | IntErrorKind::InvalidDigit => "name width must be a non-zero positive integer",
+                IntErrorKind::PosOverflow => "name width is too large",
+                IntErrorKind::NegOverflow => "name width is too small",
+                _ => "invalid name width",
+            }));
+        }
+    });
+
+    None
+}
+
+/// Parses the sort command-line argument.
+fn parse_sort<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(
+        SubCommand::List(ListArguments { sorting, unsorted, seed, collation, .. })
+        | SubCommand::Tree(TreeArguments { sorting, unsorted, seed, collation, .. }),
+    ) = arguments.command.as_mut()
+    else {
+        unreachable!();
+    };
+
+    let Some(orderings) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing sort order"));
+    };
+
+    *sorting = None;
+    // Both names skip sorting entirely, with `first-seen` spelling out that the file system's order is kept as-is.
+    *unsorted = matches!(orderings, "none" | "first-seen");
+
+    if *unsorted {
+        return None;
+    }
+
+    // Presets are complete orders with their own tiebreaks, so they are only accepted on their own.
+    *sorting = match orderings {
+        "size-desc" => Some(SortOrder::Size.reverse().then(SortOrder::Name)),
+        "recent" => Some(SortOrder::Modified.then(SortOrder::Name)),
+        "alpha" => Some(SortOrder::Name),
+        // Without a seed, entries are shuffled differently on each run.
+        "random" => Some(SortOrder::Random(seed.unwrap_or_else(self::random_seed))),
+        "ext-size" => Some(SortOrder::Extension.then(SortOrder::Size.reverse()).then(SortOrder::Name)),
+        _ => None,
+    };
+
+    if let Some(sorting) = sorting.as_mut() {
+        sorting.set_collation(*collation);
+
+        return None;
+    }
+
+    for string in orderings.split(',') {
+        let mut next = match string.trim_start_matches("reverse-") {
+            "name" => SortOrder::Name,
+            "length" => SortOrder::NameLength,
+            "extension" => SortOrder::Extension,
+            // The `ls`-style names are accepted as aliases, but are left out of the help display to avoid clutter.
+            "accessed" | "atime" => SortOrder::Accessed,
+            "created" => SortOrder::Created,
+            "modified" | "mtime" => SortOrder::Modified,
+            "ctime" => SortOrder::ChangeTime,
+            "size" => SortOrder::Size,
+            "total-size" => SortOrder::TotalSize,
+            "depth" => SortOrder::Depth,
+            "files" => SortOrder::Files,
+            // Ordering by files alone would leave directories and symbolic links mixed together after them.
+            "files-first" => SortOrder::Files.then(SortOrder::Directories),
+            "symlinks" => SortOrder::Symlinks,
+            "directories" => SortOrder::Directories,
+            "hidden" => SortOrder::Hidden,
+            v @ ("none" | "first-seen" | "size-desc" | "recent" | "alpha" | "random" | "ext-size") => {
+                return Some(self::exit_and_print(
+                    ERROR_CLI_USAGE,
+                    format_args!("sort order '{v}' must be given alone"),
+                ));
+            }
+            v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid sort order '{v}'"))),
+        };
+
+        if string.starts_with("reverse-") {
+            next = next.reverse();
+        }
+
+        if let Some(current) = sorting.take().filter(|v| v.top() != &next) {
+            *sorting = Some(current.then(next));
+        } else {
+            *sorting = Some(next);
+        }
+    }
+
+    if let Some(sorting) = sorting.as_mut() {
+        sorting.set_collation(*collation);
+    }
+
+    None
+}
+
+/// Parses the seed command-line argument.
+///
+/// The seed applies to a random sort order whether it is given before or after it.
+fn parse_seed<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(choice) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing seed"));
+    };
+
+    let Some(
+        SubCommand::List(ListArguments { sorting, seed, .. }) | SubCommand::Tree(TreeArguments { sorting, seed, .. }),
+    ) = arguments.command.as_mut()
+    else {
+        unreachable!();
+    };
+
+    let value = match choice.parse::<u64>() {
+        Ok(value) => value,
+        Err(error) => {
+            return Some(self::exit_and_print(ERROR_CLI_USAGE, match error.kind() {
+                IntErrorKind::Empty => "missing seed",
+                IntErrorKind::InvalidDigit => "seed must be a positive integer or zero",
+                IntErrorKind::PosOverflow => "seed is too large",
+                IntErrorKind::NegOverflow => "seed is too small",
+                _ => "invalid seed",
+            }));
+        }
+    };
+
+    *seed = Some(value);
+
+    if let Some(SortOrder::Random(current)) = sorting {
+        *current = value;
+    }
+
+    None
+}
+
+/// Parses the collation command-line argument.
+///
+/// The collation applies to every name ordering, whether it is given before or after the sort order.
+fn parse_collation<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(choice) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing collation"));
+    };
+
+    let Some(
+        SubCommand::List(ListArguments { sorting, unsorted, collation, .. })
+        | SubCommand::Tree(TreeArguments { sorting, unsorted, collation, .. }),
+    ) = arguments.command.as_mut()
+    else {
+        unreachable!();
+    };
+
+    *collation = match choice {
+        "bytes" => Collation::Bytes,
+        // Names are always compared in the CLDR root order, so `locale` never reads `LC_COLLATE`.
+        "unicode" | "locale" => Collation::Unicode,
+        v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid collation '{v}'"))),
+    };
+
+    // The default order also sorts by name, so it is filled in to have the collation applied to it.
+    if !*unsorted {
+        sorting.get_or_insert_default().set_collation(*collation);
+    }
+
+    None
+}
+
+/// Returns a seed that differs between runs, used when shuffling entries without an explicit seed.
+fn random_seed() -> u64 {
+    let elapsed = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+
+    elapsed.as_secs() ^ u64::from(elapsed.subsec_nanos()).rotate_left(32) ^ u64::from(std::process::id())
+}
+
+/// Parses the mode command-line argument.
+fn parse_mode<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(choice) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing mode visibility"));
+    };
+
+    let Some(SubCommand::List(ListArguments { mode, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *mode = match choice {
+        "hide" => ModeVisibility::Hide,
+        "show" => ModeVisibility::Show,
+        "extended" => ModeVisibility::Extended,
+        v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid mode visibility '{v}'"))),
+    };
+
+    None
+}
+
+/// Parses the size command-line argument.
+fn parse_size<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(choice) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing size visibility"));
+    };
+
+    let Some(SubCommand::List(ListArguments { size, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *size = match choice {
+        "hide" => SizeVisibility::Hide,
+        "simple" => SizeVisibility::Simple,
+        "base-2" => SizeVisibility::Base2,
+        "base-10" => SizeVisibility::Base10,
+        v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid size visibility '{v}'"))),
+    };
+
+    None
+}
+
+/// Parses the human and si command-line arguments, which are aliases of specific size visibilities.
+fn parse_size_alias(arguments: &mut Arguments, visibility: SizeVisibility) -> Option<ParseResult> {
+    let Some(SubCommand::List(ListArguments { size, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *size = visibility;
+
+    None
+}
+
+/// Parses the created, accessed, and/or modified command-line argument.
 fn parse_time<'p, I>(
     arguments: &mut Arguments,
     parser: &mut Parser<&'p str, I>,
@@ -460,52 +1661,423 @@ fn parse_time<'p, I>(
 where
     I: Iterator<Item = &'p str>,
 {
-    let Some(choice) = (match parser.parse_next_assigned_value() {
+    let Some(choice) = (match self::parse_value(parser) {
         Ok(choice) => choice,
         Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
     }) else {
         return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing time visibility"));
     };
 
-    let choice = match choice {
-        "hide" => TimeVisibility::Hide,
-        "simple" => TimeVisibility::Simple,
-        "iso8601" => TimeVisibility::Iso8601,
-        v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid time visibility '{v}'"))),
+    let choice = match choice {
+        "hide" => TimeVisibility::Hide,
+        "simple" => TimeVisibility::Simple,
+        "iso8601" => TimeVisibility::Iso8601,
+        v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid time visibility '{v}'"))),
+    };
+
+    let Some(SubCommand::List(ListArguments { created, accessed, modified, changed, .. })) = arguments.command.as_mut()
+    else {
+        unreachable!();
+    };
+
+    match kind {
+        TimeSectionType::Created => *created = choice,
+        TimeSectionType::Accessed => *accessed = choice,
+        TimeSectionType::Modified => *modified = choice,
+        TimeSectionType::Changed => *changed = choice,
+    }
+
+    None
+}
+
+/// Parses the time-style command-line argument.
+fn parse_time_style<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(choice) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing time style"));
+    };
+
+    let Some(SubCommand::List(ListArguments { time_style, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *time_style = match choice {
+        "default" => TimeStyle::Default,
+        "long-iso" => TimeStyle::LongIso,
+        "full-iso" => TimeStyle::FullIso,
+        "weekday" => TimeStyle::Weekday,
+        v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid time style '{v}'"))),
+    };
+
+    None
+}
+
+/// Parses the zero-pad command-line argument.
+fn parse_zero_pad(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::List(ListArguments { zero_pad, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *zero_pad = true;
+
+    None
+}
+
+/// Parses the dir-size command-line argument.
+fn parse_dir_size<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(choice) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing directory size choice"));
+    };
+
+    let Some(SubCommand::List(ListArguments { dir_size, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *dir_size = match choice {
+        "hide" => DirectorySize::Hide,
+        "child-count" => DirectorySize::ChildCount,
+        v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid directory size choice '{v}'"))),
+    };
+
+    None
+}
+
+/// Parses the total command-line argument.
+fn parse_total(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::List(ListArguments { total, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *total = true;
+
+    None
+}
+
+/// Parses the blocks command-line argument.
+fn parse_blocks(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(command) = arguments.command.as_mut() else { unreachable!() };
+
+    match command {
+        SubCommand::List(arguments) => arguments.blocks = true,
+        SubCommand::Tree(_) | SubCommand::Completions(_) => unreachable!(),
+    }
+
+    None
+}
+
+/// Parses the block size command-line argument.
+fn parse_block_size<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(choice) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing block size"));
+    };
+
+    let Some(SubCommand::List(ListArguments { block_size, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    let Some(size) = BlockSize::from_unit(choice) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid block size '{choice}'")));
+    };
+
+    *block_size = size;
+
+    None
+}
+
+/// Parses the sparse command-line argument.
+fn parse_sparse(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::List(ListArguments { sparse, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *sparse = true;
+
+    None
+}
+
+/// Parses the since command-line argument.
+fn parse_since<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(choice) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing date"));
+    };
+
+    let Some(SubCommand::List(ListArguments { since, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    let Some(date) = self::parse_date(choice) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid date '{choice}'")));
+    };
+
+    *since = Some(date);
+
+    None
+}
+
+/// Parses the until command-line argument.
+fn parse_until<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(choice) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing date"));
+    };
+
+    let Some(SubCommand::List(ListArguments { until, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    let Some(date) = self::parse_date(choice) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid date '{choice}'")));
+    };
+
+    *until = Some(date);
+
+    None
+}
+
+/// Parses the given date, which is either a full RFC 3339 timestamp or a date alone.
+///
+/// Dates given alone refer to the start of that day in the local time zone.
+fn parse_date(date: &str) -> Option<SystemTime> {
+    if let Ok(date_time) = OffsetDateTime::parse(date, &Rfc3339) {
+        return Some(date_time.into());
+    }
+
+    let date = Date::parse(date, time::macros::format_description!("[year]-[month]-[day]")).ok()?;
+
+    Some(date.midnight().assume_offset(crate::section::time::local_offset()).into())
+}
+
+/// Parses the user command-line argument.
+fn parse_user(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(command) = arguments.command.as_mut() else { unreachable!() };
+
+    match command {
+        SubCommand::List(arguments) => arguments.user = true,
+        SubCommand::Tree(_) | SubCommand::Completions(_) => unreachable!(),
+    }
+
+    None
+}
+
+/// Parses the group command-line argument.
+fn parse_group(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(command) = arguments.command.as_mut() else { unreachable!() };
+
+    match command {
+        SubCommand::List(arguments) => arguments.group = true,
+        SubCommand::Tree(_) | SubCommand::Completions(_) => unreachable!(),
+    }
+
+    None
+}
+
+/// Parses the context command-line argument.
+fn parse_context(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::List(ListArguments { context, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *context = true;
+
+    None
+}
+
+/// Parses the columns command-line argument.
+///
+/// The name column is always shown, and is placed last if it was not given.
+fn parse_columns<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(names) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing columns"));
+    };
+
+    let Some(SubCommand::List(ListArguments { columns, .. })) = arguments.command.as_mut() else { unreachable!() };
+    let columns = columns.insert(Vec::new());
+
+    for name in names.split(',') {
+        let column = match name {
+            "mode" => ColumnKind::Mode,
+            "size" => ColumnKind::Size,
+            "blocks" => ColumnKind::Blocks,
+            "disk" => ColumnKind::Disk,
+            "created" => ColumnKind::Created,
+            "accessed" => ColumnKind::Accessed,
+            "modified" => ColumnKind::Modified,
+            "changed" => ColumnKind::Changed,
+            "user" => ColumnKind::User,
+            "group" => ColumnKind::Group,
+            "context" => ColumnKind::Context,
+            "name" => ColumnKind::Name,
+            v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid column '{v}'"))),
+        };
+
+        if columns.contains(&column) {
+            return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("duplicate column '{name}'")));
+        }
+
+        columns.push(column);
+    }
+
+    if !columns.contains(&ColumnKind::Name) {
+        columns.push(ColumnKind::Name);
+    }
+
+    None
+}
+
+/// Parses the header command-line argument.
+fn parse_header(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(command) = arguments.command.as_mut() else { unreachable!() };
+
+    match command {
+        SubCommand::List(arguments) => arguments.header = true,
+        SubCommand::Tree(_) | SubCommand::Completions(_) => unreachable!(),
+    }
+
+    None
+}
+
+/// Parses the link-target-meta command-line argument.
+fn parse_link_target_meta(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(command) = arguments.command.as_mut() else { unreachable!() };
+
+    match command {
+        SubCommand::List(arguments) => arguments.link_target_meta = true,
+        SubCommand::Tree(_) | SubCommand::Completions(_) => unreachable!(),
+    }
+
+    None
+}
+
+/// Parses the recursive command-line argument.
+fn parse_recursive(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::List(ListArguments { recursive, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *recursive = true;
+
+    None
+}
+
+/// Parses the tree command-line argument.
+fn parse_tree(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::List(ListArguments { tree, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *tree = true;
+
+    None
+}
+
+/// Parses the no-headers command-line argument.
+fn parse_no_headers(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::List(ListArguments { no_headers, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *no_headers = true;
+
+    None
+}
+
+/// Parses the no-separators command-line argument.
+fn parse_no_separators(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::List(ListArguments { no_separators, .. })) = arguments.command.as_mut() else {
+        unreachable!()
     };
 
-    let Some(SubCommand::List(ListArguments { created, accessed, modified, .. })) = arguments.command.as_mut() else {
-        unreachable!();
+    *no_separators = true;
+
+    None
+}
+
+/// Parses the find-hardlinks command-line argument.
+fn parse_find_hardlinks(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::List(ListArguments { find_hardlinks, .. })) = arguments.command.as_mut() else {
+        unreachable!()
     };
 
-    match kind {
-        TimeSectionType::Created => *created = choice,
-        TimeSectionType::Accessed => *accessed = choice,
-        TimeSectionType::Modified => *modified = choice,
-    }
+    *find_hardlinks = true;
 
     None
 }
 
-/// Parses the user command-line argument.
-fn parse_user(arguments: &mut Arguments) -> Option<ParseResult> {
+/// Parses the dir-only command-line argument.
+fn parse_dir_only(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::List(ListArguments { dir_only, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *dir_only = true;
+
+    None
+}
+
+/// Parses the relative command-line argument.
+fn parse_relative(arguments: &mut Arguments) -> Option<ParseResult> {
     let Some(command) = arguments.command.as_mut() else { unreachable!() };
 
     match command {
-        SubCommand::List(arguments) => arguments.user = true,
-        SubCommand::Tree(_) => unreachable!(),
+        SubCommand::List(arguments) => arguments.relative = true,
+        SubCommand::Tree(_) | SubCommand::Completions(_) => unreachable!(),
     }
 
     None
 }
 
-/// Parses the group command-line argument.
-fn parse_group(arguments: &mut Arguments) -> Option<ParseResult> {
+/// Parses the print0 command-line argument.
+fn parse_print0(arguments: &mut Arguments) -> Option<ParseResult> {
     let Some(command) = arguments.command.as_mut() else { unreachable!() };
 
     match command {
-        SubCommand::List(arguments) => arguments.group = true,
-        SubCommand::Tree(_) => unreachable!(),
+        SubCommand::List(arguments) => arguments.print0 = true,
+        SubCommand::Tree(_) | SubCommand::Completions(_) => unreachable!(),
+    }
+
+    None
+}
+
+/// Parses the paths-from command-line argument.
+///
+/// Each non-empty line of the file is treated as a path, and lines that cannot be resolved are skipped with a warning.
+fn parse_paths_from<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(file) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing paths file"));
+    };
+    let contents = match std::fs::read(file) {
+        Ok(contents) => contents,
+        Err(error) => return Some(self::exit_and_print(ERROR_GENERIC, format_args!("{file}: {error}"))),
+    };
+
+    let paths = match arguments.command.as_mut() {
+        None | Some(SubCommand::Completions(_)) => unreachable!(),
+        Some(SubCommand::List(arguments)) => &mut arguments.paths,
+        Some(SubCommand::Tree(arguments)) => &mut arguments.paths,
+    };
+
+    for line in contents.split(|byte| *byte == b'\n').filter(|line| !line.is_empty()) {
+        let line = Path::new(OsStr::from_bytes(line));
+
+        match line.canonicalize().map(PathBuf::into_boxed_path) {
+            Ok(path) if !paths.contains(&path) => paths.push(path),
+            Ok(_) => {}
+            Err(error) => eprintln!("warning: skipping `{}`: {error}", line.display()),
+        }
     }
 
     None
@@ -516,7 +2088,7 @@ fn parse_exclude<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str,
 where
     I: Iterator<Item = &'p str>,
 {
-    let Some(path) = (match parser.parse_next_assigned_value() {
+    let Some(path) = (match self::parse_value(parser) {
         Ok(choice) => choice,
         Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
     }) else {
@@ -528,7 +2100,7 @@ where
     };
 
     match arguments.command.as_mut() {
-        None => unreachable!(),
+        None | Some(SubCommand::Completions(_)) => unreachable!(),
         Some(SubCommand::List(arguments)) => arguments.excluded.get_or_insert_default().insert(path),
         Some(SubCommand::Tree(arguments)) => arguments.excluded.get_or_insert_default().insert(path),
     };
@@ -536,12 +2108,53 @@ where
     None
 }
 
+/// Parses the exclude-from command-line argument.
+fn parse_exclude_from<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(file) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing exclusions file"));
+    };
+    let contents = match std::fs::read(file) {
+        Ok(contents) => contents,
+        Err(error) => return Some(self::exit_and_print(ERROR_GENERIC, format_args!("{file}: {error}"))),
+    };
+
+    let excluded = match arguments.command.as_mut() {
+        None | Some(SubCommand::Completions(_)) => unreachable!(),
+        Some(SubCommand::List(arguments)) => arguments.excluded.get_or_insert_default(),
+        Some(SubCommand::Tree(arguments)) => arguments.excluded.get_or_insert_default(),
+    };
+
+    for line in contents.split(|byte| *byte == b'\n').map(<[u8]>::trim_ascii) {
+        if line.is_empty() || line.starts_with(b"#") {
+            continue;
+        }
+
+        let line = Path::new(OsStr::from_bytes(line));
+
+        // Exclusions that do not exist cannot match anything, so they are only worth a warning.
+        match line.canonicalize() {
+            Ok(path) => {
+                excluded.insert(path.into_boxed_path());
+            }
+            Err(error) => eprintln!("warning: skipping `{}`: {error}", line.display()),
+        }
+    }
+
+    None
+}
+
 /// Parses the include command-line argument.
 fn parse_include<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
 where
     I: Iterator<Item = &'p str>,
 {
-    let Some(path) = (match parser.parse_next_assigned_value() {
+    let Some(path) = (match self::parse_value(parser) {
         Ok(choice) => choice,
         Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
     }) else {
@@ -553,7 +2166,7 @@ where
     };
 
     match arguments.command.as_mut() {
-        None => unreachable!(),
+        None | Some(SubCommand::Completions(_)) => unreachable!(),
         Some(SubCommand::List(arguments)) => arguments.included.get_or_insert_default().insert(path),
         Some(SubCommand::Tree(arguments)) => arguments.included.get_or_insert_default().insert(path),
     };
@@ -566,21 +2179,25 @@ fn parse_depth<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>
 where
     I: Iterator<Item = &'p str>,
 {
-    let Some(choice) = (match parser.parse_next_assigned_value() {
+    let Some(choice) = (match self::parse_value(parser) {
         Ok(choice) => choice,
         Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
     }) else {
         return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing traversal depth"));
     };
 
-    let Some(SubCommand::Tree(TreeArguments { max_depth, .. })) = arguments.command.as_mut() else { unreachable!() };
+    let Some(SubCommand::List(ListArguments { max_depth, .. }) | SubCommand::Tree(TreeArguments { max_depth, .. })) =
+        arguments.command.as_mut()
+    else {
+        unreachable!();
+    };
 
     *max_depth = Some(match choice.parse() {
         Ok(value) => value,
         Err(error) => {
             return Some(self::exit_and_print(ERROR_CLI_USAGE, match error.kind() {
                 IntErrorKind::Empty => "missing traversal depth",
-                IntErrorKind::Zero | IntErrorKind::InvalidDigit => "depth must be a non-zero positive integer",
+                IntErrorKind::InvalidDigit => "depth must be a positive integer or zero",
                 IntErrorKind::PosOverflow => "depth is too large",
                 IntErrorKind::NegOverflow => "depth is too small",
                 _ => "invalid depth",
@@ -590,3 +2207,245 @@ where
 
     None
 }
+
+/// Parses the max-entries command-line argument.
+fn parse_max_entries<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(choice) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing entry count"));
+    };
+
+    let Some(SubCommand::List(ListArguments { max_entries, .. }) | SubCommand::Tree(TreeArguments { max_entries, .. })) =
+        arguments.command.as_mut()
+    else {
+        unreachable!();
+    };
+
+    *max_entries = Some(match choice.parse() {
+        Ok(value) => value,
+        Err(error) => {
+            return Some(self::exit_and_print(ERROR_CLI_USAGE, match error.kind() {
+                IntErrorKind::Empty => "missing entry count",
+                IntErrorKind::Zero | IntErrorKind::InvalidDigit => "entry count must be a non-zero positive integer",
+                IntErrorKind::PosOverflow => "entry count is too large",
+                IntErrorKind::NegOverflow => "entry count is too small",
+                _ => "invalid entry count",
+            }));
+        }
+    });
+
+    None
+}
+
+/// Parses the narrow command-line argument.
+fn parse_narrow(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::Tree(TreeArguments { narrow, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *narrow = true;
+
+    None
+}
+
+/// Parses the tree color command-line argument.
+fn parse_tree_color<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(choice) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing tree color"));
+    };
+
+    let Some(SubCommand::Tree(TreeArguments { tree_color, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *tree_color = match choice {
+        "default" => TreeColor::Default,
+        "cycle" => TreeColor::Cycle,
+        v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid tree color '{v}'"))),
+    };
+
+    None
+}
+
+/// Parses the ascii and indent command-line arguments.
+fn parse_tree_style(arguments: &mut Arguments, style: TreeStyle) -> Option<ParseResult> {
+    let Some(SubCommand::Tree(TreeArguments { tree_style, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *tree_style = style;
+
+    None
+}
+
+/// Parses the no-root command-line argument.
+fn parse_no_root(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::Tree(TreeArguments { no_root, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *no_root = true;
+
+    None
+}
+
+/// Parses the leaves-only command-line argument.
+fn parse_leaves_only(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::Tree(TreeArguments { leaves_only, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *leaves_only = true;
+
+    None
+}
+
+/// Parses the full-path command-line argument.
+fn parse_full_path(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::Tree(TreeArguments { full_path, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *full_path = true;
+
+    None
+}
+
+/// Parses the flat command-line argument.
+fn parse_flat(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::Tree(TreeArguments { flat, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *flat = true;
+
+    None
+}
+
+/// Parses the stats command-line argument.
+fn parse_stats(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::Tree(TreeArguments { stats, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *stats = true;
+
+    None
+}
+
+/// Parses the counts command-line argument.
+fn parse_counts(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(SubCommand::Tree(TreeArguments { counts, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *counts = true;
+
+    None
+}
+
+/// Parses the progress command-line argument.
+fn parse_progress(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(command) = arguments.command.as_mut() else { unreachable!() };
+
+    match command {
+        SubCommand::List(arguments) => arguments.progress = true,
+        SubCommand::Tree(arguments) => arguments.progress = true,
+        SubCommand::Completions(_) => unreachable!(),
+    }
+
+    None
+}
+
+/// Parses the sort-paths command-line argument.
+fn parse_sort_paths(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(command) = arguments.command.as_mut() else { unreachable!() };
+
+    match command {
+        SubCommand::List(arguments) => arguments.sort_paths = true,
+        SubCommand::Tree(arguments) => arguments.sort_paths = true,
+        SubCommand::Completions(_) => unreachable!(),
+    }
+
+    None
+}
+
+/// Parses the set-title command-line argument.
+fn parse_set_title(arguments: &mut Arguments) -> Option<ParseResult> {
+    let Some(command) = arguments.command.as_mut() else { unreachable!() };
+
+    match command {
+        SubCommand::List(arguments) => arguments.set_title = true,
+        SubCommand::Tree(arguments) => arguments.set_title = true,
+        SubCommand::Completions(_) => unreachable!(),
+    }
+
+    None
+}
+
+/// Parses the format command-line argument.
+fn parse_format<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(choice) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing output format"));
+    };
+
+    let Some(SubCommand::List(ListArguments { format, .. })) = arguments.command.as_mut() else { unreachable!() };
+
+    *format = match choice {
+        "default" => OutputFormat::Default,
+        "csv" => OutputFormat::Csv,
+        v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid output format '{v}'"))),
+    };
+
+    None
+}
+
+/// Parses the pager command-line argument.
+fn parse_pager<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(choice) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing pager choice"));
+    };
+
+    let choice = match choice {
+        "auto" => PagerChoice::Auto,
+        "always" => PagerChoice::Always,
+        "never" => PagerChoice::Never,
+        v => return Some(self::exit_and_print(ERROR_CLI_USAGE, format_args!("invalid pager choice '{v}'"))),
+    };
+
+    match arguments.command.as_mut() {
+        Some(SubCommand::List(arguments)) => arguments.pager = choice,
+        Some(SubCommand::Tree(arguments)) => arguments.pager = choice,
+        None | Some(SubCommand::Completions(_)) => unreachable!(),
+    }
+
+    None
+}
+
+/// Parses the output command-line argument.
+fn parse_output<'p, I>(arguments: &mut Arguments, parser: &mut Parser<&'p str, I>) -> Option<ParseResult>
+where
+    I: Iterator<Item = &'p str>,
+{
+    let Some(path) = (match self::parse_value(parser) {
+        Ok(choice) => choice,
+        Err(error) => return Some(self::exit_and_print(ERROR_CLI_USAGE, error)),
+    }) else {
+        return Some(self::exit_and_print(ERROR_CLI_USAGE, "missing output file"));
+    };
+
+    let Some(SubCommand::List(ListArguments { output, .. }) | SubCommand::Tree(TreeArguments { output, .. })) =
+        arguments.command.as_mut()
+    else {
+        unreachable!();
+    };
+
+    *output = Some(Box::from(Path::new(path)));
+
+    None
+}
@@ -16,21 +16,28 @@
 
 //! Defines the command's argument data types.
 
-use std::collections::HashSet;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
 use std::fs::Metadata;
 use std::num::NonZero;
 use std::os::unix::fs::MetadataExt;
 use std::path::Path;
+use std::time::SystemTime;
 
+use feruca::Collator;
 use recomposition::sort::Sort;
 
 use crate::arguments::schema::CommandSchema;
+use crate::files::SortChildren;
 
 /// The program's command-line arguments.
 #[derive(Default)]
 pub struct Arguments {
     /// Determines whether to output using color.
     pub color: ColorChoice,
+    /// The width that output is wrapped to, overriding the terminal's width if given.
+    pub width: Option<usize>,
     /// The program's selected sub-command.
     pub command: Option<SubCommand>,
 }
@@ -56,6 +63,7 @@ impl Arguments {
             None => super::SCHEMA,
             Some(SubCommand::List(..)) => sub_schema(0),
             Some(SubCommand::Tree(..)) => sub_schema(1),
+            Some(SubCommand::Completions(..)) => sub_schema(2),
         }
     }
 }
@@ -70,6 +78,8 @@ pub enum ColorChoice {
     Always,
     /// Never output with color.
     Never,
+    /// Always output with color, written as HTML.
+    Html,
 }
 
 impl ColorChoice {
@@ -96,6 +106,138 @@ impl ColorChoice {
     pub const fn is_never(&self) -> bool {
         matches!(self, Self::Never)
     }
+
+    /// Returns `true` if the color choice is [`Html`].
+    ///
+    /// [`Html`]: ColorChoice::Html
+    #[must_use]
+    pub const fn is_html(&self) -> bool {
+        matches!(self, Self::Html)
+    }
+
+    /// Returns `true` if output should use color, checking whether standard output supports it if automatic.
+    ///
+    /// Terminals that declare themselves as `dumb` through the `TERM` environment variable never automatically use
+    /// color, even if standard output is a terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fvr::arguments::model::ColorChoice;
+    ///
+    /// // SAFETY: This example does not spawn any other threads that could read the environment.
+    /// unsafe { std::env::set_var("TERM", "dumb") };
+    ///
+    /// assert!(!ColorChoice::Auto.resolve());
+    /// assert!(ColorChoice::Always.resolve());
+    /// assert!(!ColorChoice::Never.resolve());
+    /// ```
+    #[must_use]
+    pub fn resolve(self) -> bool {
+        use supports_color::{Stream, on_cached};
+
+        match self {
+            Self::Auto if std::env::var_os("TERM").is_some_and(|term| term == "dumb") => false,
+            Self::Auto => on_cached(Stream::Stdout).is_some_and(|v| v.has_basic),
+            Self::Always | Self::Html => true,
+            Self::Never => false,
+        }
+    }
+}
+
+/// Determines whether to write output through a pager.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PagerChoice {
+    /// Only use a pager if the output would not fit within the terminal.
+    Auto,
+    /// Always use a pager when writing into a terminal.
+    Always,
+    /// Never use a pager.
+    #[default]
+    Never,
+}
+
+impl PagerChoice {
+    /// Returns `true` if the pager choice is [`Auto`].
+    ///
+    /// [`Auto`]: PagerChoice::Auto
+    #[must_use]
+    pub const fn is_auto(&self) -> bool {
+        matches!(self, Self::Auto)
+    }
+
+    /// Returns `true` if the pager choice is [`Always`].
+    ///
+    /// [`Always`]: PagerChoice::Always
+    #[must_use]
+    pub const fn is_always(&self) -> bool {
+        matches!(self, Self::Always)
+    }
+
+    /// Returns `true` if the pager choice is [`Never`].
+    ///
+    /// [`Never`]: PagerChoice::Never
+    #[must_use]
+    pub const fn is_never(&self) -> bool {
+        matches!(self, Self::Never)
+    }
+
+    /// Returns `true` if the given output should be written through a pager.
+    ///
+    /// This assumes that the output is being written into a terminal, as pagers are meant to be read interactively. The
+    /// height should be that of the terminal, or [`None`] if it is unknown, in which case only [`Always`] pages.
+    ///
+    /// [`Always`]: PagerChoice::Always
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fvr::arguments::model::PagerChoice;
+    ///
+    /// let output = "line\n".repeat(100);
+    ///
+    /// assert!(!PagerChoice::Never.should_page(output.as_bytes(), Some(24)));
+    /// assert!(PagerChoice::Always.should_page(b"line\n", None));
+    /// assert!(PagerChoice::Auto.should_page(output.as_bytes(), Some(24)));
+    /// assert!(!PagerChoice::Auto.should_page(b"line\n", Some(24)));
+    /// ```
+    #[must_use]
+    pub fn should_page(self, output: &[u8], height: Option<usize>) -> bool {
+        match self {
+            // The shell's prompt is written after the output's last line, so it also needs to fit on screen.
+            Self::Auto => height.is_some_and(|height| output.split(|byte| *byte == b'\n').count() > height),
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+/// Determines how listed entries are written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Write entries using the selected columns.
+    #[default]
+    Default,
+    /// Write entries as comma-separated values, with a header row and a fixed set of columns.
+    Csv,
+}
+
+impl OutputFormat {
+    /// Returns `true` if the output format is [`Default`].
+    ///
+    /// [`Default`]: OutputFormat::Default
+    #[must_use]
+    pub const fn is_default(&self) -> bool {
+        matches!(self, Self::Default)
+    }
+
+    /// Returns `true` if the output format is [`Csv`].
+    ///
+    /// [`Csv`]: OutputFormat::Csv
+    #[must_use]
+    pub const fn is_csv(&self) -> bool {
+        matches!(self, Self::Csv)
+    }
 }
 
 /// The program's sub-command.
@@ -104,6 +246,8 @@ pub enum SubCommand {
     List(ListArguments),
     /// The tree sub-command.
     Tree(TreeArguments),
+    /// The completions sub-command.
+    Completions(CompletionsArguments),
 }
 
 impl SubCommand {
@@ -123,6 +267,23 @@ impl SubCommand {
         matches!(self, Self::Tree(..))
     }
 
+    /// Returns `true` if the sub-command is [`Completions`].
+    ///
+    /// [`Completions`]: SubCommand::Completions
+    #[must_use]
+    pub const fn is_completions(&self) -> bool {
+        matches!(self, Self::Completions(..))
+    }
+
+    /// Returns `true` if the sub-command is either [`List`] or [`Tree`].
+    ///
+    /// [`List`]: SubCommand::List
+    /// [`Tree`]: SubCommand::Tree
+    #[must_use]
+    pub const fn is_list_or_tree(&self) -> bool {
+        matches!(self, Self::List(..) | Self::Tree(..))
+    }
+
     /// Returns the inner value of this sub-command if it is a [`List`].
     ///
     /// [`List`]: SubCommand::List
@@ -138,6 +299,14 @@ impl SubCommand {
     pub const fn as_tree(&self) -> Option<&TreeArguments> {
         if let Self::Tree(v) = self { Some(v) } else { None }
     }
+
+    /// Returns the inner value of this sub-command if it is a [`Completions`].
+    ///
+    /// [`Completions`]: SubCommand::Completions
+    #[must_use]
+    pub const fn as_completions(&self) -> Option<&CompletionsArguments> {
+        if let Self::Completions(v) = self { Some(v) } else { None }
+    }
 }
 
 /// The program's command-line arguments for the list sub-command.
@@ -146,64 +315,273 @@ impl SubCommand {
 pub struct ListArguments {
     /// The paths to list.
     pub paths: Vec<Box<Path>>,
+    /// Whether to list the paths in sorted order rather than the order that they were given in.
+    pub sort_paths: bool,
     /// Whether to show hidden files.
     pub show_hidden: bool,
+    /// The glob patterns that hide matching entry names.
+    pub hide_patterns: Vec<Box<str>>,
+    /// The glob patterns that entry names must match one of to be shown, if any are given.
+    pub name_patterns: Vec<Box<str>>,
+    /// Whether to exclude entries matched by `.gitignore` files.
+    pub gitignore: bool,
     /// Whether to resolve symbolic links.
     pub resolve_symlinks: bool,
+    /// Whether to color files based on their contents.
+    pub mime: bool,
+    /// Determines how special characters within names are written.
+    pub quoting: Quoting,
+    /// The maximum number of columns that names may take up before being truncated.
+    pub max_name_width: Option<NonZero<usize>>,
     /// The preferred sorting function.
     pub sorting: Option<SortOrder>,
+    /// Whether to skip sorting entirely.
+    pub unsorted: bool,
+    /// The seed used to shuffle entries when sorting randomly, if one was given.
+    pub seed: Option<u64>,
+    /// Determines how names are compared when sorting by name.
+    pub collation: Collation,
     /// The preferred mode visibility.
     pub mode: ModeVisibility,
     /// The preferred size visibility.
     pub size: SizeVisibility,
+    /// Whether to pad simple sizes with leading zeros, rather than trailing spaces.
+    pub zero_pad: bool,
+    /// Determines what is shown in place of a directory's size.
+    pub dir_size: DirectorySize,
+    /// Whether to show the total size of each listing's entries beneath them.
+    pub total: bool,
+    /// Whether to show allocated blocks.
+    pub blocks: bool,
+    /// The unit that allocated blocks are counted in.
+    pub block_size: BlockSize,
+    /// Whether to show disk usage, marking sparse files.
+    pub sparse: bool,
     /// The preferred creation date visibility.
     pub created: TimeVisibility,
     /// The preferred access date visibility.
     pub accessed: TimeVisibility,
     /// The preferred modification date visibility.
     pub modified: TimeVisibility,
+    /// The preferred status change date visibility.
+    pub changed: TimeVisibility,
+    /// The preferred format of dates shown in the simple format.
+    pub time_style: TimeStyle,
+    /// The earliest modification date of shown entries, inclusive.
+    pub since: Option<SystemTime>,
+    /// The latest modification date of shown entries, exclusive.
+    pub until: Option<SystemTime>,
     /// Whether to show owner users.
     pub user: bool,
     /// Whether to show owner groups.
     pub group: bool,
+    /// Whether to show security contexts.
+    pub context: bool,
+    /// The columns to show and their order, if explicitly requested.
+    pub columns: Option<Vec<ColumnKind>>,
+    /// Whether to print a header row naming each column.
+    pub header: bool,
+    /// Whether to show the metadata of symbolic link targets in place of the links themselves.
+    pub link_target_meta: bool,
+    /// Whether to show paths relative to the current directory.
+    pub relative: bool,
+    /// Whether to list the contents of directories recursively.
+    pub recursive: bool,
+    /// Whether to list the contents of directories as a tree, drawn beside each entry's metadata.
+    pub tree: bool,
+    /// The depth of listed trees, where zero shows only the listed paths and [`None`] is unlimited.
+    pub max_depth: Option<usize>,
+    /// Whether to omit the name written above the contents of each listed directory.
+    pub no_headers: bool,
+    /// Whether to omit the blank line written between the contents of each listed directory.
+    pub no_separators: bool,
+    /// Whether to mark files that share an inode with another listed file.
+    pub find_hardlinks: bool,
+    /// Whether to list directories themselves, rather than their contents.
+    pub dir_only: bool,
+    /// Whether to print only null-delimited entry paths.
+    pub print0: bool,
+    /// Determines how listed entries are written.
+    pub format: OutputFormat,
     /// The paths to exclude.
     pub excluded: Option<HashSet<Box<Path>>>,
     /// The paths to include.
     pub included: Option<HashSet<Box<Path>>>,
+    /// The maximum number of entries to display.
+    pub max_entries: Option<NonZero<usize>>,
+    /// Whether to report how many entries have been scanned to standard error.
+    pub progress: bool,
+    /// Whether to set the terminal's title to the listed paths.
+    pub set_title: bool,
+    /// Whether to write output through a pager.
+    pub pager: PagerChoice,
+    /// The file to write into instead of standard output.
+    pub output: Option<Box<Path>>,
 }
 
 /// The program's command-line arguments for the tree sub-command.
 #[derive(Default)]
+#[expect(clippy::struct_excessive_bools, reason = "such is the nature of command-line flags")]
 pub struct TreeArguments {
     /// The paths to list.
     pub paths: Vec<Box<Path>>,
+    /// Whether to list the paths in sorted order rather than the order that they were given in.
+    pub sort_paths: bool,
     /// Whether to show hidden files.
     pub show_hidden: bool,
+    /// The glob patterns that hide matching entry names.
+    pub hide_patterns: Vec<Box<str>>,
+    /// The glob patterns that entry names must match one of to be shown, if any are given.
+    pub name_patterns: Vec<Box<str>>,
+    /// Whether to exclude entries matched by `.gitignore` files.
+    pub gitignore: bool,
     /// Whether to resolve symbolic links.
     pub resolve_symlinks: bool,
+    /// Whether to color files based on their contents.
+    pub mime: bool,
+    /// Determines how special characters within names are written.
+    pub quoting: Quoting,
+    /// The maximum number of columns that names may take up before being truncated.
+    pub max_name_width: Option<NonZero<usize>>,
     /// The preferred sorting function.
     pub sorting: Option<SortOrder>,
+    /// Whether to skip sorting entirely.
+    pub unsorted: bool,
+    /// The seed used to shuffle entries when sorting randomly, if one was given.
+    pub seed: Option<u64>,
+    /// Determines how names are compared when sorting by name.
+    pub collation: Collation,
     /// The paths to exclude.
     pub excluded: Option<HashSet<Box<Path>>>,
     /// The paths to include.
     pub included: Option<HashSet<Box<Path>>>,
-    /// The depth of the search.
-    pub max_depth: Option<NonZero<usize>>,
+    /// The maximum number of entries to display.
+    pub max_entries: Option<NonZero<usize>>,
+    /// Whether to report how many entries have been scanned to standard error.
+    pub progress: bool,
+    /// Whether to set the terminal's title to the listed paths.
+    pub set_title: bool,
+    /// The depth of the search, where zero shows only the listed paths and [`None`] is unlimited.
+    pub max_depth: Option<usize>,
+    /// Whether to indent each level of the tree by a single column.
+    pub narrow: bool,
+    /// The preferred branch coloring.
+    pub tree_color: TreeColor,
+    /// The characters that branches are drawn with.
+    pub tree_style: TreeStyle,
+    /// Whether to write a summary of the shown entries after the tree.
+    pub stats: bool,
+    /// Whether to write the number of entries within each directory after its name.
+    pub counts: bool,
+    /// Whether to omit each root entry, starting the tree with its children.
+    pub no_root: bool,
+    /// Whether to only show entries that have no children.
+    pub leaves_only: bool,
+    /// Whether to show each entry's path relative to its root rather than just its name.
+    pub full_path: bool,
+    /// Whether to omit the tree's branches, showing each entry's full path on its own line.
+    pub flat: bool,
+    /// Whether to write output through a pager.
+    pub pager: PagerChoice,
+    /// The file to write into instead of standard output.
+    pub output: Option<Box<Path>>,
+}
+
+/// The program's command-line arguments for the completions sub-command.
+#[derive(Default)]
+pub struct CompletionsArguments {
+    /// The shell to generate completions for.
+    pub shell: Option<Shell>,
+}
+
+/// A shell that completion scripts may be generated for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    /// The Bourne Again shell.
+    Bash,
 }
 
 /// Describes how entries should be sorted.
+///
+/// Every ordering is total, including for entries whose dates are unavailable, so it is safe to use with unstable
+/// sorts.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SortOrder {
-    /// Alphabetically.
+    /// Alphabetically, comparing the bytes of each name.
     Name,
-    /// Access date.
+    /// Alphabetically, following the Unicode Collation Algorithm's root order rather than comparing bytes.
+    ///
+    /// This places accented letters next to their unaccented forms, and only falls back to comparing bytes for names
+    /// that would otherwise be equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    ///
+    /// use fvr::arguments::model::SortOrder;
+    /// use recomposition::sort::Sort;
+    ///
+    /// let data = std::fs::metadata("Cargo.toml")?;
+    /// let entry = |path: &str| (std::path::Path::new(path).into(), data.clone());
+    ///
+    /// assert_eq!(SortOrder::Name.compare(&entry("Émile"), &entry("Fabian")), Ordering::Greater);
+    /// assert_eq!(SortOrder::CollatedName.compare(&entry("Émile"), &entry("Fabian")), Ordering::Less);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    CollatedName,
+    /// File name length in bytes, shortest first and falling back to alphabetical order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    ///
+    /// use fvr::arguments::model::SortOrder;
+    /// use recomposition::sort::Sort;
+    ///
+    /// let data = std::fs::metadata("Cargo.toml")?;
+    /// let entry = |path: &str| (std::path::Path::new(path).into(), data.clone());
+    ///
+    /// assert_eq!(
+    ///     SortOrder::NameLength.compare(&entry("src/main.rs"), &entry("a/lib.rs")),
+    ///     Ordering::Greater
+    /// );
+    /// assert_eq!(
+    ///     SortOrder::NameLength.compare(&entry("src/b.rs"), &entry("src/c.rs")),
+    ///     Ordering::Less
+    /// );
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    NameLength,
+    /// File extension, with entries that have no extension first.
+    Extension,
+    /// Access date, falling back to the modification date if unavailable.
+    ///
+    /// File systems mounted with `noatime` rarely update access dates, so this may not reflect actual usage.
     Accessed,
     /// Creation date.
+    ///
+    /// Many Linux file systems do not record creation dates, in which case the commands sort by modification date.
     Created,
     /// Modification date.
     Modified,
+    /// Status change date, which is updated whenever an entry's contents or metadata change.
+    ChangeTime,
     /// File size.
+    ///
+    /// Directories are treated as having a size of zero, since their own size only reflects how their entries are
+    /// stored. Use [`TotalSize`] to sort directories by the size of their contents instead.
+    ///
+    /// [`TotalSize`]: SortOrder::TotalSize
     Size,
+    /// File size, including the contents of directories.
+    TotalSize,
+    /// Depth from the listed path, such that shallower entries come first.
+    ///
+    /// Directories are always visited before their contents, and the entries within a directory share the same depth,
+    /// so this never reorders them and instead defers to any following order.
+    Depth,
     /// Hidden files.
     Hidden,
     /// Directories.
@@ -216,6 +594,8 @@ pub enum SortOrder {
     Reverse(Box<Self>),
     /// Chained order, preferring the left-most order.
     Then(Box<(Self, Self)>),
+    /// Shuffled order, which is always the same for a given seed.
+    Random(u64),
 }
 
 impl SortOrder {
@@ -236,6 +616,96 @@ impl SortOrder {
         }
     }
 
+    /// Returns `true` if this order sorts by creation date at any point.
+    #[must_use]
+    pub fn uses_created(&self) -> bool {
+        match self {
+            Self::Created => true,
+            Self::Reverse(sort) => sort.uses_created(),
+            Self::Then(orders) => orders.0.uses_created() || orders.1.uses_created(),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if this order sorts by total size at any point.
+    #[must_use]
+    pub fn uses_total_size(&self) -> bool {
+        match self {
+            Self::TotalSize => true,
+            Self::Reverse(sort) => sort.uses_total_size(),
+            Self::Then(orders) => orders.0.uses_total_size() || orders.1.uses_total_size(),
+            _ => false,
+        }
+    }
+
+    /// Replaces every creation date ordering within this order with a modification date ordering.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fvr::arguments::model::SortOrder;
+    ///
+    /// let mut sort = SortOrder::Directories.then(SortOrder::Created.reverse());
+    ///
+    /// sort.replace_created();
+    ///
+    /// assert_eq!(sort, SortOrder::Directories.then(SortOrder::Modified.reverse()));
+    /// ```
+    pub fn replace_created(&mut self) {
+        match self {
+            Self::Created => *self = Self::Modified,
+            Self::Reverse(sort) => sort.replace_created(),
+            Self::Then(orders) => {
+                orders.0.replace_created();
+                orders.1.replace_created();
+            }
+            _ => {}
+        }
+    }
+
+    /// Replaces every creation date ordering within this order with a modification date ordering if none of the given
+    /// roots have a creation date, returning `true` if this order was changed.
+    ///
+    /// Creation dates are missing on many Linux file systems, in which case every entry would otherwise compare equal.
+    #[must_use]
+    pub fn fall_back_from_created(&mut self, roots: &[(Box<Path>, Metadata)]) -> bool {
+        let unsupported =
+            self.uses_created() && crate::files::is_created_unsupported(roots.iter().map(|(_, data)| data.created()));
+
+        if unsupported {
+            self.replace_created();
+        }
+
+        unsupported
+    }
+
+    /// Replaces every name ordering within this order with one that uses the given collation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fvr::arguments::model::{Collation, SortOrder};
+    ///
+    /// let mut sort = SortOrder::Directories.then(SortOrder::Name.reverse());
+    ///
+    /// sort.set_collation(Collation::Unicode);
+    ///
+    /// assert_eq!(sort, SortOrder::Directories.then(SortOrder::CollatedName.reverse()));
+    /// ```
+    pub fn set_collation(&mut self, collation: Collation) {
+        match self {
+            Self::Name | Self::CollatedName => {
+                *self = if collation.is_unicode() { Self::CollatedName } else { Self::Name };
+            }
+            Self::Reverse(sort) => sort.set_collation(collation),
+            Self::Then(orders) => {
+                orders.0.set_collation(collation);
+                orders.1.set_collation(collation);
+            }
+            _ => {}
+        }
+    }
+
     /// Returns a reference to the most recent [`SortOrder`].
     #[must_use]
     pub fn top(&self) -> &Self {
@@ -248,30 +718,138 @@ impl SortOrder {
 
 impl Sort<(Box<Path>, Metadata)> for SortOrder {
     fn compare(&self, lhs: &(Box<Path>, Metadata), rhs: &(Box<Path>, Metadata)) -> std::cmp::Ordering {
-        use recomposition::sort::{order, partial_order};
+        self.compare_measured(lhs, rhs, &HashMap::new())
+    }
+}
+
+impl SortChildren for SortOrder {
+    fn sort_children(&self, children: &mut [(Box<Path>, Metadata)]) {
+        // Total sizes walk entire directories, so each is measured once up front rather than within every comparison.
+        let total_sizes = if self.uses_total_size() {
+            children
+                .iter()
+                .filter(|(_, data)| data.is_dir())
+                .map(|(path, data)| (path.clone(), crate::files::total_size(path, data)))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        children.sort_unstable_by(|lhs, rhs| self.compare_measured(lhs, rhs, &total_sizes));
+    }
+}
 
+impl SortOrder {
+    /// Compares the given entries, using the given total sizes of directories wherever they have been measured.
+    fn compare_measured(
+        &self,
+        lhs: &(Box<Path>, Metadata),
+        rhs: &(Box<Path>, Metadata),
+        total_sizes: &HashMap<Box<Path>, u64>,
+    ) -> std::cmp::Ordering {
+        use recomposition::sort::order;
+
+        // Dates are sorted newest first, and entries without a date are consistently sorted after all others.
         match self {
             Self::Name => order().map_ref(Path::as_os_str).compare(&lhs.0, &rhs.0),
-            Self::Accessed => partial_order().reverse().map(|m: &Metadata| m.accessed().ok()).compare(&lhs.1, &rhs.1),
-            Self::Created => partial_order().reverse().map(|m: &Metadata| m.created().ok()).compare(&lhs.1, &rhs.1),
-            Self::Modified => partial_order().reverse().map(|m: &Metadata| m.modified().ok()).compare(&lhs.1, &rhs.1),
-            Self::Size => order().map(MetadataExt::size).compare(&lhs.1, &rhs.1),
+            Self::CollatedName => self::collate(lhs.0.as_os_str(), rhs.0.as_os_str()),
+            Self::NameLength => order()
+                .map(|(path, _): &(Box<Path>, Metadata)| path.file_name().map_or(0, OsStr::len))
+                .compare(lhs, rhs)
+                .then_with(|| Self::Name.compare(lhs, rhs)),
+            Self::Extension => order().compare(&lhs.0.extension(), &rhs.0.extension()),
+            Self::Accessed => crate::files::compare_dates(
+                lhs.1.accessed().or_else(|_| lhs.1.modified()).ok(),
+                rhs.1.accessed().or_else(|_| rhs.1.modified()).ok(),
+            ),
+            Self::Created => crate::files::compare_dates(lhs.1.created().ok(), rhs.1.created().ok()),
+            Self::Modified => crate::files::compare_dates(lhs.1.modified().ok(), rhs.1.modified().ok()),
+            Self::ChangeTime => {
+                order().reverse().map(|m: &Metadata| (m.ctime(), m.ctime_nsec())).compare(&lhs.1, &rhs.1)
+            }
+            Self::Size => order().map(|m: &Metadata| if m.is_dir() { 0 } else { m.size() }).compare(&lhs.1, &rhs.1),
+            Self::TotalSize => order()
+                .map(|(path, data): &(Box<Path>, Metadata)| {
+                    total_sizes.get(path).copied().unwrap_or_else(|| crate::files::total_size(path, data))
+                })
+                .compare(lhs, rhs),
+            Self::Depth => std::cmp::Ordering::Equal,
             Self::Hidden => order().reverse().map(|p| crate::files::is_hidden(p)).compare(&lhs.0, &rhs.0),
             Self::Directories => order().reverse().map(Metadata::is_dir).compare(&lhs.1, &rhs.1),
             Self::Files => order().reverse().map(Metadata::is_file).compare(&lhs.1, &rhs.1),
             Self::Symlinks => order().reverse().map(Metadata::is_symlink).compare(&lhs.1, &rhs.1),
-            Self::Reverse(sort_order) => sort_order.reverse().compare(lhs, rhs),
-            Self::Then(orders) => (&orders.0).then(&orders.1).compare(lhs, rhs),
+            Self::Reverse(sort_order) => sort_order.compare_measured(lhs, rhs, total_sizes).reverse(),
+            Self::Then(orders) => orders
+                .0
+                .compare_measured(lhs, rhs, total_sizes)
+                .then_with(|| orders.1.compare_measured(lhs, rhs, total_sizes)),
+            Self::Random(seed) => order()
+                .map(|(path, _): &(Box<Path>, Metadata)| self::shuffle_key(*seed, path))
+                .compare(lhs, rhs)
+                .then_with(|| Self::Name.compare(lhs, rhs)),
         }
     }
 }
 
+/// Compares the given names using the Unicode Collation Algorithm's root order.
+fn collate(lhs: &OsStr, rhs: &OsStr) -> std::cmp::Ordering {
+    thread_local! {
+        // Collators cache the weights they have looked up, so one is kept around rather than created for each call.
+        static COLLATOR: RefCell<Collator> = RefCell::new(Collator::default());
+    }
+
+    COLLATOR.with_borrow_mut(|collator| collator.collate(lhs.as_encoded_bytes(), rhs.as_encoded_bytes()))
+}
+
+/// Returns a key for the given path that is evenly spread out for each seed, such that sorting by it shuffles entries.
+fn shuffle_key(seed: u64, path: &Path) -> u64 {
+    // This hashes the path using FNV-1a, then finishes it with the SplitMix64 mixer so that similar paths are spread
+    // out.
+    let hash =
+        path.as_os_str().as_encoded_bytes().iter().fold(0xCBF2_9CE4_8422_2325 ^ seed, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01B3)
+        });
+    let hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    let hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+    hash ^ (hash >> 31)
+}
+
 impl Default for SortOrder {
     fn default() -> Self {
         Self::Directories.then(Self::Files).then(Self::Name)
     }
 }
 
+/// A column that may be shown within a listing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnKind {
+    /// The entry's mode.
+    Mode,
+    /// The entry's size.
+    Size,
+    /// The entry's allocated blocks.
+    Blocks,
+    /// The entry's disk usage.
+    Disk,
+    /// The entry's creation date.
+    Created,
+    /// The entry's access date.
+    Accessed,
+    /// The entry's modification date.
+    Modified,
+    /// The entry's status change date.
+    Changed,
+    /// The entry's owner user.
+    User,
+    /// The entry's owner group.
+    Group,
+    /// The entry's security context.
+    Context,
+    /// The entry's name.
+    Name,
+}
+
 /// Determines whether to display an entry's Unix file mode.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum ModeVisibility {
@@ -358,6 +936,222 @@ impl SizeVisibility {
     }
 }
 
+/// Determines what is displayed in place of a directory's size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DirectorySize {
+    /// Directory sizes are not rendered.
+    #[default]
+    Hide,
+    /// Output the number of entries within the directory.
+    ChildCount,
+}
+
+impl DirectorySize {
+    /// Returns `true` if the directory size is [`Hide`].
+    ///
+    /// [`Hide`]: DirectorySize::Hide
+    #[must_use]
+    pub const fn is_hide(&self) -> bool {
+        matches!(self, Self::Hide)
+    }
+
+    /// Returns `true` if the directory size is [`ChildCount`].
+    ///
+    /// [`ChildCount`]: DirectorySize::ChildCount
+    #[must_use]
+    pub const fn is_child_count(&self) -> bool {
+        matches!(self, Self::ChildCount)
+    }
+}
+
+/// The unit that allocated blocks are counted in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockSize(NonZero<u64>);
+
+impl BlockSize {
+    /// The block size used by default, which matches `du`.
+    pub const DEFAULT: Self = Self(NonZero::new(1_024).unwrap());
+
+    /// Creates a new [`BlockSize`] of the given number of bytes.
+    #[inline]
+    #[must_use]
+    pub const fn new(bytes: NonZero<u64>) -> Self {
+        Self(bytes)
+    }
+
+    /// Parses a block size from the given unit, returning [`None`] if it is invalid or zero.
+    ///
+    /// A unit is an optional number of bytes followed by an optional suffix, where `K`, `M`, `G`, and `T` (optionally
+    /// followed by `iB`) are powers of 1024, and `KB`, `MB`, `GB`, and `TB` are powers of 1000.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fvr::arguments::model::BlockSize;
+    ///
+    /// assert_eq!(BlockSize::from_unit("512").map(BlockSize::bytes), Some(512));
+    /// assert_eq!(BlockSize::from_unit("K").map(BlockSize::bytes), Some(1_024));
+    /// assert_eq!(BlockSize::from_unit("4KiB").map(BlockSize::bytes), Some(4_096));
+    /// assert_eq!(BlockSize::from_unit("1MB").map(BlockSize::bytes), Some(1_000_000));
+    /// assert_eq!(BlockSize::from_unit("0K"), None);
+    /// assert_eq!(BlockSize::from_unit("1Q"), None);
+    /// ```
+    #[must_use]
+    pub fn from_unit(unit: &str) -> Option<Self> {
+        let split = unit.find(|c: char| !c.is_ascii_digit()).unwrap_or(unit.len());
+        let (count, suffix) = unit.split_at(split);
+        let count = if count.is_empty() { 1 } else { count.parse::<u64>().ok()? };
+
+        let (base, exponent) = match suffix {
+            "" => (1, 0),
+            "K" | "KiB" => (1_024, 1),
+            "M" | "MiB" => (1_024, 2),
+            "G" | "GiB" => (1_024, 3),
+            "T" | "TiB" => (1_024, 4),
+            "KB" => (1_000, 1),
+            "MB" => (1_000, 2),
+            "GB" => (1_000, 3),
+            "TB" => (1_000, 4),
+            _ => return None,
+        };
+
+        let bytes = count.checked_mul(u64::pow(base, exponent))?;
+
+        NonZero::new(bytes).map(Self)
+    }
+
+    /// Returns the number of bytes within a single block.
+    #[inline]
+    #[must_use]
+    pub const fn bytes(self) -> u64 {
+        self.0.get()
+    }
+}
+
+impl Default for BlockSize {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Determines how names are compared when sorting by name.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Collation {
+    /// Names are compared byte by byte, which is the same on every system.
+    #[default]
+    Bytes,
+    /// Names are compared using the Unicode Collation Algorithm's root order, such that accented letters are sorted
+    /// alongside their unaccented forms.
+    ///
+    /// This is the CLDR root order, which is the same for every locale, so `LC_COLLATE` and `LC_ALL` are not taken into
+    /// account. It may also be chosen as `locale`.
+    Unicode,
+}
+
+impl Collation {
+    /// Returns `true` if the collation is [`Bytes`].
+    ///
+    /// [`Bytes`]: Collation::Bytes
+    #[must_use]
+    pub const fn is_bytes(&self) -> bool {
+        matches!(self, Self::Bytes)
+    }
+
+    /// Returns `true` if the collation is [`Unicode`].
+    ///
+    /// [`Unicode`]: Collation::Unicode
+    #[must_use]
+    pub const fn is_unicode(&self) -> bool {
+        matches!(self, Self::Unicode)
+    }
+}
+
+/// Determines how special characters within entry names are written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Quoting {
+    /// Names are written as they are, except for the escape character, which is written as `\x1b` so that names
+    /// cannot change the terminal's colors.
+    #[default]
+    Safe,
+    /// Names are written byte-for-byte, including any control characters and ANSI escape sequences.
+    Literal,
+    /// Control characters are written as escape sequences such as `\t`, so that they cannot misalign any following
+    /// columns or be interpreted by the terminal.
+    Escape,
+    /// Names are written like [`Safe`](Quoting::Safe), except for tab characters, which are written as `\t` so
+    /// that they cannot misalign any following columns.
+    Tabs,
+    /// Names are written exactly as they are, including any ANSI escape sequences, followed by a reset so that they
+    /// cannot change the colors of anything written after them.
+    Raw,
+}
+
+impl Quoting {
+    /// Returns `true` if the quoting is [`Safe`].
+    ///
+    /// [`Safe`]: Quoting::Safe
+    #[must_use]
+    pub const fn is_safe(&self) -> bool {
+        matches!(self, Self::Safe)
+    }
+
+    /// Returns `true` if the quoting is [`Literal`].
+    ///
+    /// [`Literal`]: Quoting::Literal
+    #[must_use]
+    pub const fn is_literal(&self) -> bool {
+        matches!(self, Self::Literal)
+    }
+
+    /// Returns `true` if the quoting is [`Escape`].
+    ///
+    /// [`Escape`]: Quoting::Escape
+    #[must_use]
+    pub const fn is_escape(&self) -> bool {
+        matches!(self, Self::Escape)
+    }
+
+    /// Returns `true` if the quoting is [`Tabs`].
+    ///
+    /// [`Tabs`]: Quoting::Tabs
+    #[must_use]
+    pub const fn is_tabs(&self) -> bool {
+        matches!(self, Self::Tabs)
+    }
+
+    /// Returns `true` if the quoting is [`Raw`].
+    ///
+    /// [`Raw`]: Quoting::Raw
+    #[must_use]
+    pub const fn is_raw(&self) -> bool {
+        matches!(self, Self::Raw)
+    }
+
+    /// Returns `true` if the given byte is written as an escape sequence when using this quoting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fvr::arguments::model::Quoting;
+    ///
+    /// assert!(Quoting::Safe.escapes(0x1B));
+    /// assert!(!Quoting::Safe.escapes(b'\t'));
+    /// assert!(!Quoting::Literal.escapes(0x1B));
+    /// assert!(Quoting::Escape.escapes(b'\t'));
+    /// assert!(Quoting::Tabs.escapes(b'\t') && !Quoting::Tabs.escapes(0x07));
+    /// assert!(!Quoting::Raw.escapes(0x1B));
+    /// ```
+    #[must_use]
+    pub const fn escapes(&self, byte: u8) -> bool {
+        match self {
+            Self::Safe => byte == 0x1B,
+            Self::Escape => byte.is_ascii_control(),
+            Self::Tabs => byte == 0x1B || byte == b'\t',
+            Self::Literal | Self::Raw => false,
+        }
+    }
+}
+
 /// Determines whether to display dates.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum TimeVisibility {
@@ -395,3 +1189,117 @@ impl TimeVisibility {
         matches!(self, Self::Iso8601)
     }
 }
+
+/// Determines which format is used for dates shown in the simple format.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimeStyle {
+    /// The day, month, year, and time, such as ` 7 Mar '25 14:05`.
+    #[default]
+    Default,
+    /// The date and time in an ISO-like format, such as `2025-03-07 14:05`.
+    LongIso,
+    /// The date, time, and offset in an ISO-like format with full precision.
+    FullIso,
+    /// The default format preceded by the day of the week, such as `Fri  7 Mar '25 14:05`.
+    Weekday,
+}
+
+impl TimeStyle {
+    /// Returns `true` if the time style is [`Default`].
+    ///
+    /// [`Default`]: TimeStyle::Default
+    #[must_use]
+    pub const fn is_default(&self) -> bool {
+        matches!(self, Self::Default)
+    }
+
+    /// Returns `true` if the time style is [`LongIso`].
+    ///
+    /// [`LongIso`]: TimeStyle::LongIso
+    #[must_use]
+    pub const fn is_long_iso(&self) -> bool {
+        matches!(self, Self::LongIso)
+    }
+
+    /// Returns `true` if the time style is [`FullIso`].
+    ///
+    /// [`FullIso`]: TimeStyle::FullIso
+    #[must_use]
+    pub const fn is_full_iso(&self) -> bool {
+        matches!(self, Self::FullIso)
+    }
+
+    /// Returns `true` if the time style is [`Weekday`].
+    ///
+    /// [`Weekday`]: TimeStyle::Weekday
+    #[must_use]
+    pub const fn is_weekday(&self) -> bool {
+        matches!(self, Self::Weekday)
+    }
+}
+
+/// Determines which characters tree branches are drawn with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TreeStyle {
+    /// Branches are drawn using Unicode box-drawing characters.
+    #[default]
+    Unicode,
+    /// Branches are drawn using ASCII characters.
+    Ascii,
+    /// Branches are replaced with plain indentation.
+    Indent,
+}
+
+impl TreeStyle {
+    /// Returns `true` if the tree style is [`Unicode`].
+    ///
+    /// [`Unicode`]: TreeStyle::Unicode
+    #[must_use]
+    pub const fn is_unicode(&self) -> bool {
+        matches!(self, Self::Unicode)
+    }
+
+    /// Returns `true` if the tree style is [`Ascii`].
+    ///
+    /// [`Ascii`]: TreeStyle::Ascii
+    #[must_use]
+    pub const fn is_ascii(&self) -> bool {
+        matches!(self, Self::Ascii)
+    }
+
+    /// Returns `true` if the tree style is [`Indent`].
+    ///
+    /// [`Indent`]: TreeStyle::Indent
+    #[must_use]
+    pub const fn is_indent(&self) -> bool {
+        matches!(self, Self::Indent)
+    }
+}
+
+/// Determines how tree branches are colored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TreeColor {
+    /// Branches are rendered using a single color.
+    #[default]
+    Default,
+    /// Branches cycle through a palette of colors based on their depth.
+    Cycle,
+}
+
+impl TreeColor {
+    /// Returns `true` if the tree color is [`Default`].
+    ///
+    /// [`Default`]: TreeColor::Default
+    #[must_use]
+    pub const fn is_default(&self) -> bool {
+        matches!(self, Self::Default)
+    }
+
+    /// Returns `true` if the tree color is [`Cycle`].
+    ///
+    /// [`Cycle`]: TreeColor::Cycle
+    #[must_use]
+    pub const fn is_cycle(&self) -> bool {
+        matches!(self, Self::Cycle)
+    }
+}
@@ -75,12 +75,70 @@ const fn assert_ascii(string: &str) {
     test_indexed!(string.as_bytes(), "string must not contain control characters", |_, byte| !byte.is_ascii_control());
 }
 
+/// The width of the column containing the names of each sub-command, positional, and argument.
+const NAME_WIDTH: usize = 32;
+/// The minimum width of the description column, below which descriptions are no longer wrapped any further.
+const MIN_DESCRIPTION_WIDTH: usize = 20;
+
 /// Writes the given command schema into the provided writer as a help display.
 ///
+/// Descriptions are wrapped to fit within the given width, while remaining aligned to the same column.
+///
+/// # Examples
+///
+/// ```
+/// use fvr::arguments::schema::{
+///     ArgumentSchema, ArgumentSchemaBuilder, CommandSchema, CommandSchemaBuilder, ValueSchema,
+///     ValueSchemaBuilder,
+/// };
+///
+/// const ORDER_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("ORDER")
+///     .required()
+///     .list()
+///     .default("name")
+///     .options(&["name", "size"])
+///     .build();
+/// const PATTERN_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("PATTERN").build();
+/// const ARGUMENTS: &[ArgumentSchema<'static>] = &[
+///     ArgumentSchemaBuilder::new("sort", "Control how entries are sorted")
+///         .short('s')
+///         .value(ORDER_VALUE)
+///         .build(),
+///     ArgumentSchemaBuilder::new("all", "Include hidden entries").build(),
+///     ArgumentSchemaBuilder::new("hide-matching-pattern", "Hide matching entries")
+///         .value(PATTERN_VALUE)
+///         .build(),
+/// ];
+/// const SCHEMA: CommandSchema<'static> =
+///     CommandSchemaBuilder::new("list", "List entries").arguments(ARGUMENTS).build();
+///
+/// let mut buffer = Vec::new();
+///
+/// fvr::arguments::schema::write_help(SCHEMA, 80, &mut buffer)?;
+///
+/// let expected = concat!(
+///     "list\n",
+///     "  List entries\n",
+///     "\n",
+///     "Usage: list [ARGUMENTS]\n",
+///     "\n",
+///     "Arguments:\n",
+///     "  -s, --sort [ORDER...]          Control how entries are sorted\n",
+///     "                                 - default: name\n",
+///     "                                 - options: name, size\n",
+///     "      --all                      Include hidden entries\n",
+///     "      --hide-matching-pattern [PATTERN?]\n",
+///     "                                 Hide matching entries\n",
+/// );
+///
+/// assert_eq!(String::from_utf8_lossy(&buffer), expected);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
 /// # Errors
 ///
 /// This function will return an error if writing fails.
-pub fn write_help(schema: CommandSchema<'_>, f: &mut impl Write) -> std::io::Result<()> {
+pub fn write_help(schema: CommandSchema<'_>, width: usize, f: &mut impl Write) -> std::io::Result<()> {
     f.write_all(schema.name.as_bytes())?;
 
     if let Some(version) = schema.version {
@@ -97,15 +155,7 @@ pub fn write_help(schema: CommandSchema<'_>, f: &mut impl Write) -> std::io::Res
     }
 
     for ValueSchema { name, list, required, .. } in schema.positionals.into_iter().flat_map(|v| v.iter()) {
-        write!(f, " [{name}")?;
-
-        if *list {
-            f.write_all(if *required { b"..." } else { b"..?" })?;
-        } else if !*required {
-            f.write_all(b"?")?;
-        }
-
-        f.write_all(b"]")?;
+        write!(f, " [{name}{}]", self::value_suffix(*list, *required))?;
     }
 
     f.write_all(b"\n")?;
@@ -114,7 +164,9 @@ pub fn write_help(schema: CommandSchema<'_>, f: &mut impl Write) -> std::io::Res
         f.write_all(b"\nSub-commands:\n")?;
 
         for CommandSchema { name, about, .. } in commands {
-            writeln!(f, "  {name: <30} {about}")?;
+            write!(f, "  {name: <30}")?;
+
+            self::write_description(f, about, 2 + name.len().max(30), width)?;
         }
     }
 
@@ -122,14 +174,10 @@ pub fn write_help(schema: CommandSchema<'_>, f: &mut impl Write) -> std::io::Res
         f.write_all(b"\nPositionals:\n")?;
 
         for ValueSchema { name, about, default, options, .. } in positionals {
-            writeln!(f, "  {name: <30} {}", about.unwrap_or(""))?;
+            write!(f, "  {name: <30}")?;
 
-            if let Some(default) = default {
-                writeln!(f, "{: <32} - default: {default}", "")?;
-            }
-            if let Some(options) = options {
-                writeln!(f, "{: <32} - options: {}", "", options.join(", "))?;
-            }
+            self::write_description(f, about.unwrap_or(""), 2 + name.len().max(30), width)?;
+            self::write_value_details(f, *default, *options, width)?;
         }
     }
 
@@ -143,33 +191,19 @@ pub fn write_help(schema: CommandSchema<'_>, f: &mut impl Write) -> std::io::Res
                 write!(f, "      ")?;
             }
 
-            if let Some(ValueSchema { name, list, required, .. }) = value {
-                let mut temp = Vec::with_capacity(long.len() + 1 + name.len() + 6);
-
-                write!(&mut temp, "{long} [{name}")?;
+            if let Some(ValueSchema { name, list, required, default, options, .. }) = value {
+                let suffix = self::value_suffix(*list, *required);
+                // Schema strings are always ASCII, so their lengths match the number of columns they take up.
+                let len = long.len() + 2 + name.len() + suffix.len() + 1;
 
-                if *list {
-                    temp.write_all(if *required { b"..." } else { b"..?" })?;
-                } else if !*required {
-                    temp.write_all(b"?")?;
-                }
+                write!(f, "--{long} [{name}{suffix}]{: <padding$}", "", padding = 24_usize.saturating_sub(len))?;
 
-                temp.write_all(b"]")?;
-
-                write!(f, "--{: <24}", String::from_utf8_lossy(&temp))?;
+                self::write_description(f, about, 8 + len.max(24), width)?;
+                self::write_value_details(f, *default, *options, width)?;
             } else {
                 write!(f, "--{long: <24}")?;
-            }
 
-            writeln!(f, " {about}")?;
-
-            if let Some(ValueSchema { default, options, .. }) = value {
-                if let Some(default) = default {
-                    writeln!(f, "{: <32} - default: {default}", "")?;
-                }
-                if let Some(options) = options {
-                    writeln!(f, "{: <32} - options: {}", "", options.join(", "))?;
-                }
+                self::write_description(f, about, 8 + long.len().max(24), width)?;
             }
         }
     }
@@ -177,6 +211,227 @@ pub fn write_help(schema: CommandSchema<'_>, f: &mut impl Write) -> std::io::Res
     Ok(())
 }
 
+/// Returns the suffix written after the name of a value to show whether it is a list or is optional.
+const fn value_suffix(list: bool, required: bool) -> &'static str {
+    match (list, required) {
+        (true, true) => "...",
+        (true, false) => "..?",
+        (false, true) => "",
+        (false, false) => "?",
+    }
+}
+
+/// Writes the given description after a name that has already been written with the given length.
+///
+/// If the name overflows its column, the description is moved onto the next line to keep it aligned.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_description(f: &mut impl Write, about: &str, name_len: usize, width: usize) -> std::io::Result<()> {
+    if name_len > NAME_WIDTH {
+        write!(f, "\n{: <NAME_WIDTH$}", "")?;
+    }
+
+    f.write_all(b" ")?;
+
+    self::write_wrapped(f, about, NAME_WIDTH + 1, width)
+}
+
+/// Writes the default value and available options of a value, if present.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_value_details(
+    f: &mut impl Write,
+    default: Option<&str>,
+    options: Option<&[&str]>,
+    width: usize,
+) -> std::io::Result<()> {
+    if let Some(default) = default {
+        write!(f, "{: <NAME_WIDTH$} - ", "")?;
+
+        self::write_wrapped(f, &format!("default: {default}"), NAME_WIDTH + 3, width)?;
+    }
+    if let Some(options) = options {
+        write!(f, "{: <NAME_WIDTH$} - ", "")?;
+
+        self::write_wrapped(f, &format!("options: {}", options.join(", ")), NAME_WIDTH + 3, width)?;
+    }
+
+    Ok(())
+}
+
+/// Writes the given text followed by a newline, wrapping it at word boundaries to fit within the given width.
+///
+/// The first line is assumed to already be indented, and all following lines are indented to the given column.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_wrapped(f: &mut impl Write, text: &str, indent: usize, width: usize) -> std::io::Result<()> {
+    let max_len = width.saturating_sub(indent).max(MIN_DESCRIPTION_WIDTH);
+    let mut line_len = 0;
+
+    for word in text.split_ascii_whitespace() {
+        if line_len == 0 {
+            line_len = word.len();
+        } else if line_len + 1 + word.len() > max_len {
+            write!(f, "\n{: <indent$}", "")?;
+
+            line_len = word.len();
+        } else {
+            f.write_all(b" ")?;
+
+            line_len += 1 + word.len();
+        }
+
+        f.write_all(word.as_bytes())?;
+    }
+
+    f.write_all(b"\n")
+}
+
+/// Writes the given command schema into the provided writer as a single line of JSON.
+///
+/// # Examples
+///
+/// ```
+/// let mut buffer = Vec::new();
+///
+/// fvr::arguments::schema::write_json(fvr::arguments::SCHEMA, &mut buffer)?;
+///
+/// let json = String::from_utf8_lossy(&buffer);
+///
+/// assert!(json.contains(r#""name":"list""#));
+/// assert!(json.contains(r#""name":"tree""#));
+/// assert!(json.contains(r#""long":"color""#));
+/// assert!(json.contains(r#""options":["auto","always","never","html"]"#));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+pub fn write_json(schema: CommandSchema<'_>, f: &mut impl Write) -> std::io::Result<()> {
+    self::write_json_command(f, schema)?;
+
+    f.write_all(b"\n")
+}
+
+/// Writes the given command schema as a JSON object.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_json_command<W: Write>(f: &mut W, schema: CommandSchema<'_>) -> std::io::Result<()> {
+    f.write_all(b"{\"name\":")?;
+    self::write_json_string(f, Some(schema.name))?;
+    f.write_all(b",\"about\":")?;
+    self::write_json_string(f, Some(schema.about))?;
+    f.write_all(b",\"version\":")?;
+    self::write_json_string(f, schema.version)?;
+    f.write_all(b",\"arguments\":")?;
+    self::write_json_array(f, schema.arguments, self::write_json_argument)?;
+    f.write_all(b",\"positionals\":")?;
+    self::write_json_array(f, schema.positionals, self::write_json_value)?;
+    f.write_all(b",\"commands\":")?;
+    self::write_json_array(f, schema.commands, self::write_json_command)?;
+
+    f.write_all(b"}")
+}
+
+/// Writes the given argument schema as a JSON object.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_json_argument<W: Write>(f: &mut W, schema: ArgumentSchema<'_>) -> std::io::Result<()> {
+    let mut short = [0; 4];
+
+    f.write_all(b"{\"long\":")?;
+    self::write_json_string(f, Some(schema.long))?;
+    f.write_all(b",\"short\":")?;
+    self::write_json_string(f, schema.short.map(|short_char| &*short_char.encode_utf8(&mut short)))?;
+    f.write_all(b",\"about\":")?;
+    self::write_json_string(f, Some(schema.about))?;
+    f.write_all(b",\"value\":")?;
+
+    match schema.value {
+        Some(value) => self::write_json_value(f, value)?,
+        None => f.write_all(b"null")?,
+    }
+
+    f.write_all(b"}")
+}
+
+/// Writes the given value schema as a JSON object.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_json_value<W: Write>(f: &mut W, schema: ValueSchema<'_>) -> std::io::Result<()> {
+    f.write_all(b"{\"name\":")?;
+    self::write_json_string(f, Some(schema.name))?;
+    f.write_all(b",\"about\":")?;
+    self::write_json_string(f, schema.about)?;
+    write!(f, ",\"list\":{},\"required\":{},\"default\":", schema.list, schema.required)?;
+    self::write_json_string(f, schema.default)?;
+    f.write_all(b",\"options\":")?;
+    self::write_json_array(f, schema.options, |f, option| self::write_json_string(f, Some(option)))?;
+
+    f.write_all(b"}")
+}
+
+/// Writes the given items as a JSON array, or `null` if missing.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_json_array<W: Write, T: Copy>(
+    f: &mut W,
+    items: Option<&[T]>,
+    write_item: impl Fn(&mut W, T) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+    let Some(items) = items else { return f.write_all(b"null") };
+
+    f.write_all(b"[")?;
+
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            f.write_all(b",")?;
+        }
+
+        write_item(f, *item)?;
+    }
+
+    f.write_all(b"]")
+}
+
+/// Writes the given string as a JSON string, or `null` if missing.
+///
+/// Schema strings are always ASCII without control characters, so only quotes and backslashes must be escaped.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_json_string(f: &mut impl Write, string: Option<&str>) -> std::io::Result<()> {
+    let Some(string) = string else { return f.write_all(b"null") };
+
+    f.write_all(b"\"")?;
+
+    for byte in string.bytes() {
+        if matches!(byte, b'"' | b'\\') {
+            f.write_all(b"\\")?;
+        }
+
+        f.write_all(&[byte])?;
+    }
+
+    f.write_all(b"\"")
+}
+
 /// A command schema definition.
 #[must_use = "schema definitions do nothing by themselves"]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -198,6 +453,29 @@ pub struct CommandSchema<'s> {
 impl CommandSchema<'_> {
     /// Asserts that this command is valid and may be used within a command-line application.
     ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fvr::arguments::schema::{ArgumentSchemaBuilder, CommandSchemaBuilder};
+    ///
+    /// # fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> &'static str {
+    /// #     std::panic::set_hook(Box::new(|_| {}));
+    /// #     *std::panic::catch_unwind(f).unwrap_err().downcast::<&str>().unwrap()
+    /// # }
+    /// let argument = ArgumentSchemaBuilder::new("all", "Show everything").build();
+    ///
+    /// assert_eq!(
+    ///     panic_message(|| _ = CommandSchemaBuilder::new("List", "Lists things").build()),
+    ///     "command name should be entirely lowercase"
+    /// );
+    /// assert_eq!(
+    ///     panic_message(|| _ = CommandSchemaBuilder::new("list", "Lists things")
+    ///         .arguments(&[argument, argument])
+    ///         .build()),
+    ///     "command arguments should not contain duplicate values"
+    /// );
+    /// ```
+    ///
     /// # Panics
     ///
     /// Panics if this schema is not valid.
@@ -328,6 +606,29 @@ pub struct ValueSchema<'s> {
 impl ValueSchema<'_> {
     /// Asserts that this command value is valid and may be used within a command-line application.
     ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fvr::arguments::schema::ValueSchemaBuilder;
+    ///
+    /// # fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> &'static str {
+    /// #     std::panic::set_hook(Box::new(|_| {}));
+    /// #     *std::panic::catch_unwind(f).unwrap_err().downcast::<&str>().unwrap()
+    /// # }
+    /// assert_eq!(
+    ///     panic_message(|| _ = ValueSchemaBuilder::new("").build()),
+    ///     "string must not be empty"
+    /// );
+    /// assert_eq!(
+    ///     panic_message(|| _ = ValueSchemaBuilder::new("name").build()),
+    ///     "value name should be entirely uppercase"
+    /// );
+    /// assert_eq!(
+    ///     panic_message(|| _ = ValueSchemaBuilder::new("NAME").options(&["one", "one"]).build()),
+    ///     "options should not contain duplicates"
+    /// );
+    /// ```
+    ///
     /// # Panics
     ///
     /// Panics if this schema is not valid.
@@ -454,6 +755,27 @@ impl ArgumentSchema<'_> {
 
     /// Asserts that this command argument is valid and may be used within a command-line application.
     ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fvr::arguments::schema::ArgumentSchemaBuilder;
+    ///
+    /// # fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> &'static str {
+    /// #     std::panic::set_hook(Box::new(|_| {}));
+    /// #     *std::panic::catch_unwind(f).unwrap_err().downcast::<&str>().unwrap()
+    /// # }
+    /// assert_eq!(
+    ///     panic_message(|| _ = ArgumentSchemaBuilder::new("All", "Show everything").build()),
+    ///     "long argument name should be entirely lowercase"
+    /// );
+    /// assert_eq!(
+    ///     panic_message(
+    ///         || _ = ArgumentSchemaBuilder::new("all", "Show everything").short('-').build()
+    ///     ),
+    ///     "short argument name should be alphanumeric"
+    /// );
+    /// ```
+    ///
     /// # Panics
     ///
     /// Panics if this schema is not valid.
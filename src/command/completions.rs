@@ -0,0 +1,145 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright © 2025 Jaxydog
+//
+// This file is part of fvr.
+//
+// fvr is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public
+// License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later
+// version.
+//
+// fvr is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License along with fvr. If not,
+// see <https://www.gnu.org/licenses/>.
+
+//! Implements the completions sub-command.
+
+use std::io::Write;
+
+use crate::arguments::SCHEMA;
+use crate::arguments::model::{Arguments, CompletionsArguments, Shell, SubCommand};
+use crate::arguments::schema::{ArgumentSchema, CommandSchema, ValueSchema};
+
+/// Runs the command.
+///
+/// # Errors
+///
+/// This function will return an error if the command fails.
+#[expect(clippy::needless_pass_by_value, reason = "all sub-commands are invoked with owned arguments")]
+pub fn invoke(arguments: Arguments) -> std::io::Result<()> {
+    let Some(SubCommand::Completions(CompletionsArguments { shell: Some(shell) })) = arguments.command else {
+        unreachable!()
+    };
+
+    let f = &mut std::io::stdout().lock();
+
+    match shell {
+        Shell::Bash => self::write_bash(SCHEMA, f)?,
+    }
+
+    f.flush()
+}
+
+/// Writes a Bash completion script for the given command schema into the provided writer.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+pub fn write_bash(schema: CommandSchema<'_>, f: &mut impl Write) -> std::io::Result<()> {
+    let commands = schema.commands.unwrap_or_default();
+
+    writeln!(f, "_{}() {{", schema.name)?;
+    f.write_all(b"    local cur=\"${COMP_WORDS[COMP_CWORD]}\"\n")?;
+    f.write_all(b"    local prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n")?;
+    f.write_all(b"    local command=\"\"\n\n")?;
+
+    if !commands.is_empty() {
+        f.write_all(b"    for word in \"${COMP_WORDS[@]:1:COMP_CWORD-1}\"; do\n        case \"$word\" in\n")?;
+        writeln!(f, "            {})", self::join(commands.iter().map(|command| command.name), "|"))?;
+        f.write_all(b"                command=\"$word\"\n                break\n                ;;\n")?;
+        f.write_all(b"        esac\n    done\n\n")?;
+    }
+
+    f.write_all(b"    case \"$command\" in\n")?;
+
+    self::write_bash_command(f, schema, "\"\"")?;
+
+    for command in commands {
+        self::write_bash_command(f, *command, command.name)?;
+    }
+
+    f.write_all(b"    esac\n}\n\n")?;
+
+    writeln!(f, "complete -F _{0} {0}", schema.name)
+}
+
+/// Writes a single Bash `case` arm that completes the arguments and positionals of the given command schema.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_bash_command(f: &mut impl Write, schema: CommandSchema<'_>, pattern: &str) -> std::io::Result<()> {
+    let arguments = schema.arguments.unwrap_or_default();
+
+    writeln!(f, "        {pattern})")?;
+    f.write_all(b"            case \"$prev\" in\n")?;
+
+    for argument @ ArgumentSchema { value, .. } in arguments {
+        let Some(value) = value else { continue };
+
+        writeln!(f, "                {})", self::join(self::argument_names(argument), "|"))?;
+        f.write_all(b"                    ")?;
+
+        self::write_bash_compgen(f, value.options)?;
+
+        f.write_all(b"                    return\n                    ;;\n")?;
+    }
+
+    f.write_all(b"            esac\n\n            if [[ \"$cur\" == -* ]]; then\n                ")?;
+
+    let names = arguments.iter().flat_map(self::argument_names);
+
+    writeln!(f, "COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))", self::join(names, " "))?;
+
+    f.write_all(b"            else\n                ")?;
+
+    if let Some(commands) = schema.commands {
+        self::write_bash_compgen(f, Some(&commands.iter().map(|command| command.name).collect::<Box<[_]>>()))?;
+    } else {
+        self::write_bash_compgen(f, schema.positionals.and_then(|v| v.first()).and_then(|v: &ValueSchema| v.options))?;
+    }
+
+    f.write_all(b"            fi\n            ;;\n")
+}
+
+/// Writes a Bash `compgen` invocation that completes the given options, or file paths if there are none.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_bash_compgen(f: &mut impl Write, options: Option<&[&str]>) -> std::io::Result<()> {
+    if let Some(options) = options {
+        writeln!(f, "COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))", options.join(" "))
+    } else {
+        f.write_all(b"COMPREPLY=($(compgen -f -- \"$cur\"))\n")
+    }
+}
+
+/// Returns every name that the given argument may be specified with.
+fn argument_names(argument: &ArgumentSchema<'_>) -> impl Iterator<Item = String> {
+    std::iter::once(format!("--{}", argument.long)).chain(argument.short.map(|short| format!("-{short}")))
+}
+
+/// Joins the given values into a single string, separated by the given separator.
+fn join<T: AsRef<str>>(values: impl IntoIterator<Item = T>, separator: &str) -> String {
+    values.into_iter().fold(String::new(), |mut string, value| {
+        if !string.is_empty() {
+            string.push_str(separator);
+        }
+
+        string.push_str(value.as_ref());
+        string
+    })
+}
@@ -16,138 +16,718 @@
 
 //! Implements the list sub-command.
 
-use std::fs::Metadata;
-use std::io::Write;
+use std::collections::HashMap;
+use std::fs::{File, Metadata};
+use std::io::{BufWriter, IsTerminal, Write};
+use std::os::unix::fs::MetadataExt;
 use std::path::Path;
+use std::rc::Rc;
 
-use recomposition::sort::ListSortExt;
+use recomposition::filter::Filter;
 
-use crate::arguments::model::{Arguments, SubCommand};
-use crate::files::{Entry, is_hidden};
+use crate::arguments::model::{Arguments, ColorChoice, ListArguments, SortOrder, SubCommand};
+use crate::files::gitignore::GitIgnore;
+use crate::files::{Entry, Roots, glob, is_hidden};
+use crate::render::Columns;
+use crate::render::html::HtmlWriter;
 use crate::section::Section;
-use crate::section::mode::ModeSection;
 use crate::section::name::NameSection;
-use crate::section::size::SizeSection;
-use crate::section::time::TimeSection;
-use crate::section::user::{GroupSection, UserSection};
+use crate::terminal::Progress;
+use crate::writev;
 
-/// Runs the command.
+/// Runs the command, writing into either standard output or the requested output file.
+///
+/// Returns `true` if any path was skipped because it could not be read.
+///
+/// # Errors
+///
+/// This function will return an error if the command fails.
+pub fn invoke(mut arguments: Arguments) -> std::io::Result<bool> {
+    let Some(SubCommand::List(ListArguments { output, set_title, paths, pager, .. })) = arguments.command.as_mut()
+    else {
+        unreachable!()
+    };
+
+    if let Some(path) = output.take() {
+        // Files are never assumed to support color unless it has been explicitly requested.
+        if arguments.color.is_auto() {
+            arguments.color = ColorChoice::Never;
+        }
+
+        return self::invoke_into(arguments, &mut BufWriter::new(File::create(path)?));
+    }
+
+    let mut stdout = std::io::stdout().lock();
+
+    // The title is only ever set for terminals, since anything else would receive the escape sequence as text.
+    if *set_title && !arguments.color.is_never() && stdout.is_terminal() {
+        crate::terminal::write_title(&mut stdout, paths.iter())?;
+    }
+
+    let pager = *pager;
+
+    if pager.is_never() || !stdout.is_terminal() {
+        return self::invoke_into(arguments, &mut stdout);
+    }
+
+    // Output is buffered so that it is only paged once it is known whether it would fit on screen.
+    let mut buffer = Vec::new();
+    let skipped_paths = self::invoke_into(arguments, &mut buffer)?;
+
+    if pager.should_page(&buffer, crate::terminal::terminal_height()) {
+        crate::terminal::write_paged(&mut stdout, &buffer)?;
+    } else {
+        stdout.write_all(&buffer)?;
+    }
+
+    Ok(skipped_paths)
+}
+
+/// Runs the command, writing into the given writer.
+///
+/// If the color choice is automatic, color support is determined using standard output.
+///
+/// Paths that cannot be read are reported to standard error and skipped, in which case this returns `true`.
+///
+/// # Examples
+///
+/// ```
+/// use fvr::arguments::{ParseResult, parse_arguments_from};
+///
+/// let ParseResult::Ok(arguments) = parse_arguments_from(["list", "--color", "never", "."]) else {
+///     unreachable!()
+/// };
+/// let mut buffer = Vec::new();
+///
+/// fvr::command::list::invoke_into(arguments, &mut buffer)?;
+///
+/// assert!(String::from_utf8_lossy(&buffer).lines().any(|line| line == "Cargo.toml"));
+/// assert!(!buffer.contains(&b'\x1b'));
+/// # Ok::<(), std::io::Error>(())
+/// ```
 ///
 /// # Errors
 ///
 /// This function will return an error if the command fails.
-pub fn invoke(arguments: Arguments) -> std::io::Result<()> {
+pub fn invoke_into(arguments: Arguments, f: &mut impl Write) -> std::io::Result<bool> {
+    if !arguments.color.is_html() {
+        return self::write_listing(arguments, f);
+    }
+
+    let mut f = HtmlWriter::new(f);
+    let skipped_paths = self::write_listing(arguments, &mut f)?;
+
+    f.finish()?;
+
+    Ok(skipped_paths)
+}
+
+/// Writes the command's output into the given writer, using color escape sequences if color is enabled.
+///
+/// Returns `true` if any path was skipped because it could not be read.
+///
+/// # Errors
+///
+/// This function will return an error if the command fails.
+fn write_listing(arguments: Arguments, f: &mut impl Write) -> std::io::Result<bool> {
     let Some(SubCommand::List(list_arguments)) = arguments.command else { unreachable!() };
+    let color = arguments.color.resolve();
 
-    let sort = list_arguments.sorting.clone().unwrap_or_default();
-    let filter = recomposition::filter::from_fn(|(path, _): &(Box<Path>, _)| {
-        (list_arguments.show_hidden || !is_hidden(path))
-            && list_arguments.included.as_ref().is_none_or(|include| include.contains(path))
-            && !list_arguments.excluded.as_ref().is_some_and(|exclude| exclude.contains(path))
+    let mut sort = (!list_arguments.unsorted).then(|| list_arguments.sorting.clone().unwrap_or_default());
+    let gitignore = list_arguments.gitignore.then(GitIgnore::new);
+    let filter = recomposition::filter::from_fn(|entry: &(Box<Path>, Metadata)| {
+        self::is_listed(&list_arguments, gitignore.as_ref(), entry)
     });
 
-    let mode_section = if list_arguments.mode.is_hide() {
-        None //
-    } else {
-        Some(ModeSection::new(list_arguments.mode.is_extended()))
-    };
-    let size_section = if list_arguments.size.is_hide() {
-        None // 
-    } else {
-        Some(SizeSection::new(list_arguments.size))
-    };
-    let created_section = if list_arguments.created.is_hide() {
-        None //
-    } else {
-        Some(TimeSection::created(list_arguments.created))
-    };
-    let accessed_section = if list_arguments.accessed.is_hide() {
-        None //
-    } else {
-        Some(TimeSection::accessed(list_arguments.accessed))
-    };
-    let modified_section = if list_arguments.modified.is_hide() {
-        None //
+    let relative_to = list_arguments
+        .relative
+        .then(|| std::env::current_dir().and_then(std::fs::canonicalize).map(Rc::from))
+        .transpose()?;
+    let mut columns = Columns::new(&list_arguments, relative_to);
+
+    // Paths are listed in the order that they were given, unless they are sorted so that headers are reproducible.
+    // Otherwise, they are cloned so that the remaining arguments can still be borrowed as a whole.
+    let paths = if list_arguments.sort_paths {
+        crate::files::sorted_paths(&list_arguments.paths).collect()
     } else {
-        Some(TimeSection::modified(list_arguments.modified))
+        list_arguments.paths.clone()
     };
-    let user_section = list_arguments.user.then_some(UserSection);
-    let group_section = list_arguments.group.then_some(GroupSection);
-    let name_section = NameSection::new(true, list_arguments.resolve_symlinks);
+    let (paths, skipped_paths) = crate::files::read_roots(paths);
 
-    let f = &mut std::io::stdout().lock();
+    if sort.as_mut().is_some_and(|sort| sort.fall_back_from_created(&paths)) {
+        eprintln!("warning: creation time unsupported on this filesystem; falling back to modified");
+    }
 
-    let total_paths = list_arguments.paths.len();
-    let paths = list_arguments.paths.into_iter().map(|path| {
-        let data = std::fs::symlink_metadata(&path)?;
+    if list_arguments.find_hardlinks {
+        columns.hardlinks = Some(self::hardlink_groups(&paths, &filter, sort.as_ref(), &list_arguments));
+    }
+    if list_arguments.dir_only {
+        self::write_roots(f, &mut columns, color, paths, &filter, &list_arguments)?;
 
-        Ok((path, data))
-    });
+        return Ok(skipped_paths);
+    }
+    if list_arguments.print0 || list_arguments.format.is_csv() {
+        return Ok(self::write_plain(f, paths, &filter, sort.as_ref(), &list_arguments)? || skipped_paths);
+    }
+    if list_arguments.tree {
+        return Ok(
+            self::write_trees(f, &mut columns, color, paths, &filter, sort.as_ref(), &list_arguments)? || skipped_paths
+        );
+    }
 
-    let mut paths = paths.collect::<std::io::Result<Box<[(Box<Path>, Metadata)]>>>()?;
+    Ok(self::write_listings(f, &mut columns, color, paths, &filter, sort.as_ref(), &list_arguments)? || skipped_paths)
+}
 
-    paths.sort_unstable_with(&sort);
+/// Writes the contents of each of the given paths as a listing of its own, along with those of every directory within
+/// them if listing recursively.
+///
+/// Returns `true` if any path was skipped because its children could not be accessed.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_listings<F>(
+    f: &mut impl Write,
+    columns: &mut Columns,
+    color: bool,
+    paths: Roots,
+    filter: &F,
+    sort: Option<&SortOrder>,
+    arguments: &ListArguments,
+) -> std::io::Result<bool>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+{
+    let mut progress = arguments.progress.then(Progress::stderr);
+    let mut skipped_paths = false;
+    // Paths that could not be read are still counted, so that the remaining paths are always named.
+    let total_paths = arguments.paths.len();
+
+    // Entry limits apply across every listing, so that each sub-command limits its output in the same way.
+    let mut written = 0;
+    let mut skipped = 0_usize;
 
     for (index, (path, data)) in paths.into_iter().enumerate() {
-        let entry = Entry::new(path, Some(data), index, total_paths, &filter);
+        let entry = Entry::new(path, Some(data), index, total_paths, filter);
 
-        if index > 0 {
-            f.write_all(b"\n")?;
-        }
-        if total_paths > 1 {
-            if entry.can_traverse() {
-                name_section.write(arguments.color, f, &[], &entry)?;
+        // Nested directories are named relative to the listed path's parent, such as `src/command/`.
+        let nested_name =
+            NameSection::new(false, false, entry.path.parent().map(Rc::from), false, arguments.quoting, None);
+        // Each listing is kept alongside the depth of its entries, so that directories past the maximum are not
+        // entered.
+        let mut pending = vec![(Rc::new(entry), Vec::new(), 1)];
+        let mut nested = false;
+
+        while let Some((entry, mut ancestors, depth)) = pending.pop() {
+            // Listings that start once the limit has been reached are still visited, but only to count their entries,
+            // so nothing is written for them.
+            let limited = arguments.max_entries.is_some_and(|max_entries| written >= max_entries.get());
+
+            if !limited && (index > 0 || nested) && !arguments.no_separators {
+                f.write_all(b"\n")?;
+            }
+
+            let heading = if nested {
+                Some(&nested_name)
             } else {
-                let path = entry.path.absolute()?.parent().map_or_else(|| Path::new("/").into(), Box::from);
+                (total_paths > 1 || arguments.recursive).then_some(&columns.name)
+            };
 
-                name_section.write(arguments.color, f, &[], &Entry::root(path, None, &filter))?;
+            if let Some(heading) = heading.filter(|_| !limited && !arguments.no_headers) {
+                self::write_heading(f, color, heading, &entry, filter)?;
             }
 
-            f.write_all(b":\n")?;
+            nested = true;
+
+            // Entries are collected before any are written, so that every column can be measured against them first.
+            let mut children = Vec::new();
+            // Symbolic links are never followed, which also prevents the listing from ever entering a cycle.
+            let mut directories = Vec::new();
+            let result = crate::files::visit_entries(&entry, filter, sort, |parents, child| {
+                if let Some(progress) = progress.as_mut() {
+                    progress.tick();
+                }
+
+                // Directories that are left out are still listed, so that their own entries are counted.
+                if arguments.recursive && child.is_dir() && !parents.is_empty() && self::can_descend(arguments, depth) {
+                    directories.push((child.path.clone(), child.data.clone()));
+                }
+
+                if arguments.max_entries.is_some_and(|max_entries| written >= max_entries.get()) {
+                    skipped += 1;
+
+                    return Ok(());
+                }
+
+                written += 1;
+
+                children.push(Entry::new(child.path.clone(), child.data.clone(), child.index, child.total, filter));
+
+                Ok(())
+            });
+
+            if let Err(error) = result {
+                eprintln!("{}: {error}", entry.path.display());
+
+                skipped_paths = true;
+            }
+
+            // Every directory above this one is included, so that links leading back into any of them are marked.
+            if entry.can_traverse() {
+                ancestors.push(Rc::clone(&entry));
+            }
+
+            let parents = ancestors.iter().map(Rc::as_ref).collect::<Vec<_>>();
+
+            self::write_entries(f, columns, color, arguments, &parents, &children.iter().collect::<Vec<_>>())?;
+
+            // Directories are pushed in reverse so that they are listed in the same order that they were shown.
+            pending.extend(
+                directories
+                    .into_iter()
+                    .rev()
+                    .map(|(path, data)| (Rc::new(Entry::root(path, data, filter)), ancestors.clone(), depth + 1)),
+            );
         }
+    }
+
+    self::write_skipped(f, skipped)?;
+
+    f.flush()?;
+
+    Ok(skipped_paths)
+}
+
+/// Writes the given entries as a listing of their own, measuring every column against them first.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_entries<F>(
+    f: &mut impl Write,
+    columns: &mut Columns,
+    color: bool,
+    arguments: &ListArguments,
+    parents: &[&Entry<F>],
+    entries: &[&Entry<F>],
+) -> std::io::Result<()>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+{
+    columns.measure(entries);
+
+    for &entry in entries {
+        columns.measure_name(parents, entry);
+    }
+
+    // The header is written alongside the first entry so that nothing is shown for paths that cannot be read.
+    if arguments.header && !entries.is_empty() {
+        crate::render::render_header(columns, color, f)?;
+    }
+
+    for &entry in entries {
+        crate::render::render_line(columns, color, f, parents, entry)?;
+    }
+
+    self::write_footer(f, columns, color, arguments, entries)
+}
+
+/// Returns whether a recursive listing enters the directories found among entries at the given depth.
+///
+/// Listed paths' own entries are at a depth of one, just as they would be within a tree.
+const fn can_descend(arguments: &ListArguments, depth: usize) -> bool {
+    match arguments.max_depth {
+        Some(max_depth) => depth < max_depth,
+        None => true,
+    }
+}
+
+/// Writes the line shown above a listing's entries, naming the directory that they are within.
+///
+/// Files are listed within their parent directory, so that directory is named instead.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_heading<F>(
+    f: &mut impl Write,
+    color: bool,
+    name: &NameSection,
+    entry: &Entry<F>,
+    filter: &F,
+) -> std::io::Result<()>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+{
+    if entry.can_traverse() {
+        name.write_resolved(color, f, &[], entry)?;
+    } else {
+        let path = entry.path.absolute()?.parent().map_or_else(|| Path::new("/").into(), Box::from);
+
+        name.write_resolved(color, f, &[], &Entry::root(path, None, filter))?;
+    }
+
+    f.write_all(b":\n")
+}
 
-        crate::files::visit_entries(&entry, &filter, &sort, |parents, entry| {
-            if let Some(mode) = &mode_section {
-                mode.write(arguments.color, f, parents, entry)?;
+/// Writes the line shown beneath a listing's entries, noting their total size.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_footer<F>(
+    f: &mut impl Write,
+    columns: &Columns,
+    color: bool,
+    arguments: &ListArguments,
+    entries: &[&Entry<F>],
+) -> std::io::Result<()>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+{
+    if let Some(total) = columns.total_size(entries).filter(|_| arguments.total && !entries.is_empty()) {
+        crate::render::render_total(columns, color, f, total)?;
+    }
+
+    Ok(())
+}
+
+/// Writes the line shown beneath every listing, noting how many entries were left out of them, if any.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_skipped(f: &mut impl Write, skipped: usize) -> std::io::Result<()> {
+    if skipped > 0 { writeln!(f, "... ({skipped} more)") } else { Ok(()) }
+}
+
+/// Returns `true` if the given entry should be listed based on the given arguments.
+fn is_listed(arguments: &ListArguments, gitignore: Option<&GitIgnore>, (path, data): &(Box<Path>, Metadata)) -> bool {
+    (arguments.show_hidden || !is_hidden(path))
+        && !glob::matches_file_name(&arguments.hide_patterns, path)
+        && (arguments.name_patterns.is_empty() || glob::matches_file_name(&arguments.name_patterns, path))
+        && arguments.included.as_ref().is_none_or(|include| include.contains(path))
+        && !arguments.excluded.as_ref().is_some_and(|exclude| exclude.contains(path))
+        && !gitignore.is_some_and(|gitignore| gitignore.is_ignored(path, data.is_dir()))
+        && arguments.since.is_none_or(|since| data.modified().is_ok_and(|modified| modified >= since))
+        && arguments.until.is_none_or(|until| data.modified().is_ok_and(|modified| modified < until))
+}
+
+/// Returns a group number for each file that shares its inode with another file that will be listed, numbered in the
+/// order that the groups are first listed in.
+///
+/// This visits entries in the same way as the listing itself, so directories that cannot be read are left for the
+/// listing to report.
+fn hardlink_groups<F>(
+    roots: &Roots,
+    filter: &F,
+    sort: Option<&SortOrder>,
+    arguments: &ListArguments,
+) -> HashMap<(u64, u64), usize>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+{
+    let mut counts = HashMap::<(u64, u64), usize>::new();
+    let mut order = Vec::new();
+    let mut record = |data: &Metadata| {
+        if data.is_file() && data.nlink() > 1 {
+            let count = counts.entry((data.dev(), data.ino())).or_default();
 
-                f.write_all(b" ")?;
+            if *count == 0 {
+                order.push((data.dev(), data.ino()));
             }
-            if let Some(size) = &size_section {
-                size.write(arguments.color, f, parents, entry)?;
 
-                f.write_all(b" ")?;
+            *count += 1;
+        }
+    };
+
+    if arguments.dir_only {
+        roots.iter().for_each(|(_, data)| record(data));
+    } else {
+        let mut recorded = 0;
+
+        for (index, (path, data)) in roots.iter().enumerate() {
+            let mut pending = vec![(Entry::new(path.clone(), Some(data.clone()), index, roots.len(), filter), 1)];
+
+            while let Some((entry, depth)) = pending.pop() {
+                let mut directories = Vec::new();
+
+                let _ = crate::files::visit_entries(&entry, filter, sort, |parents, entry| {
+                    if arguments.recursive
+                        && entry.is_dir()
+                        && !parents.is_empty()
+                        && self::can_descend(arguments, depth)
+                    {
+                        directories.push((entry.path.clone(), entry.data.clone()));
+                    }
+                    if arguments.max_entries.is_some_and(|max_entries| recorded >= max_entries.get()) {
+                        return Ok(());
+                    }
+
+                    recorded += 1;
+
+                    if let Some(data) = entry.data.as_ref() {
+                        record(data);
+                    }
+
+                    Ok(())
+                });
+
+                pending.extend(
+                    directories.into_iter().rev().map(|(path, data)| (Entry::root(path, data, filter), depth + 1)),
+                );
             }
-            if let Some(created) = &created_section {
-                created.write(arguments.color, f, parents, entry)?;
+        }
+    }
+
+    order.into_iter().filter(|identity| counts[identity] > 1).zip(1 ..).collect()
+}
 
-                f.write_all(b" ")?;
+/// Writes each of the given paths as an entry of its own, without listing the contents of any directories.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_roots<F>(
+    f: &mut impl Write,
+    columns: &mut Columns,
+    color: bool,
+    paths: Roots,
+    filter: &F,
+    arguments: &ListArguments,
+) -> std::io::Result<()>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+{
+    let total_paths = paths.len();
+    let entries = paths
+        .into_iter()
+        .enumerate()
+        .map(|(index, (path, data))| Entry::new(path, Some(data), index, total_paths, filter))
+        .collect::<Vec<_>>();
+
+    if arguments.print0 {
+        for entry in &entries {
+            writev!(f, [entry.path.as_os_str().as_encoded_bytes(), b"\0"])?;
+        }
+
+        return f.flush();
+    }
+
+    self::write_entries(f, columns, color, arguments, &[], &entries.iter().collect::<Vec<_>>())?;
+
+    f.flush()
+}
+
+/// Writes each of the given paths as a tree, drawing each entry's branches between its metadata and its name.
+///
+/// Returns `true` if any path was skipped because its children could not be accessed.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_trees<F>(
+    f: &mut impl Write,
+    columns: &mut Columns,
+    color: bool,
+    paths: Roots,
+    filter: &F,
+    sort: Option<&SortOrder>,
+    arguments: &ListArguments,
+) -> std::io::Result<bool>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+{
+    let mut progress = arguments.progress.then(Progress::stderr);
+    let mut skipped_paths = false;
+    let mut written = 0;
+    let mut skipped = 0_usize;
+
+    for (index, (path, data)) in paths.into_iter().enumerate() {
+        if index > 0 && !arguments.no_separators {
+            f.write_all(b"\n")?;
+        }
+
+        let root = Rc::new(Entry::root(path.clone(), Some(data.clone()), filter));
+
+        // Entries are collected before any are written, so that every column can be measured against them first.
+        let mut lines = vec![(Vec::new(), Rc::clone(&root))];
+
+        // Files have no contents, so they are only ever shown as the root of their own tree.
+        let children = root
+            .can_traverse()
+            .then(|| crate::files::entries(Entry::root(path, Some(data), filter), arguments.max_depth, filter, sort));
+
+        for result in children.into_iter().flatten() {
+            let (parents, entry) = match result {
+                Ok(line) => line,
+                Err(error) => {
+                    eprintln!("{}: {error}", root.path.display());
+
+                    skipped_paths = true;
+
+                    continue;
+                }
+            };
+
+            if let Some(progress) = progress.as_mut() {
+                progress.tick();
             }
-            if let Some(accessed) = &accessed_section {
-                accessed.write(arguments.color, f, parents, entry)?;
 
-                f.write_all(b" ")?;
+            if arguments.max_entries.is_some_and(|max_entries| written >= max_entries.get()) {
+                skipped += 1;
+            } else {
+                written += 1;
+
+                lines.push((parents, entry));
             }
-            if let Some(modified) = &modified_section {
-                modified.write(arguments.color, f, parents, entry)?;
+        }
+
+        let entries = lines.iter().map(|(_, entry)| &**entry).collect::<Vec<_>>();
+
+        columns.measure(&entries);
+
+        for (parents, entry) in &lines {
+            columns.measure_name(&parents.iter().map(Rc::as_ref).collect::<Vec<_>>(), entry);
+        }
+
+        if arguments.header {
+            crate::render::render_header(columns, color, f)?;
+        }
+
+        for (parents, entry) in &lines {
+            let parents = parents.iter().map(Rc::as_ref).collect::<Vec<_>>();
 
-                f.write_all(b" ")?;
+            crate::render::render_line(columns, color, f, &parents, entry)?;
+        }
+
+        self::write_footer(f, columns, color, arguments, &entries)?;
+    }
+
+    self::write_skipped(f, skipped)?;
+
+    f.flush()?;
+
+    Ok(skipped_paths)
+}
+
+/// Writes every entry within the given paths without using the section writers.
+///
+/// Each entry is either written as a row of comma-separated values, below a header row, or as its path followed by a
+/// null byte. Directories are descended into just as they would be listed, and no more entries are written than the
+/// listing would show, though nothing is written in place of those that were left out.
+///
+/// Returns `true` if any path was skipped because its children could not be accessed.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_plain<F>(
+    f: &mut impl Write,
+    paths: Roots,
+    filter: &F,
+    sort: Option<&SortOrder>,
+    arguments: &ListArguments,
+) -> std::io::Result<bool>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+{
+    let mut progress = arguments.progress.then(Progress::stderr);
+    let csv = !arguments.print0;
+    let total_paths = paths.len();
+    let mut skipped_paths = false;
+    let mut written = 0;
+
+    // Recursive listings and trees descend as deep as they were asked to, while other listings only show children.
+    let max_depth = if arguments.tree || arguments.recursive { arguments.max_depth } else { Some(1) };
+
+    if csv {
+        f.write_all(b"path,size,mode,uid,gid,mtime,is_dir\n")?;
+    }
+
+    for (index, (path, data)) in paths.into_iter().enumerate() {
+        let entry = Entry::new(path, Some(data), index, total_paths, filter);
+
+        let mut write_failed = false;
+        let result = crate::files::visit_entries_recursive(&entry, max_depth, filter, sort, &mut |_, entry| {
+            if let Some(progress) = progress.as_mut() {
+                progress.tick();
             }
-            if let Some(user) = &user_section {
-                user.write(arguments.color, f, parents, entry)?;
 
-                f.write_all(b" ")?;
+            if arguments.max_entries.is_some_and(|max_entries| written >= max_entries.get()) {
+                return Ok(());
             }
-            if let Some(group) = &group_section {
-                group.write(arguments.color, f, parents, entry)?;
 
-                f.write_all(b" ")?;
+            written += 1;
+
+            let result = if csv {
+                self::write_csv_row(f, entry)
+            } else {
+                writev!(f, [entry.path.as_os_str().as_encoded_bytes(), b"\0"])
+            };
+
+            write_failed = result.is_err();
+
+            result
+        });
+
+        if let Err(error) = result {
+            if write_failed {
+                return Err(error);
             }
 
-            name_section.write(arguments.color, f, parents, entry)?;
+            eprintln!("{}: {error}", entry.path.display());
 
-            f.write_all(b"\n")
-        })?;
+            skipped_paths = true;
+        }
     }
 
-    f.flush()
+    f.flush()?;
+
+    Ok(skipped_paths)
+}
+
+/// Writes the given entry as a row of comma-separated values.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_csv_row<F>(f: &mut impl Write, entry: &Entry<F>) -> std::io::Result<()>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+{
+    self::write_csv_field(f, entry.path.as_os_str().as_encoded_bytes())?;
+
+    if let Some(data) = entry.data.as_ref() {
+        let mode = data.mode() & 0o7777;
+
+        writeln!(f, ",{},{mode:o},{},{},{},{}", data.len(), data.uid(), data.gid(), data.mtime(), data.is_dir())
+    } else {
+        f.write_all(b",,,,,,\n")
+    }
+}
+
+/// Writes the given field, quoting it if it contains a comma, quote, or line break.
+///
+/// Quotes within a quoted field are escaped by doubling them, as described by RFC 4180.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_csv_field(f: &mut impl Write, field: &[u8]) -> std::io::Result<()> {
+    if !field.iter().any(|byte| matches!(byte, b',' | b'"' | b'\r' | b'\n')) {
+        return f.write_all(field);
+    }
+
+    f.write_all(b"\"")?;
+
+    for (index, part) in field.split(|byte| *byte == b'"').enumerate() {
+        if index > 0 {
+            f.write_all(b"\"\"")?;
+        }
+
+        f.write_all(part)?;
+    }
+
+    f.write_all(b"\"")
 }
@@ -16,48 +16,163 @@
 
 //! Implements the tree sub-command.
 
-use std::fs::Metadata;
-use std::io::Write;
-use std::num::NonZero;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs::{File, Metadata};
+use std::io::{BufWriter, IsTerminal, Stderr, Write};
 use std::path::Path;
+use std::rc::Rc;
 
-use recomposition::sort::ListSortExt;
+use recomposition::filter::Filter;
 
-use crate::arguments::model::{Arguments, SubCommand};
-use crate::files::{Entry, is_hidden};
+use crate::arguments::model::{Arguments, ColorChoice, SubCommand, TreeArguments};
+use crate::files::gitignore::GitIgnore;
+use crate::files::{Entry, SortChildren, glob, is_hidden};
+use crate::render::html::HtmlWriter;
 use crate::section::Section;
 use crate::section::name::NameSection;
+use crate::section::size::units;
 use crate::section::tree::TreeSection;
+use crate::terminal::Progress;
+use crate::writev;
 
-/// Runs the command.
+/// Runs the command, writing into either standard output or the requested output file.
+///
+/// Returns `true` if any path was skipped because it could not be read.
 ///
 /// # Errors
 ///
 /// This function will return an error if the command fails.
-pub fn invoke(arguments: Arguments) -> std::io::Result<()> {
-    let Some(SubCommand::Tree(tree_arguments)) = arguments.command else { unreachable!() };
-
-    let sort = tree_arguments.sorting.clone().unwrap_or_default();
-    let filter = recomposition::filter::from_fn(|(path, _)| {
-        (tree_arguments.show_hidden || !is_hidden(path))
-            && tree_arguments.included.as_ref().is_none_or(|include| include.contains(path))
-            && !tree_arguments.excluded.as_ref().is_some_and(|exclude| exclude.contains(path))
-    });
+pub fn invoke(mut arguments: Arguments) -> std::io::Result<bool> {
+    let Some(SubCommand::Tree(TreeArguments { output, set_title, paths, pager, .. })) = arguments.command.as_mut()
+    else {
+        unreachable!()
+    };
 
-    let tree_section = TreeSection::new(tree_arguments.max_depth.map_or(usize::MAX, NonZero::get));
-    let name_section = NameSection::new(true, tree_arguments.resolve_symlinks);
+    if let Some(path) = output.take() {
+        // Files are never assumed to support color unless it has been explicitly requested.
+        if arguments.color.is_auto() {
+            arguments.color = ColorChoice::Never;
+        }
+
+        return self::invoke_into(arguments, &mut BufWriter::new(File::create(path)?));
+    }
 
-    let f = &mut std::io::stdout().lock();
+    let mut stdout = std::io::stdout().lock();
 
-    let paths = tree_arguments.paths.into_iter().map(|path| {
-        let data = std::fs::symlink_metadata(&path)?;
+    // The title is only ever set for terminals, since anything else would receive the escape sequence as text.
+    if *set_title && !arguments.color.is_never() && stdout.is_terminal() {
+        crate::terminal::write_title(&mut stdout, paths.iter())?;
+    }
+
+    let pager = *pager;
+
+    if pager.is_never() || !stdout.is_terminal() {
+        return self::invoke_into(arguments, &mut stdout);
+    }
 
-        Ok((path, data))
+    // Output is buffered so that it is only paged once it is known whether it would fit on screen.
+    let mut buffer = Vec::new();
+    let skipped_paths = self::invoke_into(arguments, &mut buffer)?;
+
+    if pager.should_page(&buffer, crate::terminal::terminal_height()) {
+        crate::terminal::write_paged(&mut stdout, &buffer)?;
+    } else {
+        stdout.write_all(&buffer)?;
+    }
+
+    Ok(skipped_paths)
+}
+
+/// Runs the command, writing into the given writer.
+///
+/// If the color choice is automatic, color support is determined using standard output.
+///
+/// Paths that cannot be read are reported to standard error and skipped, in which case this returns `true`.
+///
+/// # Examples
+///
+/// A depth of zero shows only the given paths, without any of their contents.
+///
+/// ```
+/// use fvr::arguments::{ParseResult, parse_arguments_from};
+///
+/// let ParseResult::Ok(arguments) =
+///     parse_arguments_from(["tree", "--color", "never", "--depth", "0", "src"])
+/// else {
+///     unreachable!()
+/// };
+/// let mut buffer = Vec::new();
+///
+/// fvr::command::tree::invoke_into(arguments, &mut buffer)?;
+///
+/// assert_eq!(String::from_utf8_lossy(&buffer).lines().count(), 1);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if the command fails.
+pub fn invoke_into(arguments: Arguments, f: &mut impl Write) -> std::io::Result<bool> {
+    if !arguments.color.is_html() {
+        return self::write_tree(arguments, f);
+    }
+
+    let mut f = HtmlWriter::new(f);
+    let skipped_paths = self::write_tree(arguments, &mut f)?;
+
+    f.finish()?;
+
+    Ok(skipped_paths)
+}
+
+/// Writes the command's output into the given writer, using color escape sequences if color is enabled.
+///
+/// Returns `true` if any path was skipped because it could not be read.
+///
+/// # Errors
+///
+/// This function will return an error if the command fails.
+fn write_tree(arguments: Arguments, f: &mut impl Write) -> std::io::Result<bool> {
+    let Some(SubCommand::Tree(mut tree_arguments)) = arguments.command else { unreachable!() };
+    let mut paths = std::mem::take(&mut tree_arguments.paths);
+
+    if tree_arguments.sort_paths {
+        paths = crate::files::sorted_paths(&paths).collect();
+    }
+
+    let color = arguments.color.resolve();
+
+    let mut sort = (!tree_arguments.unsorted).then(|| tree_arguments.sorting.clone().unwrap_or_default());
+    let gitignore = tree_arguments.gitignore.then(GitIgnore::new);
+    let filter = recomposition::filter::from_fn(|entry: &(Box<Path>, Metadata)| {
+        self::is_shown(&tree_arguments, gitignore.as_ref(), entry)
     });
 
-    let mut paths = paths.collect::<std::io::Result<Box<[(Box<Path>, Metadata)]>>>()?;
+    let tree_section = self::tree_section(&tree_arguments);
+    let name_section = NameSection::new(
+        true,
+        tree_arguments.resolve_symlinks,
+        None,
+        tree_arguments.mime,
+        tree_arguments.quoting,
+        tree_arguments.max_name_width,
+    );
+    let mut progress = tree_arguments.progress.then(Progress::stderr);
 
-    paths.sort_unstable_with(&sort);
+    // Paths are listed in the order that they were given, unless they were sorted so that trees are reproducible.
+    let (paths, mut skipped_paths) = crate::files::read_roots(paths);
+
+    if sort.as_mut().is_some_and(|sort| sort.fall_back_from_created(&paths)) {
+        eprintln!("warning: creation time unsupported on this filesystem; falling back to modified");
+    }
+
+    // Entry limits apply across every listed path.
+    let mut written = 0;
+    let mut skipped = 0_usize;
+
+    // Statistics are accumulated while writing, so they only ever describe the entries that were actually shown.
+    let mut stats = Stats::default();
 
     for (index, (path, data)) in paths.into_iter().enumerate() {
         let entry = Entry::root(path, Some(data), &filter);
@@ -66,32 +181,332 @@ pub fn invoke(arguments: Arguments) -> std::io::Result<()> {
             f.write_all(b"\n")?;
         }
 
-        if entry.can_traverse() {
-            tree_section.write(arguments.color, f, &[], &entry)?;
-            name_section.write(arguments.color, f, &[], &entry)?;
+        let parent = self::file_parent(&entry, &filter)?;
+        let root = parent.as_ref().unwrap_or(&entry);
+
+        // Without a root, its children are instead placed at the top of the tree.
+        if !tree_arguments.no_root {
+            self::write_root(f, color, &tree_arguments, &tree_section, &name_section, root)?;
+        }
+
+        // Flat trees have no branches to show where an entry is, so they always show full paths instead. Leaves are
+        // drawn without the directories above them, so they also show their full paths to keep that context.
+        let entry_name_section = if tree_arguments.full_path || tree_arguments.flat || tree_arguments.leaves_only {
+            NameSection { trim_paths: false, relative_to: Some(Rc::from(&*root.path)), ..name_section.clone() }
+        } else {
+            name_section.clone()
+        };
+
+        // Only failures to read the path are skipped, as failing to write means that nothing else can be shown.
+        let mut write_failed = false;
+        let mut write_entry = |parents: &[&Entry<_>], entry: &Entry<_>| {
+            if tree_arguments.max_entries.is_some_and(|max_entries| written >= max_entries.get()) {
+                skipped += 1;
+
+                return Ok(());
+            }
+
+            written += 1;
+
+            stats.record(entry);
+
+            let result =
+                if tree_arguments.flat { Ok(()) } else { tree_section.write_resolved(color, f, parents, entry) }
+                    .and_then(|()| entry_name_section.write_resolved(color, f, parents, entry))
+                    .and_then(|()| if tree_arguments.counts { self::write_count(f, color, entry) } else { Ok(()) })
+                    .and_then(|()| f.write_all(b"\n"));
+
+            write_failed = result.is_err();
+
+            result
+        };
+
+        // Filtered entries are found before any are written, so that branches are only ever drawn between shown
+        // entries.
+        let result = if tree_arguments.leaves_only || !tree_arguments.name_patterns.is_empty() {
+            self::find_shown(&tree_arguments, &tree_section, &entry, &filter, sort.as_ref(), progress.as_mut())
+                .and_then(|shown| shown.visit(&entry, &filter, &mut write_entry))
         } else {
-            let path = entry.path.absolute()?.parent().map_or_else(|| Path::new("/").into(), Box::from);
-            let entry = Entry::root(path, None, &filter);
+            crate::files::visit_entries_recursive(
+                &entry,
+                tree_arguments.max_depth,
+                &filter,
+                sort.as_ref(),
+                &mut |parents, entry| {
+                    if let Some(progress) = progress.as_mut() {
+                        progress.tick();
+                    }
+
+                    write_entry(parents, entry)
+                },
+            )
+        };
+
+        if let Err(error) = result {
+            if write_failed {
+                return Err(error);
+            }
+
+            eprintln!("{}: {error}", entry.path.display());
+
+            skipped_paths = true;
+        }
+    }
+
+    if skipped > 0 {
+        writeln!(f, "... ({skipped} more)")?;
+    }
 
-            tree_section.write(arguments.color, f, &[], &entry)?;
-            name_section.write(arguments.color, f, &[], &entry)?;
+    if tree_arguments.stats {
+        writeln!(f, "\n{stats}")?;
+    }
+
+    f.flush()?;
+
+    Ok(skipped_paths)
+}
+
+/// Finds the entries within the given root that are shown when only leaves or matching names are shown.
+///
+/// Every directory is still traversed to find the entries within it, but is itself left out unless it is shown.
+///
+/// # Errors
+///
+/// This function will return an error if an entry's children could not be accessed.
+fn find_shown<F, S>(
+    arguments: &TreeArguments,
+    tree_section: &TreeSection,
+    root: &Entry<F>,
+    filter: &F,
+    sort: Option<&S>,
+    mut progress: Option<&mut Progress<Stderr>>,
+) -> std::io::Result<ShownEntries>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+    S: SortChildren,
+{
+    let mut shown = ShownEntries::default();
+
+    crate::files::visit_entries_recursive(root, arguments.max_depth, filter, sort, &mut |parents, entry| {
+        if let Some(progress) = progress.as_mut() {
+            progress.tick();
+        }
+
+        let is_leaf = !arguments.leaves_only || !tree_section.is_branch(parents, entry);
+        let is_match =
+            arguments.name_patterns.is_empty() || glob::matches_file_name(&arguments.name_patterns, &entry.path);
+
+        if is_leaf && is_match {
+            shown.insert(parents, entry);
         }
 
-        f.write_all(b"\n")?;
+        Ok(())
+    })?;
 
-        crate::files::visit_entries_recursive(
-            &entry,
-            tree_arguments.max_depth,
-            &filter,
-            &sort,
-            &mut |parents, entry| {
-                tree_section.write(arguments.color, f, parents, entry)?;
-                name_section.write(arguments.color, f, parents, entry)?;
+    Ok(shown)
+}
 
-                f.write_all(b"\n")
-            },
-        )?;
+/// Returns the directory that the given entry is drawn beneath, if it is a file that cannot be traversed itself.
+///
+/// # Errors
+///
+/// This function will return an error if the entry's absolute path cannot be resolved.
+fn file_parent<'f, F>(entry: &Entry<'f, F>, filter: &'f F) -> std::io::Result<Option<Entry<'f, F>>>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+{
+    if entry.can_traverse() {
+        return Ok(None);
     }
 
-    f.flush()
+    let path = entry.path.absolute()?.parent().map_or_else(|| Path::new("/").into(), Box::from);
+
+    Ok(Some(Entry::root(path, None, filter)))
+}
+
+/// Returns `true` if the given entry should be shown based on the given arguments.
+fn is_shown(arguments: &TreeArguments, gitignore: Option<&GitIgnore>, (path, data): &(Box<Path>, Metadata)) -> bool {
+    (arguments.show_hidden || !is_hidden(path))
+        && !glob::matches_file_name(&arguments.hide_patterns, path)
+        && arguments.included.as_ref().is_none_or(|include| include.contains(path))
+        && !arguments.excluded.as_ref().is_some_and(|exclude| exclude.contains(path))
+        && !gitignore.is_some_and(|gitignore| gitignore.is_ignored(path, data.is_dir()))
+}
+
+/// Writes the line for the given root entry.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+fn write_root<F>(
+    f: &mut impl Write,
+    color: bool,
+    arguments: &TreeArguments,
+    tree_section: &TreeSection,
+    name_section: &NameSection,
+    root: &Entry<F>,
+) -> std::io::Result<()>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+{
+    if !arguments.flat {
+        tree_section.write_resolved(color, f, &[], root)?;
+    }
+
+    name_section.write_resolved(color, f, &[], root)?;
+
+    if arguments.counts {
+        self::write_count(f, color, root)?;
+    }
+
+    f.write_all(b"\n")
+}
+
+/// Writes the number of entries within the given entry, such as ` (12)`, if it can be traversed.
+///
+/// Entries are counted using the same filter as the tree, so hidden entries are only counted if they are shown.
+fn write_count<F>(f: &mut impl Write, color: bool, entry: &Entry<F>) -> std::io::Result<()>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+{
+    let Some(count) = entry.child_count() else {
+        return Ok(());
+    };
+
+    let mut buffer = itoa::Buffer::new();
+    let count = buffer.format(count).as_bytes();
+
+    if color { writev!(f, [b" (", count, b")"] in BrightBlack) } else { writev!(f, [b" (", count, b")"]) }
+}
+
+/// Returns the section that draws branches for the given arguments.
+fn tree_section(tree_arguments: &TreeArguments) -> TreeSection {
+    TreeSection::new(
+        tree_arguments.max_depth.unwrap_or(usize::MAX),
+        tree_arguments.narrow,
+        tree_arguments.tree_color,
+        tree_arguments.no_root,
+        tree_arguments.tree_style,
+    )
+}
+
+/// The entries shown within a filtered tree, each placed beneath its nearest shown ancestor.
+#[derive(Debug, Default)]
+struct ShownEntries {
+    /// Every shown entry, alongside the indices of the shown entries placed beneath it.
+    entries: Vec<(Box<Path>, Option<Metadata>, Vec<usize>)>,
+    /// The indices of the shown entries without a shown ancestor.
+    top: Vec<usize>,
+    /// The index of each shown entry by its path.
+    indices: HashMap<Box<Path>, usize>,
+}
+
+impl ShownEntries {
+    /// Records the given entry as shown, placing it beneath the nearest of the given parents that is also shown.
+    fn insert<F>(&mut self, parents: &[&Entry<F>], entry: &Entry<F>)
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        let index = self.entries.len();
+        let siblings = match parents.iter().rev().find_map(|parent| self.indices.get(&parent.path)) {
+            Some(&parent) => &mut self.entries[parent].2,
+            None => &mut self.top,
+        };
+
+        siblings.push(index);
+
+        self.indices.insert(entry.path.clone(), index);
+        self.entries.push((entry.path.clone(), entry.data.clone(), Vec::new()));
+    }
+
+    /// Visits every shown entry beneath the given root in the order that they were found.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the closure fails.
+    fn visit<F, V>(&self, root: &Entry<F>, filter: &F, visit: &mut V) -> std::io::Result<()>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+        V: FnMut(&[&Entry<F>], &Entry<F>) -> std::io::Result<()>,
+    {
+        self.visit_siblings(&[root], &self.top, filter, visit)
+    }
+
+    /// Visits the shown entries at the given indices beneath the given parents, followed by each of their own.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the closure fails.
+    fn visit_siblings<F, V>(
+        &self,
+        parents: &[&Entry<F>],
+        siblings: &[usize],
+        filter: &F,
+        visit: &mut V,
+    ) -> std::io::Result<()>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+        V: FnMut(&[&Entry<F>], &Entry<F>) -> std::io::Result<()>,
+    {
+        for (index, &sibling) in siblings.iter().enumerate() {
+            let (path, data, children) = &self.entries[sibling];
+            // Entries are only drawn as branches if any of their children are also shown.
+            let entry = Entry::new(path.clone(), data.clone(), index, siblings.len(), filter)
+                .with_children(!children.is_empty());
+
+            visit(parents, &entry)?;
+
+            if !children.is_empty() {
+                let mut new_parents = Vec::with_capacity(parents.len() + 1);
+
+                new_parents.extend_from_slice(parents);
+                new_parents.push(&entry);
+
+                self.visit_siblings(&new_parents, children, filter, visit)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A summary of the entries that were shown within a tree.
+#[derive(Clone, Copy, Debug, Default)]
+struct Stats {
+    /// The number of shown directories.
+    directories: usize,
+    /// The number of shown files.
+    files: usize,
+    /// The total size of every shown file.
+    bytes: u64,
+}
+
+impl Stats {
+    /// Records the given entry as shown.
+    fn record<F>(&mut self, entry: &Entry<F>)
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        if entry.is_dir() {
+            self.directories += 1;
+        } else {
+            self.files += 1;
+            self.bytes += entry.data.as_ref().map_or(0, Metadata::len);
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let directories_label = if self.directories == 1 { "directory" } else { "directories" };
+        let files_label = if self.files == 1 { "file" } else { "files" };
+
+        write!(
+            f,
+            "{} {directories_label}, {} {files_label}, {}",
+            self.directories,
+            self.files,
+            units::format_base_2(self.bytes)
+        )
+    }
 }
@@ -20,13 +20,30 @@ use std::cell::OnceCell;
 use std::ffi::OsStr;
 use std::fs::Metadata;
 use std::io::Result;
-use std::num::NonZero;
+use std::iter::Enumerate;
 use std::os::unix::ffi::OsStrExt;
 use std::os::unix::fs::MetadataExt;
 use std::path::{Component, Path, PathBuf};
+use std::rc::Rc;
+use std::time::SystemTime;
 
 use recomposition::filter::Filter;
-use recomposition::sort::{ListSortExt, Sort};
+
+pub mod gitignore;
+pub mod glob;
+pub mod magic;
+
+/// The paths that were explicitly given to be listed, alongside their metadata.
+pub type Roots = Box<[(Box<Path>, Metadata)]>;
+
+/// The entries within a directory, alongside their metadata.
+type Children = Vec<(Box<Path>, Metadata)>;
+
+/// An order that the children of a single directory may be sorted in.
+pub trait SortChildren {
+    /// Sorts the given children of a single directory.
+    fn sort_children(&self, children: &mut [(Box<Path>, Metadata)]);
+}
 
 /// An entry returned by a visit call.
 #[derive(Clone, Debug)]
@@ -48,8 +65,16 @@ where
     file_name_cache: OnceCell<Option<Box<OsStr>>>,
     /// Caches whether this entry has children.
     has_children_cache: OnceCell<bool>,
+    /// Caches the number of entries within this entry.
+    child_count_cache: OnceCell<Option<usize>>,
     /// Caches whether this entry can be traversed like a directory.
     can_traverse_cache: OnceCell<bool>,
+    /// Caches the entry's resolved device and inode numbers.
+    identity_cache: OnceCell<Option<(u64, u64)>>,
+    /// Caches whether the entry has an access control list.
+    has_acl_cache: OnceCell<bool>,
+    /// Caches the entry's metadata if it was not provided.
+    metadata_cache: OnceCell<Option<Metadata>>,
 }
 
 impl<'e, F> Entry<'e, F>
@@ -68,7 +93,11 @@ where
             filter,
             file_name_cache: OnceCell::new(),
             has_children_cache: OnceCell::new(),
+            child_count_cache: OnceCell::new(),
             can_traverse_cache: OnceCell::new(),
+            identity_cache: OnceCell::new(),
+            has_acl_cache: OnceCell::new(),
+            metadata_cache: OnceCell::new(),
         }
     }
 
@@ -81,6 +110,22 @@ where
         Self::new(path, data, 0, 1, filter)
     }
 
+    /// Returns this entry, recording whether it has children rather than reading its children once asked.
+    #[must_use]
+    pub fn with_children(mut self, has_children: bool) -> Self {
+        self.has_children_cache = OnceCell::from(has_children);
+
+        self
+    }
+
+    /// Returns this entry, recording the number of entries within it rather than reading them once asked.
+    #[must_use]
+    pub fn with_child_count(mut self, child_count: usize) -> Self {
+        self.child_count_cache = OnceCell::from(Some(child_count));
+
+        self.with_children(child_count > 0)
+    }
+
     /// Returns whether this is the first entry in the current depth.
     #[inline]
     #[must_use]
@@ -102,22 +147,113 @@ where
         self.total - (self.index + 1)
     }
 
+    /// Returns this entry's metadata, fetching and caching it if it was not provided.
+    ///
+    /// Like provided metadata, fetched metadata describes symbolic links themselves rather than their targets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::Metadata;
+    /// use std::path::Path;
+    ///
+    /// use fvr::files::Entry;
+    ///
+    /// let root = std::env::temp_dir().join(format!("fvr-metadata-or-stat-{}", std::process::id()));
+    ///
+    /// std::fs::create_dir_all(&root)?;
+    ///
+    /// let filter = recomposition::filter::from_fn(|_: &(Box<Path>, Metadata)| true);
+    /// let entry = Entry::root(root.clone().into_boxed_path(), None, &filter);
+    /// let fetched = entry.metadata_or_stat().map(std::ptr::from_ref);
+    ///
+    /// // Once fetched, the path is never read again, so the entry still describes the directory after it is removed.
+    /// std::fs::remove_dir_all(&root)?;
+    ///
+    /// assert!(fetched.is_some());
+    /// assert_eq!(entry.metadata_or_stat().map(std::ptr::from_ref), fetched);
+    /// assert!(entry.is_dir() && !entry.is_file() && !entry.is_symlink());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn metadata_or_stat(&self) -> Option<&Metadata> {
+        self.data
+            .as_ref()
+            .or_else(|| self.metadata_cache.get_or_init(|| std::fs::symlink_metadata(&self.path).ok()).as_ref())
+    }
+
     /// Returns `true` if this entry represents a directory.
     #[inline]
     pub fn is_dir(&self) -> bool {
-        self.data.as_ref().map_or_else(|| self.path.is_dir(), Metadata::is_dir)
+        self.metadata_or_stat().is_some_and(Metadata::is_dir)
     }
 
     /// Returns `true` if this entry represents a file.
     #[inline]
     pub fn is_file(&self) -> bool {
-        self.data.as_ref().map_or_else(|| self.path.is_file(), Metadata::is_file)
+        self.metadata_or_stat().is_some_and(Metadata::is_file)
     }
 
     /// Returns `true` if this entry represents a symbolic link.
     #[inline]
     pub fn is_symlink(&self) -> bool {
-        self.data.as_ref().map_or_else(|| self.path.is_symlink(), Metadata::is_symlink)
+        self.metadata_or_stat().is_some_and(Metadata::is_symlink)
+    }
+
+    /// Returns the character that represents this entry's file type, such as `d` for directories.
+    ///
+    /// This is one of the `TYPE_*` constants of [`ModeSection`], and is shared by every section that depends on an
+    /// entry's type so that they always agree. Entries whose metadata cannot be read are of an unknown type.
+    ///
+    /// [`ModeSection`]: crate::section::mode::ModeSection
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::Metadata;
+    /// use std::os::unix::net::UnixListener;
+    /// use std::path::Path;
+    ///
+    /// use fvr::files::Entry;
+    ///
+    /// let root = std::env::temp_dir().join(format!("fvr-file-type-char-{}", std::process::id()));
+    ///
+    /// std::fs::create_dir_all(root.join("directory"))?;
+    /// std::fs::write(root.join("file"), "")?;
+    /// std::os::unix::fs::symlink("file", root.join("link"))?;
+    ///
+    /// let _listener = UnixListener::bind(root.join("socket"))?;
+    /// let fifo = std::process::Command::new("mkfifo").arg(root.join("fifo")).status()?;
+    ///
+    /// let filter = recomposition::filter::from_fn(|_: &(Box<Path>, Metadata)| true);
+    /// let type_of = |path: &Path| Entry::root(path.into(), None, &filter).file_type_char();
+    /// let types = ["directory", "file", "link", "socket", "fifo", "missing"]
+    ///     .map(|name| type_of(&root.join(name)));
+    /// let device = type_of(Path::new("/dev/null"));
+    ///
+    /// std::fs::remove_dir_all(&root)?;
+    ///
+    /// assert!(fifo.success());
+    /// assert_eq!(types, [b'd', b'-', b'l', b's', b'p', b'?']);
+    /// assert_eq!(device, b'c');
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[must_use]
+    pub fn file_type_char(&self) -> u8 {
+        use crate::section::mode::ModeSection;
+        use crate::section::mode::file_type::{
+            BLOCK_DEVICE, CHARACTER_DEVICE, DIRECTORY, FIFO_PIPE, FILE, MASK, SOCKET, SYMBOLIC_LINK,
+        };
+
+        match self.metadata_or_stat().map(MetadataExt::mode).map(|mode| mode & MASK) {
+            Some(FILE) => ModeSection::TYPE_FILE,
+            Some(DIRECTORY) => ModeSection::TYPE_DIRECTORY,
+            Some(SYMBOLIC_LINK) => ModeSection::TYPE_SYMBOLIC_LINK,
+            Some(FIFO_PIPE) => ModeSection::TYPE_FIFO_PIPE,
+            Some(SOCKET) => ModeSection::TYPE_SOCKET,
+            Some(BLOCK_DEVICE) => ModeSection::TYPE_BLOCK_DEVICE,
+            Some(CHARACTER_DEVICE) => ModeSection::TYPE_CHARACTER_DEVICE,
+            _ => ModeSection::TYPE_UNKNOWN,
+        }
     }
 
     /// Returns `true` if this entry has an executable flag set.
@@ -151,6 +287,81 @@ where
         })
     }
 
+    /// Returns the device and inode numbers of this entry, following symbolic links.
+    pub fn identity(&self) -> Option<(u64, u64)> {
+        *self.identity_cache.get_or_init(|| match &self.data {
+            Some(data) if !data.is_symlink() => Some((data.dev(), data.ino())),
+            // Symbolic links must be resolved, since their own metadata describes the link rather than its target.
+            _ => std::fs::metadata(&self.path).ok().map(|data| (data.dev(), data.ino())),
+        })
+    }
+
+    /// Returns `true` if this entry has an access control list beyond its permission bits.
+    ///
+    /// This requires a system call for each entry, so the result is cached.
+    pub fn has_acl(&self) -> bool {
+        *self.has_acl_cache.get_or_init(|| crate::section::mode::ModeSection::has_acl(&self.path))
+    }
+
+    /// Returns `true` if this entry resolves to the same directory as any of the given parent entries.
+    ///
+    /// Traversing such an entry would loop forever, so recursive visits will not descend into it.
+    pub fn is_cycle(&self, parents: &[&Entry<F>]) -> bool {
+        self.can_traverse()
+            && self.identity().is_some_and(|identity| parents.iter().any(|parent| parent.identity() == Some(identity)))
+    }
+
+    /// Returns the number of entries within this entry that match its filter, or [`None`] if it cannot be read.
+    ///
+    /// Unlike [`has_children`](Entry::has_children), this reads every entry within a directory, so the result is cached
+    /// for sections that both measure and write it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::Metadata;
+    /// use std::path::Path;
+    ///
+    /// use fvr::files::Entry;
+    ///
+    /// let root = std::env::temp_dir().join(format!("fvr-child-count-{}", std::process::id()));
+    ///
+    /// std::fs::create_dir_all(&root)?;
+    /// std::fs::write(root.join("first"), [])?;
+    ///
+    /// let filter = recomposition::filter::from_fn(|_: &(Box<Path>, Metadata)| true);
+    /// let entry = Entry::root(root.clone().into_boxed_path(), None, &filter);
+    /// let counted = entry.child_count();
+    ///
+    /// // Once counted, the directory is never read again, so later entries are not included.
+    /// std::fs::write(root.join("second"), [])?;
+    ///
+    /// let recounted = entry.child_count();
+    ///
+    /// std::fs::remove_dir_all(&root)?;
+    ///
+    /// assert_eq!(counted, Some(1));
+    /// assert_eq!(recounted, counted);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[must_use]
+    pub fn child_count(&self) -> Option<usize> {
+        *self.child_count_cache.get_or_init(|| {
+            if !self.can_traverse() {
+                return None;
+            }
+
+            let children = std::fs::read_dir(&self.path).ok()?;
+
+            Some(
+                children
+                    .filter_map(|v| v.and_then(|v| v.metadata().map(|m| (v.path().into_boxed_path(), m))).ok())
+                    .filter(|v| self.filter.test(v))
+                    .count(),
+            )
+        })
+    }
+
     /// Returns `true` if this entry represents a directory and has one or more entries within it.
     #[must_use]
     pub fn has_children(&self) -> bool {
@@ -173,26 +384,64 @@ where
 ///
 /// The closure takes two arguments; a reference to the parent entries, and the child entry itself.
 ///
+/// If the entry cannot be traversed, such as when it is a regular file, the closure is only called for the entry
+/// itself.
+///
+/// If no sort is provided, entries are never compared and are visited in the order that they are returned by the file
+/// system.
+///
+/// # Examples
+///
+/// ```
+/// use std::fs::Metadata;
+/// use std::path::Path;
+///
+/// use fvr::arguments::model::SortOrder;
+/// use fvr::files::{Entry, visit_entries};
+///
+/// let root = std::env::temp_dir().join(format!("fvr-visit-unsorted-{}", std::process::id()));
+///
+/// std::fs::create_dir_all(&root)?;
+///
+/// for name in ["c", "a", "d", "b"] {
+///     std::fs::write(root.join(name), [])?;
+/// }
+///
+/// let filter = recomposition::filter::from_fn(|_: &(Box<Path>, Metadata)| true);
+/// let entry =
+///     Entry::root(root.clone().into_boxed_path(), Some(std::fs::metadata(&root)?), &filter);
+/// let mut visited = Vec::new();
+///
+/// let result = visit_entries(&entry, &filter, None::<&SortOrder>, |_, child| {
+///     visited.push(child.path.clone());
+///
+///     Ok(())
+/// });
+/// let expected = std::fs::read_dir(&root)?
+///     .map(|v| v.map(|v| v.path().into_boxed_path()))
+///     .collect::<Vec<_>>();
+///
+/// std::fs::remove_dir_all(&root)?;
+/// result?;
+///
+/// assert_eq!(visited, expected.into_iter().collect::<std::io::Result<Vec<_>>>()?);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
 /// # Errors
 ///
 /// This function will return an error if the entry's children could not be accessed or the closure fails.
-pub fn visit_entries<F, S, V>(entry: &Entry<F>, filter: &F, sort: &S, mut visit: V) -> Result<()>
+pub fn visit_entries<F, S, V>(entry: &Entry<F>, filter: &F, sort: Option<&S>, mut visit: V) -> Result<()>
 where
     F: Filter<(Box<Path>, Metadata)>,
-    S: Sort<(Box<Path>, Metadata)>,
+    S: SortChildren,
     V: FnMut(&[&Entry<F>], &Entry<F>) -> Result<()>,
 {
     if !entry.can_traverse() {
         return visit(&[], entry);
     }
 
-    let mut collection = std::fs::read_dir(&entry.path)?
-        .map(|v| v.and_then(|v| v.metadata().map(|d| (v.path().into_boxed_path(), d))))
-        .filter(|v| v.as_ref().map_or(true, |v| filter.test(v)))
-        .collect::<Result<Box<[(Box<Path>, Metadata)]>>>()?;
-
-    collection.sort_unstable_with(sort);
-
+    let collection = self::read_children(&entry.path, filter, sort)?;
     let total = collection.len();
 
     collection.into_iter().enumerate().try_for_each(|(index, (path, data))| {
@@ -206,51 +455,240 @@ where
 ///
 /// The closure takes two arguments; a reference to the parent entries, and the child entry itself.
 ///
+/// If no sort is provided, entries are visited in the order that they are returned by the file system.
+///
+/// A maximum depth of zero visits nothing, while [`None`] visits every level.
+///
+/// The children of each directory are read before it is visited, so visited directories that are descended into
+/// already know their [`child_count`](Entry::child_count).
+///
 /// # Errors
 ///
 /// This function will return an error if an entry's children could not be accessed or the closure fails.
 pub fn visit_entries_recursive<F, S, V>(
     entry: &Entry<F>,
-    max_depth: Option<NonZero<usize>>,
+    max_depth: Option<usize>,
     filter: &F,
-    sort: &S,
+    sort: Option<&S>,
     visit: &mut V,
 ) -> Result<()>
 where
     F: Filter<(Box<Path>, Metadata)>,
-    S: Sort<(Box<Path>, Metadata)>,
+    S: SortChildren,
     V: FnMut(&[&Entry<F>], &Entry<F>) -> Result<()>,
 {
     #[inline]
-    fn inner<F, S, V>(entries: &[&Entry<F>], max_depth: usize, filter: &F, sort: &S, visit: &mut V) -> Result<()>
+    fn inner<F, S, V>(
+        entries: &[&Entry<F>],
+        children: Children,
+        max_depth: usize,
+        filter: &F,
+        sort: Option<&S>,
+        visit: &mut V,
+    ) -> Result<()>
     where
         F: Filter<(Box<Path>, Metadata)>,
-        S: Sort<(Box<Path>, Metadata)>,
+        S: SortChildren,
         V: FnMut(&[&Entry<F>], &Entry<F>) -> Result<()>,
     {
-        if max_depth == 0 {
-            return Ok(());
+        let total = children.len();
+
+        for (index, (path, data)) in children.into_iter().enumerate() {
+            let entry = Entry::new(path, Some(data), index, total, filter);
+            let children = if max_depth > 1 && entry.has_children() && !entry.is_cycle(entries) {
+                Some(self::read_children(&entry.path, filter, sort)?)
+            } else {
+                None
+            };
+            let entry = match children.as_ref() {
+                Some(children) => entry.with_child_count(children.len()),
+                None => entry,
+            };
+
+            visit(entries, &entry)?;
+
+            if let Some(children) = children {
+                let mut new_entries = Vec::with_capacity(entries.len() + 1);
+
+                new_entries.extend_from_slice(entries);
+                new_entries.push(&entry);
+
+                inner(&new_entries, children, max_depth - 1, filter, sort, visit)?;
+            }
         }
 
-        let Some(entry) = entries.last() else { unreachable!() };
+        Ok(())
+    }
 
-        self::visit_entries(entry, filter, sort, |_, entry| {
-            visit(entries, entry)?;
+    let max_depth = max_depth.unwrap_or(usize::MAX);
 
-            if entry.has_children() {
-                let mut new_entries = Vec::with_capacity(entries.len() + 1);
+    if max_depth == 0 {
+        return Ok(());
+    }
+    if !entry.can_traverse() {
+        return visit(&[entry], entry);
+    }
 
-                new_entries.extend_from_slice(entries);
-                new_entries.push(entry);
+    inner(&[entry], self::read_children(&entry.path, filter, sort)?, max_depth, filter, sort, visit)
+}
 
-                inner(&new_entries, max_depth.saturating_sub(1), filter, sort, visit)?;
+/// Returns an iterator over all children of the given entry, visiting them recursively in the same order as
+/// [`visit_entries_recursive`].
+///
+/// Each item pairs an entry with its parent entries, starting with the given entry. If the given entry cannot be
+/// traversed, such as when it is a regular file, only the entry itself is returned.
+///
+/// If a directory's children cannot be accessed, the error is returned in their place and iteration continues with
+/// the directory's siblings.
+///
+/// # Examples
+///
+/// ```
+/// use std::fs::Metadata;
+/// use std::path::Path;
+///
+/// use fvr::arguments::model::SortOrder;
+/// use fvr::files::{Entry, entries, visit_entries_recursive};
+///
+/// let filter = recomposition::filter::from_fn(|_: &(Box<Path>, Metadata)| true);
+/// let sort = SortOrder::default();
+/// let root =
+///     || Entry::root(Path::new("src").into(), std::fs::symlink_metadata("src").ok(), &filter);
+/// let mut visited = Vec::new();
+///
+/// visit_entries_recursive(&root(), None, &filter, Some(&sort), &mut |parents, entry| {
+///     visited.push((parents.len(), entry.path.clone()));
+///
+///     Ok(())
+/// })?;
+///
+/// let collected = entries(root(), None, &filter, Some(&sort))
+///     .map(|result| result.map(|(parents, entry)| (parents.len(), entry.path.clone())))
+///     .collect::<std::io::Result<Vec<_>>>()?;
+///
+/// assert_eq!(collected, visited);
+/// assert!(collected.iter().any(|(depth, path)| *depth == 2 && path.ends_with("command/list.rs")));
+///
+/// let first_rust_file = entries(root(), Some(1), &filter, Some(&sort))
+///     .filter_map(Result::ok)
+///     .find(|(_, entry)| entry.path.extension().is_some_and(|extension| extension == "rs"));
+///
+/// assert!(first_rust_file.is_some_and(|(parents, _)| parents.len() == 1));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn entries<'e, F, S>(
+    entry: Entry<'e, F>,
+    max_depth: Option<usize>,
+    filter: &'e F,
+    sort: Option<&'e S>,
+) -> Entries<'e, F, S>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+    S: SortChildren,
+{
+    Entries {
+        filter,
+        sort,
+        max_depth: max_depth.unwrap_or(usize::MAX),
+        levels: Vec::new(),
+        pending: (max_depth != Some(0)).then(|| Rc::new(entry)),
+    }
+}
+
+/// An iterator over the children of an entry, returned by [`entries`].
+pub struct Entries<'e, F, S>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+{
+    /// The filter used to resolve entries.
+    filter: &'e F,
+    /// The order that children are visited in, if any.
+    sort: Option<&'e S>,
+    /// The maximum depth to visit.
+    max_depth: usize,
+    /// The directories currently being visited, from the outermost inwards.
+    levels: Vec<Level<'e, F>>,
+    /// The entry whose children should be read before continuing, if any.
+    pending: Option<Rc<Entry<'e, F>>>,
+}
+
+/// A directory being visited by [`Entries`].
+struct Level<'e, F>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+{
+    /// The directory itself.
+    entry: Rc<Entry<'e, F>>,
+    /// The total number of children within the directory.
+    total: usize,
+    /// The children that have not been visited yet, alongside their indices.
+    children: Enumerate<std::vec::IntoIter<(Box<Path>, Metadata)>>,
+}
+
+impl<'e, F, S> Iterator for Entries<'e, F, S>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+    S: SortChildren,
+{
+    type Item = Result<(Vec<Rc<Entry<'e, F>>>, Rc<Entry<'e, F>>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(entry) = self.pending.take() {
+            // The root is returned as its own child if it cannot be traversed, just as it is visited.
+            if self.levels.is_empty() && !entry.can_traverse() {
+                return Some(Ok((vec![Rc::clone(&entry)], entry)));
             }
 
-            Ok(())
-        })
+            match self::read_children(&entry.path, self.filter, self.sort) {
+                Ok(children) => {
+                    let total = children.len();
+
+                    self.levels.push(Level { entry, total, children: children.into_iter().enumerate() });
+                }
+                Err(error) => return Some(Err(error)),
+            }
+        }
+
+        loop {
+            let Level { total, children, .. } = self.levels.last_mut()?;
+
+            let Some((index, (path, data))) = children.next() else {
+                self.levels.pop();
+
+                continue;
+            };
+
+            let entry = Rc::new(Entry::new(path, Some(data), index, *total, self.filter));
+            let parents: Vec<_> = self.levels.iter().map(|level| Rc::clone(&level.entry)).collect();
+
+            if parents.len() < self.max_depth
+                && entry.has_children()
+                && !entry.is_cycle(&parents.iter().map(Rc::as_ref).collect::<Vec<_>>())
+            {
+                self.pending = Some(Rc::clone(&entry));
+            }
+
+            return Some(Ok((parents, entry)));
+        }
+    }
+}
+
+/// Returns the children of the directory at the given path that match the given filter, sorted if a sort is given.
+fn read_children<F, S>(path: &Path, filter: &F, sort: Option<&S>) -> Result<Children>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+    S: SortChildren,
+{
+    let mut collection = std::fs::read_dir(path)?
+        .map(|v| v.and_then(|v| v.metadata().map(|d| (v.path().into_boxed_path(), d))))
+        .filter(|v| v.as_ref().map_or(true, |v| filter.test(v)))
+        .collect::<Result<Children>>()?;
+
+    if let Some(sort) = sort {
+        sort.sort_children(&mut collection);
     }
 
-    inner(&[entry], max_depth.map_or(usize::MAX, NonZero::get), filter, sort, visit)
+    Ok(collection)
 }
 
 /// Returns `true` if the given path is considered 'hidden'.
@@ -261,6 +699,127 @@ where
     path.as_ref().file_name().and_then(|v| v.as_bytes().first()).copied().is_some_and(|v| v == b'.')
 }
 
+/// Returns the total size of the given path in bytes, including the contents of any directories within it.
+///
+/// Symbolic links are not followed.
+pub fn total_size<P>(path: P, data: &Metadata) -> u64
+where
+    P: AsRef<Path>,
+{
+    if !data.is_dir() {
+        return data.size();
+    }
+
+    std::fs::read_dir(path).map_or(0, |read_dir| {
+        read_dir
+            .filter_map(|entry_result| entry_result.and_then(|entry| Ok((entry.path(), entry.metadata()?))).ok())
+            .map(|(path, data)| self::total_size(path, &data))
+            .sum()
+    })
+}
+
+/// Returns an iterator over the given paths in lexicographic order, regardless of the order that they were given in.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use fvr::files::sorted_paths;
+///
+/// let paths = [Box::from(Path::new("c")), Box::from(Path::new("a")), Box::from(Path::new("b"))];
+///
+/// assert!(
+///     sorted_paths(&paths).eq([Path::new("a"), Path::new("b"), Path::new("c")].map(Box::from))
+/// );
+/// ```
+pub fn sorted_paths(paths: &[Box<Path>]) -> impl Iterator<Item = Box<Path>> {
+    let mut paths = paths.to_vec();
+
+    paths.sort_unstable();
+
+    paths.into_iter()
+}
+
+/// Returns the metadata of every path that was explicitly given to be listed, in the order that they were given.
+///
+/// Paths whose metadata cannot be read are reported to standard error and skipped, in which case this also returns
+/// `true`. Each path is expected to be canonical, meaning that any symbolic links given on the command line have
+/// already been followed, while links found while traversing are not.
+#[must_use]
+pub fn read_roots(paths: Vec<Box<Path>>) -> (Roots, bool) {
+    let mut skipped_paths = false;
+    let paths = paths.into_iter().filter_map(|path| match std::fs::symlink_metadata(&path) {
+        Ok(data) => Some((path, data)),
+        Err(error) => {
+            eprintln!("{}: {error}", path.display());
+
+            skipped_paths = true;
+
+            None
+        }
+    });
+
+    (paths.collect(), skipped_paths)
+}
+
+/// Compares the given dates such that newer dates are sorted first, and missing dates are sorted after all others.
+///
+/// Two missing dates compare equal, so this remains a total order even when some dates could not be read.
+///
+/// # Examples
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// use fvr::files::compare_dates;
+///
+/// let (old, new) = (Some(UNIX_EPOCH), Some(UNIX_EPOCH + Duration::from_secs(1)));
+///
+/// assert_eq!(compare_dates(new, old), Ordering::Less);
+/// assert_eq!(compare_dates(old, None), Ordering::Less);
+/// assert_eq!(compare_dates(None, new), Ordering::Greater);
+/// assert_eq!(compare_dates(None, None), Ordering::Equal);
+/// ```
+#[must_use]
+pub fn compare_dates(lhs: Option<SystemTime>, rhs: Option<SystemTime>) -> std::cmp::Ordering {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => rhs.cmp(&lhs),
+        (lhs, rhs) => lhs.is_none().cmp(&rhs.is_none()),
+    }
+}
+
+/// Returns `true` if the given creation dates show that they are unsupported entirely.
+///
+/// This is only the case if there is at least one result and every result failed because the platform or file system
+/// does not record creation dates, as opposed to any other error.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::{Error, ErrorKind};
+/// use std::time::SystemTime;
+///
+/// use fvr::files::is_created_unsupported;
+///
+/// let unsupported = || Err(Error::from(ErrorKind::Unsupported));
+///
+/// assert!(is_created_unsupported([unsupported(), unsupported()]));
+/// assert!(!is_created_unsupported([unsupported(), Ok(SystemTime::UNIX_EPOCH)]));
+/// assert!(!is_created_unsupported([Err(Error::from(ErrorKind::PermissionDenied))]));
+/// assert!(!is_created_unsupported([]));
+/// ```
+pub fn is_created_unsupported<I>(created: I) -> bool
+where
+    I: IntoIterator<Item = Result<SystemTime>>,
+{
+    let mut created = created.into_iter().peekable();
+
+    created.peek().is_some()
+        && created.all(|result| result.is_err_and(|error| error.kind() == std::io::ErrorKind::Unsupported))
+}
+
 /// Returns a new path that represents the relative path from `root` to `path`.
 ///
 /// Implementation roughly taken from the [`pathdiff`] crate.
@@ -274,10 +833,6 @@ where
     let root = root.as_ref();
     let path = path.as_ref();
 
-    if path.is_absolute() {
-        return Some(path.to_path_buf());
-    }
-
     match (root.is_absolute(), path.is_absolute()) {
         (true, false) => return None,
         (false, true) => return Some(path.to_path_buf()),
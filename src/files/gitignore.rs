@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright © 2025 Jaxydog
+//
+// This file is part of fvr.
+//
+// fvr is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public
+// License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later
+// version.
+//
+// fvr is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License along with fvr. If not,
+// see <https://www.gnu.org/licenses/>.
+
+//! Implements support for excluding entries using `.gitignore` files.
+//!
+//! Only the `.gitignore` files found between an entry and the root of its repository are considered; global excludes
+//! and `.git/info/exclude` are not read.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::rc::Rc;
+
+/// A shared list of ignore files, ordered from outermost to innermost.
+type IgnoreFiles = Rc<[Rc<IgnoreFile>]>;
+
+/// Tests paths against the `.gitignore` files of the repository that they reside within.
+#[derive(Debug, Default)]
+pub struct GitIgnore {
+    /// Caches the ignore files that apply to the entries of each visited directory.
+    cache: RefCell<HashMap<Box<Path>, Option<IgnoreFiles>>>,
+}
+
+impl GitIgnore {
+    /// The name of an ignore file.
+    pub const FILE_NAME: &str = ".gitignore";
+    /// The name of the directory that marks the root of a repository.
+    pub const REPOSITORY_MARKER: &str = ".git";
+
+    /// Creates a new [`GitIgnore`].
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if the given path is ignored by any applicable `.gitignore` file.
+    ///
+    /// Ignore files are applied from the repository root inwards, with later rules taking precedence over earlier ones.
+    #[must_use]
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Some(files) = path.parent().and_then(|parent| self.files(parent)) else { return false };
+
+        let mut ignored = false;
+
+        for file in files.iter() {
+            let Ok(relative) = path.strip_prefix(&file.base) else { continue };
+
+            for rule in file.rules.iter().filter(|rule| rule.matches(relative, is_dir)) {
+                ignored = !rule.negated;
+            }
+        }
+
+        ignored
+    }
+
+    /// Returns the ignore files that apply to entries within the given directory, ordered from outermost to innermost.
+    ///
+    /// Returns [`None`] if the directory is not within a repository.
+    fn files(&self, directory: &Path) -> Option<IgnoreFiles> {
+        if let Some(files) = self.cache.borrow().get(directory) {
+            return files.clone();
+        }
+
+        let files = if directory.join(Self::REPOSITORY_MARKER).exists() {
+            Some(IgnoreFile::load(directory).map(Rc::new).into_iter().collect())
+        } else {
+            directory
+                .parent()
+                .and_then(|parent| self.files(parent))
+                .map(|inherited| inherited.iter().cloned().chain(IgnoreFile::load(directory).map(Rc::new)).collect())
+        };
+
+        self.cache.borrow_mut().insert(Box::from(directory), files.clone());
+
+        files
+    }
+}
+
+/// The rules defined by a single `.gitignore` file.
+#[derive(Debug)]
+struct IgnoreFile {
+    /// The directory containing the file.
+    base: Box<Path>,
+    /// The file's rules, in the order that they were defined.
+    rules: Box<[Rule]>,
+}
+
+impl IgnoreFile {
+    /// Loads the ignore file within the given directory, if one exists.
+    fn load(directory: &Path) -> Option<Self> {
+        let bytes = std::fs::read(directory.join(GitIgnore::FILE_NAME)).ok()?;
+        let rules = bytes.split(|byte| *byte == b'\n').filter_map(Rule::parse).collect();
+
+        Some(Self { base: Box::from(directory), rules })
+    }
+}
+
+/// A single pattern within a `.gitignore` file.
+#[derive(Debug)]
+struct Rule {
+    /// The glob pattern.
+    pattern: Box<[u8]>,
+    /// Whether a match re-includes the entry rather than ignoring it.
+    negated: bool,
+    /// Whether the rule only matches directories.
+    directory_only: bool,
+    /// Whether the pattern is matched against the full relative path rather than just the file name.
+    anchored: bool,
+}
+
+impl Rule {
+    /// Parses a rule from a single line, returning [`None`] if the line is blank or a comment.
+    fn parse(line: &[u8]) -> Option<Self> {
+        let mut line = line.strip_suffix(b"\r").unwrap_or(line);
+
+        // Trailing spaces are ignored unless they are escaped.
+        while let [rest @ .., b' '] = line
+            && !rest.ends_with(b"\\")
+        {
+            line = rest;
+        }
+
+        if line.is_empty() || line.starts_with(b"#") {
+            return None;
+        }
+
+        let (negated, line) = match line {
+            [b'!', rest @ ..] => (true, rest),
+            [b'\\', rest @ ..] if rest.starts_with(b"!") || rest.starts_with(b"#") => (false, rest),
+            line => (false, line),
+        };
+        let (directory_only, line) = line.strip_suffix(b"/").map_or((false, line), |line| (true, line));
+
+        let anchored = line.contains(&b'/');
+        let line = line.strip_prefix(b"/").unwrap_or(line);
+
+        (!line.is_empty()).then(|| Self { pattern: Box::from(line), negated, directory_only, anchored })
+    }
+
+    /// Returns `true` if this rule matches the given path, relative to the directory containing its ignore file.
+    fn matches(&self, relative: &Path, is_dir: bool) -> bool {
+        if self.directory_only && !is_dir {
+            return false;
+        }
+
+        if self.anchored {
+            super::glob::matches(&self.pattern, relative.as_os_str().as_bytes())
+        } else {
+            relative.file_name().is_some_and(|name| super::glob::matches(&self.pattern, name.as_bytes()))
+        }
+    }
+}
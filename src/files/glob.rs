@@ -0,0 +1,220 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright © 2025 Jaxydog
+//
+// This file is part of fvr.
+//
+// fvr is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public
+// License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later
+// version.
+//
+// fvr is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License along with fvr. If not,
+// see <https://www.gnu.org/licenses/>.
+
+//! Implements matching for shell-style glob patterns.
+
+use std::ffi::OsStr;
+use std::io::Result;
+use std::path::{Path, PathBuf};
+
+/// Returns `true` if the given text matches the given glob pattern.
+///
+/// The following syntax is supported:
+///
+/// - `?` matches any single byte other than `/`.
+/// - `*` matches any sequence of bytes other than `/`.
+/// - `**` matches any sequence of bytes, including `/`. When followed by a `/`, it may also match nothing at all.
+/// - `[...]` matches any single byte within the set, which may contain ranges such as `a-z` and may be negated with a
+///   leading `!` or `^`.
+/// - `\` matches the following byte literally.
+///
+/// # Examples
+///
+/// ```
+/// use fvr::files::glob::matches;
+///
+/// assert!(matches("*.rs", "lib.rs"));
+/// assert!(!matches("*.rs", "src/lib.rs"));
+/// assert!(matches("**/*.rs", "src/files/glob.rs"));
+/// assert!(matches("[!a-c]?", "dx"));
+///
+/// // Patterns with many stars are matched in polynomial time, even when they can never match.
+/// assert!(!matches("*a*a*a*a*a*a*a*a*a*a*a*a*b", "a".repeat(200)));
+/// assert!(matches("*a*a*a*a*a*a*a*a*a*a*a*a*b", "a".repeat(200) + "b"));
+/// ```
+pub fn matches<P, T>(pattern: P, text: T) -> bool
+where
+    P: AsRef<[u8]>,
+    T: AsRef<[u8]>,
+{
+    self::matches_bytes(pattern.as_ref(), text.as_ref())
+}
+
+/// Returns `true` if the file name of the given path matches any of the given glob patterns.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use fvr::files::glob::matches_file_name;
+///
+/// let patterns = [Box::from("*.log"), Box::from("core")];
+///
+/// assert!(matches_file_name(&patterns, Path::new("/var/log/boot.log")));
+/// assert!(matches_file_name(&patterns, Path::new("core")));
+/// assert!(!matches_file_name(&patterns, Path::new("/var/log")));
+/// assert!(!matches_file_name(&[], Path::new("boot.log")));
+/// ```
+#[must_use]
+pub fn matches_file_name(patterns: &[Box<str>], path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| patterns.iter().any(|pattern| self::matches(&**pattern, name.as_encoded_bytes())))
+}
+
+/// Returns `true` if the given string contains any glob meta-characters.
+pub fn is_pattern<P>(pattern: P) -> bool
+where
+    P: AsRef<[u8]>,
+{
+    pattern.as_ref().iter().any(|byte| matches!(byte, b'*' | b'?' | b'['))
+}
+
+/// Expands the given glob pattern into all matching paths, sorted by name.
+///
+/// Only the final component of the path may contain a pattern, and it is matched against the entries within its parent
+/// directory, or the current directory if there is no parent. Hidden entries are only matched by patterns that start
+/// with a `.`.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// let paths = fvr::files::glob::expand("src/*.rs")?;
+///
+/// assert!(paths.iter().any(|path| path == Path::new("src/lib.rs")));
+/// assert!(paths.iter().all(|path| path.extension().is_some_and(|extension| extension == "rs")));
+/// assert!(
+///     fvr::files::glob::expand("Cargo.t?ml")?.iter().any(|path| path == Path::new("Cargo.toml"))
+/// );
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if the parent directory could not be read.
+pub fn expand<P>(pattern: P) -> Result<Vec<PathBuf>>
+where
+    P: AsRef<Path>,
+{
+    let pattern = pattern.as_ref();
+    let Some(name_pattern) = pattern.file_name().map(OsStr::as_encoded_bytes) else { return Ok(Vec::new()) };
+    let parent = pattern.parent().filter(|parent| !parent.as_os_str().is_empty());
+
+    if parent.is_some_and(|parent| self::is_pattern(parent.as_os_str().as_encoded_bytes())) {
+        return Ok(Vec::new());
+    }
+
+    let mut paths = Vec::new();
+
+    for entry in std::fs::read_dir(parent.unwrap_or_else(|| Path::new(".")))? {
+        let name = entry?.file_name();
+        let name_bytes = name.as_encoded_bytes();
+
+        if (name_bytes.starts_with(b".") && !name_pattern.starts_with(b".")) || !self::matches(name_pattern, name_bytes)
+        {
+            continue;
+        }
+
+        paths.push(parent.map_or_else(|| PathBuf::from(&name), |parent| parent.join(&name)));
+    }
+
+    paths.sort_unstable();
+
+    Ok(paths)
+}
+
+/// Returns `true` if the given text matches the given glob pattern.
+fn matches_bytes(pattern: &[u8], text: &[u8]) -> bool {
+    // Stars retry the rest of the pattern at every following position, which takes exponential time for patterns with
+    // many stars unless each remainder of the pattern is only ever matched against each remainder of the text once.
+    let mut memo = vec![None; (pattern.len() + 1) * (text.len() + 1)];
+
+    self::matches_remainder(pattern, text, text.len() + 1, &mut memo)
+}
+
+/// Returns `true` if the given remainder of a text matches the given remainder of a glob pattern.
+///
+/// Results are remembered by the lengths of both remainders, where the given stride is one more than the length of the
+/// full text.
+fn matches_remainder(pattern: &[u8], text: &[u8], stride: usize, memo: &mut [Option<bool>]) -> bool {
+    let key = (pattern.len() * stride) + text.len();
+
+    if let Some(matched) = memo[key] {
+        return matched;
+    }
+
+    let mut recurse = |pattern: &[u8], text: &[u8]| self::matches_remainder(pattern, text, stride, memo);
+
+    let matched = match pattern {
+        [] => text.is_empty(),
+        [b'*', b'*', rest @ ..] => {
+            matches!(rest, [b'/', after @ ..] if recurse(after, text))
+                || (0 ..= text.len()).any(|index| recurse(rest, &text[index ..]))
+        }
+        [b'*', rest @ ..] => {
+            // Single stars cannot match a separator, so the rest of the pattern must begin at or before the first one.
+            let end = text.iter().position(|byte| *byte == b'/').unwrap_or(text.len());
+
+            (0 ..= end).any(|index| recurse(rest, &text[index ..]))
+        }
+        [b'?', rest @ ..] => matches!(text, [byte, text @ ..] if *byte != b'/' && recurse(rest, text)),
+        [b'[', class @ ..] => match text {
+            [] => false,
+            [byte, text @ ..] => match self::match_class(class, *byte) {
+                Some((matched, rest)) => matched && *byte != b'/' && recurse(rest, text),
+                None => *byte == b'[' && recurse(class, text),
+            },
+        },
+        [b'\\', expected, rest @ ..] | [expected, rest @ ..] => {
+            matches!(text, [byte, text @ ..] if byte == expected && recurse(rest, text))
+        }
+    };
+
+    memo[key] = Some(matched);
+
+    matched
+}
+
+/// Matches the given byte against a bracketed class, returning whether it matched and the remainder of the pattern.
+///
+/// Returns [`None`] if the class is never closed, in which case the opening bracket should be matched literally.
+fn match_class(pattern: &[u8], byte: u8) -> Option<(bool, &[u8])> {
+    let (negated, mut rest) = match pattern {
+        [b'!' | b'^', rest @ ..] => (true, rest),
+        rest => (false, rest),
+    };
+
+    let mut matched = false;
+    let mut first = true;
+
+    loop {
+        let (start, tail) = match rest {
+            [] => return None,
+            // A closing bracket at the very start of a class is treated as a literal.
+            [b']', tail @ ..] if !first => return Some((matched != negated, tail)),
+            [b'\\', start, tail @ ..] | [start, tail @ ..] => (*start, tail),
+        };
+
+        first = false;
+
+        (matched, rest) = match tail {
+            [b'-', end, tail @ ..] if *end != b']' => (matched || (start ..= *end).contains(&byte), tail),
+            tail => (matched || start == byte, tail),
+        };
+    }
+}
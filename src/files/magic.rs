@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright © 2025 Jaxydog
+//
+// This file is part of fvr.
+//
+// fvr is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public
+// License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later
+// version.
+//
+// fvr is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License along with fvr. If not,
+// see <https://www.gnu.org/licenses/>.
+
+//! Implements detection of file types based on the first few bytes of their contents.
+//!
+//! This only recognizes a handful of common formats, and is intended for coloring rather than identification.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs::{File, Metadata};
+use std::io::Read;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+/// The maximum number of bytes read from the start of a file.
+pub const MAX_LEN: u64 = 16;
+
+/// A kind of file that can be detected from its contents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Magic {
+    /// An ELF executable or library.
+    Elf,
+    /// A script starting with a shebang.
+    Script,
+    /// An image, such as a PNG, JPEG, GIF, or WebP file.
+    Image,
+    /// A compressed archive, such as a gzip, xz, bzip2, zstd, or zip file.
+    Archive,
+}
+
+impl Magic {
+    /// Returns the kind of file that starts with the given bytes, if recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fvr::files::magic::Magic;
+    ///
+    /// assert_eq!(Magic::from_bytes(b"\x7fELF\x02\x01\x01"), Some(Magic::Elf));
+    /// assert_eq!(Magic::from_bytes(b"#!/bin/sh\n"), Some(Magic::Script));
+    /// assert_eq!(Magic::from_bytes(b"\x89PNG\r\n\x1a\n"), Some(Magic::Image));
+    /// assert_eq!(Magic::from_bytes(b"plain text"), None);
+    /// ```
+    #[must_use]
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0x7F, b'E', b'L', b'F', ..] => Some(Self::Elf),
+            [b'#', b'!', ..] => Some(Self::Script),
+            [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n', ..]
+            | [0xFF, 0xD8, 0xFF, ..]
+            | [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..]
+            | [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some(Self::Image),
+            [0x1F, 0x8B, ..]
+            | [0xFD, b'7', b'z', b'X', b'Z', 0x00, ..]
+            | [b'B', b'Z', b'h', ..]
+            | [0x28, 0xB5, 0x2F, 0xFD, ..]
+            | [b'P', b'K', 0x03, 0x04, ..] => Some(Self::Archive),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the kind of the given regular file based on its contents, if recognized.
+///
+/// At most [`MAX_LEN`] bytes are read, and results are cached by inode so that hard links are only read once. Anything
+/// other than a regular file, or a file that cannot be read, is never recognized.
+///
+/// # Examples
+///
+/// ```
+/// use fvr::files::magic::{self, Magic};
+///
+/// let path = std::env::current_exe()?;
+///
+/// assert_eq!(magic::detect(&path, &std::fs::metadata(&path)?), Some(Magic::Elf));
+///
+/// let path = std::env::temp_dir().join(format!("fvr-magic-{}.png", std::process::id()));
+///
+/// std::fs::write(&path, b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR")?;
+///
+/// let kind = magic::detect(&path, &std::fs::metadata(&path)?);
+///
+/// std::fs::remove_file(&path)?;
+///
+/// assert_eq!(kind, Some(Magic::Image));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[must_use]
+pub fn detect(path: &Path, data: &Metadata) -> Option<Magic> {
+    thread_local! {
+        static CACHE: RefCell<HashMap<(u64, u64), Option<Magic>>> = RefCell::new(HashMap::new());
+    }
+
+    if !data.is_file() {
+        return None;
+    }
+
+    let key = (data.dev(), data.ino());
+
+    if let Some(kind) = CACHE.with_borrow(|cache| cache.get(&key).copied()) {
+        return kind;
+    }
+
+    let mut buffer = Vec::new();
+    let kind = File::open(path)
+        .and_then(|file| file.take(MAX_LEN).read_to_end(&mut buffer))
+        .ok()
+        .and_then(|_| Magic::from_bytes(&buffer));
+
+    CACHE.with_borrow_mut(|cache| cache.insert(key, kind));
+
+    kind
+}
@@ -24,49 +24,32 @@
 #![deny(unsafe_code, clippy::missing_safety_doc, clippy::undocumented_unsafe_blocks)]
 // General lints
 #![warn(clippy::cargo, clippy::nursery, clippy::pedantic, missing_docs)]
-// Feature gates
-#![feature(can_vector, io_error_more, path_absolute_method, path_trailing_sep, slice_split_once, write_all_vectored)]
 
 use std::process::ExitCode;
 
-use self::arguments::ParseResult;
-use self::arguments::model::SubCommand;
-
-pub mod arguments;
-pub mod files;
-pub mod section;
-
-/// Defines sub-command implementations.
-pub mod command {
-    pub mod list;
-    pub mod tree;
-}
-
-/// Defines the application's constant exit codes.
-pub mod exit_codes {
-    /// The program ran successfully.
-    pub const SUCCESS: u8 = 0;
-    /// A generic error was encountered.
-    pub const ERROR_GENERIC: u8 = 1;
-    /// An invalid argument or number of arguments were provided.
-    pub const ERROR_CLI_USAGE: u8 = 2;
-}
+use fvr::arguments::ParseResult;
+use fvr::arguments::model::SubCommand;
 
 fn main() -> ExitCode {
-    let arguments = match self::arguments::parse_arguments() {
+    let arguments = match fvr::arguments::parse_arguments() {
         ParseResult::Ok(arguments) => arguments,
         ParseResult::Exit(code) => return ExitCode::from(code),
     };
 
-    if let Err(error) = match &arguments.command {
-        Some(SubCommand::List(_)) => self::command::list::invoke(arguments),
-        Some(SubCommand::Tree(_)) => self::command::tree::invoke(arguments),
+    let result = match &arguments.command {
+        Some(SubCommand::List(_)) => fvr::command::list::invoke(arguments),
+        Some(SubCommand::Tree(_)) => fvr::command::tree::invoke(arguments),
+        Some(SubCommand::Completions(_)) => fvr::command::completions::invoke(arguments).map(|()| false),
         None => unreachable!("a sub-command should have been specified by now"),
-    } {
-        eprintln!("{error}");
+    };
 
-        return ExitCode::from(self::exit_codes::ERROR_GENERIC);
-    }
+    match result {
+        Ok(false) => ExitCode::from(fvr::exit_codes::SUCCESS),
+        Ok(true) => ExitCode::from(fvr::exit_codes::ERROR_PARTIAL),
+        Err(error) => {
+            eprintln!("{error}");
 
-    ExitCode::from(self::exit_codes::SUCCESS)
+            ExitCode::from(fvr::exit_codes::ERROR_GENERIC)
+        }
+    }
 }
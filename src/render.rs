@@ -0,0 +1,615 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright © 2025 Jaxydog
+//
+// This file is part of fvr.
+//
+// fvr is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public
+// License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later
+// version.
+//
+// fvr is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License along with fvr. If not,
+// see <https://www.gnu.org/licenses/>.
+
+//! Renders entries as lines of sections, exactly as the list sub-command does.
+//!
+//! This allows other tools to embed `fvr`'s listing format without going through the command-line interface.
+
+use std::collections::HashMap;
+use std::fs::Metadata;
+use std::io::{Result, Write};
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::rc::Rc;
+
+use recomposition::filter::Filter;
+
+use crate::arguments::model::{ColumnKind, ListArguments, SizeVisibility, TimeVisibility, TreeColor, TreeStyle};
+use crate::files::Entry;
+use crate::section::Section;
+use crate::section::block::BlockSection;
+use crate::section::context::ContextSection;
+use crate::section::disk::DiskSection;
+use crate::section::mode::ModeSection;
+use crate::section::name::NameSection;
+use crate::section::size::SizeSection;
+use crate::section::time::TimeSection;
+use crate::section::tree::TreeSection;
+use crate::section::user::{GroupSection, UserSection};
+use crate::writev;
+
+pub mod html;
+
+/// A single section within each line of a listing.
+#[derive(Clone, Debug)]
+pub enum Column {
+    /// The mode section.
+    Mode(ModeSection),
+    /// The size section.
+    Size(SizeSection),
+    /// The block section.
+    Blocks(BlockSection),
+    /// The disk usage section.
+    Disk(DiskSection),
+    /// A creation, access, modification, or status change date section.
+    Time(TimeSection),
+    /// The user section.
+    User(UserSection),
+    /// The group section.
+    Group(GroupSection),
+    /// The security context section.
+    Context(ContextSection),
+    /// The name section, along with the width that it is padded to if any columns follow it.
+    Name(Option<usize>),
+}
+
+impl Column {
+    /// Returns the label shown above this column within a header row.
+    #[must_use]
+    pub const fn header(&self) -> &'static [u8] {
+        match self {
+            Self::Mode(_) => ModeSection::HEADER,
+            Self::Size(_) => SizeSection::HEADER,
+            Self::Blocks(_) => BlockSection::HEADER,
+            Self::Disk(_) => DiskSection::HEADER,
+            Self::Time(section) => section.header(),
+            Self::User(_) => UserSection::HEADER,
+            Self::Group(_) => GroupSection::HEADER,
+            Self::Context(_) => ContextSection::HEADER,
+            Self::Name(_) => NameSection::HEADER,
+        }
+    }
+
+    /// Returns the width of this column, as of the last time that it was measured.
+    ///
+    /// The name column is only measured if other columns follow it, so this otherwise returns [`None`] for it.
+    #[must_use]
+    pub const fn width(&self) -> Option<usize> {
+        match self {
+            Self::Mode(section) => Some(section.width),
+            Self::Size(section) => Some(section.width),
+            Self::Blocks(section) => Some(section.width),
+            Self::Disk(_) => Some(DiskSection::WIDTH),
+            Self::Time(section) => Some(section.width()),
+            Self::User(section) => Some(section.width),
+            Self::Group(section) => Some(section.width),
+            Self::Context(section) => Some(section.width),
+            Self::Name(width) => *width,
+        }
+    }
+
+    /// Measures this column against the given entries, so that it is wide enough to fit each of them.
+    fn measure<F>(&mut self, entries: &[&Entry<F>])
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        match self {
+            Self::Mode(section) => section.width = section.measure(entries),
+            Self::Size(section) => section.width = section.measure(entries),
+            Self::Blocks(section) => section.width = section.measure(entries),
+            Self::User(section) => section.width = section.measure(entries),
+            Self::Group(section) => section.width = section.measure(entries),
+            Self::Context(section) => section.width = section.measure(entries),
+            Self::Name(width) => *width = width.map(|_| 0),
+            Self::Disk(_) | Self::Time(_) => {}
+        }
+    }
+
+    /// Returns `true` if this column's contents are aligned to the right.
+    #[must_use]
+    pub const fn is_right_aligned(&self) -> bool {
+        match self {
+            Self::Size(section) => !section.visibility.is_simple(),
+            Self::Blocks(_) | Self::Disk(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// The sections that make up each line of a listing.
+#[derive(Clone, Debug)]
+pub struct Columns {
+    /// The sections to write, in order.
+    pub sections: Box<[Column]>,
+    /// The name section.
+    pub name: NameSection,
+    /// The section that draws branches before each name, if entries are listed as a tree.
+    pub tree: Option<TreeSection>,
+    /// Whether columns are widened to fit their header labels.
+    pub header: bool,
+    /// Whether symbolic links are described by their targets' metadata in every column but the name.
+    pub link_target_meta: bool,
+    /// The group number of each listed file's device and inode numbers, if hard links are being found.
+    ///
+    /// Only files that share an inode with another listed file are given a group.
+    pub hardlinks: Option<HashMap<(u64, u64), usize>>,
+}
+
+impl Columns {
+    /// Creates a new [`Columns`] using the given arguments.
+    ///
+    /// If no columns were explicitly requested, they are determined by each column's visibility in a fixed order.
+    /// Explicitly requested columns that would otherwise be hidden are shown using their simplest format.
+    ///
+    /// This must be called before any other threads are spawned, as it determines the local offset used by dates.
+    #[must_use]
+    pub fn new(arguments: &ListArguments, relative_to: Option<Rc<Path>>) -> Self {
+        let offset = crate::section::time::local_offset();
+        let time = |visibility: TimeVisibility| if visibility.is_hide() { TimeVisibility::Simple } else { visibility };
+
+        let kinds = arguments.columns.clone().unwrap_or_else(|| Self::default_kinds(arguments));
+        let sections = kinds.into_iter().map(|kind| match kind {
+            ColumnKind::Mode => Column::Mode(ModeSection::new(arguments.mode.is_extended())),
+            ColumnKind::Size => Column::Size(SizeSection::new(
+                if arguments.size.is_hide() { SizeVisibility::Simple } else { arguments.size },
+                arguments.zero_pad,
+                arguments.dir_size,
+            )),
+            ColumnKind::Blocks => Column::Blocks(BlockSection::new(arguments.block_size)),
+            ColumnKind::Disk => Column::Disk(DiskSection),
+            ColumnKind::Created => {
+                Column::Time(TimeSection::created(time(arguments.created), arguments.time_style, offset))
+            }
+            ColumnKind::Accessed => {
+                Column::Time(TimeSection::accessed(time(arguments.accessed), arguments.time_style, offset))
+            }
+            ColumnKind::Modified => {
+                Column::Time(TimeSection::modified(time(arguments.modified), arguments.time_style, offset))
+            }
+            ColumnKind::Changed => {
+                Column::Time(TimeSection::changed(time(arguments.changed), arguments.time_style, offset))
+            }
+            ColumnKind::User => Column::User(UserSection::default()),
+            ColumnKind::Group => Column::Group(GroupSection::default()),
+            ColumnKind::Context => Column::Context(ContextSection::default()),
+            ColumnKind::Name => Column::Name(None),
+        });
+        let mut sections = sections.collect::<Box<[_]>>();
+
+        // Names are only padded when another column follows them, as lines never end with padding.
+        if let Some((_, rest)) = sections.split_last_mut() {
+            for column in rest {
+                if let Column::Name(width) = column {
+                    *width = Some(0);
+                }
+            }
+        }
+
+        Self {
+            sections,
+            name: NameSection::new(
+                true,
+                arguments.resolve_symlinks,
+                relative_to,
+                arguments.mime,
+                arguments.quoting,
+                arguments.max_name_width,
+            ),
+            tree: arguments.tree.then(|| {
+                TreeSection::new(
+                    arguments.max_depth.unwrap_or(usize::MAX),
+                    false,
+                    TreeColor::default(),
+                    false,
+                    TreeStyle::default(),
+                )
+            }),
+            header: arguments.header,
+            link_target_meta: arguments.link_target_meta,
+            hardlinks: None,
+        }
+    }
+
+    /// Returns the columns that should be shown based on the given arguments, in their default order.
+    fn default_kinds(arguments: &ListArguments) -> Vec<ColumnKind> {
+        let kinds = [
+            (!arguments.mode.is_hide(), ColumnKind::Mode),
+            (!arguments.size.is_hide(), ColumnKind::Size),
+            (arguments.blocks, ColumnKind::Blocks),
+            (arguments.sparse, ColumnKind::Disk),
+            (!arguments.created.is_hide(), ColumnKind::Created),
+            (!arguments.accessed.is_hide(), ColumnKind::Accessed),
+            (!arguments.modified.is_hide(), ColumnKind::Modified),
+            (!arguments.changed.is_hide(), ColumnKind::Changed),
+            (arguments.user, ColumnKind::User),
+            (arguments.group, ColumnKind::Group),
+            (arguments.context, ColumnKind::Context),
+            (true, ColumnKind::Name),
+        ];
+
+        kinds.into_iter().filter_map(|(shown, kind)| shown.then_some(kind)).collect()
+    }
+
+    /// Measures each column against the given entries, so that every line written for them is aligned.
+    ///
+    /// This should be called before writing any of the entries, as each column is otherwise as wide as the widest value
+    /// that it could possibly hold.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::Metadata;
+    /// use std::path::Path;
+    ///
+    /// use fvr::arguments::model::{ListArguments, SizeVisibility, SortOrder};
+    /// use fvr::files::{Entry, visit_entries};
+    /// use fvr::render::{Columns, render_line};
+    ///
+    /// let root = std::env::temp_dir().join(format!("fvr-render-measure-{}", std::process::id()));
+    ///
+    /// std::fs::create_dir_all(&root)?;
+    /// std::fs::write(root.join("large"), [0; 12_345])?;
+    /// std::fs::write(root.join("small"), [0; 1])?;
+    ///
+    /// let arguments =
+    ///     ListArguments { size: SizeVisibility::Simple, user: true, ..ListArguments::default() };
+    /// let mut columns = Columns::new(&arguments, None);
+    /// let filter = recomposition::filter::from_fn(|_: &(Box<Path>, Metadata)| true);
+    /// let parent =
+    ///     Entry::root(root.clone().into_boxed_path(), Some(std::fs::metadata(&root)?), &filter);
+    /// let mut entries = Vec::new();
+    ///
+    /// let result = visit_entries(&parent, &filter, Some(&SortOrder::default()), |_, entry| {
+    ///     entries.push(Entry::new(
+    ///         entry.path.clone(),
+    ///         entry.data.clone(),
+    ///         entry.index,
+    ///         entry.total,
+    ///         &filter,
+    ///     ));
+    ///
+    ///     Ok(())
+    /// });
+    ///
+    /// std::fs::remove_dir_all(&root)?;
+    /// result?;
+    ///
+    /// let entries = entries.iter().collect::<Vec<_>>();
+    /// let mut buffer = Vec::new();
+    ///
+    /// columns.measure(&entries);
+    ///
+    /// for entry in &entries {
+    ///     render_line(&columns, false, &mut buffer, &[&parent], entry)?;
+    /// }
+    ///
+    /// let output = String::from_utf8_lossy(&buffer);
+    /// let lines = output.lines().collect::<Vec<_>>();
+    ///
+    /// assert!(lines[0].starts_with("12345 ") && lines[1].starts_with("1     "));
+    /// assert_eq!(lines[0].find("large"), lines[1].find("small"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn measure<F>(&mut self, entries: &[&Entry<F>])
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        let targets = entries.iter().map(|entry| self.link_target(entry)).collect::<Vec<_>>();
+        let entries =
+            entries.iter().zip(&targets).map(|(entry, target)| target.as_ref().unwrap_or(entry)).collect::<Vec<_>>();
+
+        for column in &mut self.sections {
+            column.measure(&entries);
+        }
+    }
+
+    /// Widens the name column to fit the given entry's name, including any branches drawn before it.
+    ///
+    /// Names are only measured if another column follows them, in which case this should be called for every line after
+    /// the columns are [measured](`Columns::measure`), as the branches drawn depend on each entry's parents.
+    pub fn measure_name<F>(&mut self, parents: &[&Entry<F>], entry: &Entry<F>)
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        let Some(Column::Name(Some(_))) = self.sections.iter().find(|column| matches!(column, Column::Name(_))) else {
+            return;
+        };
+
+        let measured = self.name_width(parents, entry);
+
+        for column in &mut self.sections {
+            if let Column::Name(Some(width)) = column {
+                *width = (*width).max(measured);
+            }
+        }
+    }
+
+    /// Returns the number of columns that the given entry's name takes up on the terminal, including its branches.
+    fn name_width<F>(&self, parents: &[&Entry<F>], entry: &Entry<F>) -> usize
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        let mut buffer = Vec::new();
+        let written = self.write_name(false, &mut buffer, parents, entry);
+
+        written.map_or(0, |()| crate::terminal::display_width(&String::from_utf8_lossy(&buffer)))
+    }
+
+    /// Writes the given entry's name, preceded by its branches if entries are listed as a tree.
+    fn write_name<F>(&self, color: bool, f: &mut impl Write, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        if let Some(tree) = self.tree {
+            tree.write_resolved(color, f, parents, entry)?;
+        }
+
+        self.name.write_resolved(color, f, parents, entry)
+    }
+
+    /// Returns the sum of the sizes shown for the given entries, or [`None`] if sizes are not shown.
+    ///
+    /// Directories are shown without a size, so they do not count towards the total.
+    #[must_use]
+    pub fn total_size<F>(&self, entries: &[&Entry<F>]) -> Option<u64>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        let section = self.sections.iter().find_map(|column| match column {
+            Column::Size(section) => Some(section),
+            _ => None,
+        })?;
+
+        Some(entries.iter().fold(0, |total, entry| {
+            let target = self.link_target(entry);
+
+            total.saturating_add(section.value(target.as_ref().unwrap_or(entry)).unwrap_or(0))
+        }))
+    }
+
+    /// Returns the given symbolic link's target if its metadata should be shown in place of the link's own.
+    ///
+    /// Broken links have no target to describe, so they keep their own metadata.
+    fn link_target<'e, F>(&self, entry: &Entry<'e, F>) -> Option<Entry<'e, F>>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        if !self.link_target_meta || !entry.is_symlink() {
+            return None;
+        }
+
+        std::fs::metadata(&entry.path)
+            .ok()
+            .map(|data| Entry::new(entry.path.clone(), Some(data), entry.index, entry.total, entry.filter))
+    }
+
+    /// Returns the number of bytes needed to widen the given column to fit its header label, if headers are shown.
+    fn padding(&self, column: &Column) -> usize {
+        if !self.header {
+            return 0;
+        }
+
+        column.width().map_or(0, |width| column.header().len().saturating_sub(width))
+    }
+}
+
+/// Writes a header row naming each column, aligned to the widths that the columns were last measured to.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+pub fn render_header(columns: &Columns, color: bool, f: &mut impl Write) -> Result<()> {
+    for (index, column) in columns.sections.iter().enumerate() {
+        if index > 0 {
+            f.write_all(b" ")?;
+        }
+
+        let label = column.header();
+        let padding = vec![b' '; column.width().unwrap_or(0).saturating_sub(label.len())];
+        let [left, right]: [&[u8]; 2] = if column.is_right_aligned() { [&padding, label] } else { [label, &padding] };
+        let cell = &mut Vec::new();
+
+        if color {
+            writev!(cell, [left, right] in White)?;
+        } else {
+            writev!(cell, [left, right])?;
+        }
+
+        self::write_cell(f, column, index + 1 == columns.sections.len(), cell)?;
+    }
+
+    f.write_all(b"\n")
+}
+
+/// Writes a line showing the given total size beneath the size column, in the same format as each entry's size.
+///
+/// The total is labelled in place of a name, and every other column is left blank.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+pub fn render_total(columns: &Columns, color: bool, f: &mut impl Write, total: u64) -> Result<()> {
+    // Blank columns after the total and its label are left out, so that the line does not end with padding.
+    let shown = columns.sections.iter().rposition(|column| matches!(column, Column::Size(_) | Column::Name(_)));
+    let shown = shown.map_or(0, |index| index + 1);
+
+    for (index, column) in columns.sections.iter().take(shown).enumerate() {
+        if index > 0 {
+            f.write_all(b" ")?;
+        }
+
+        let padding = vec![b' '; columns.padding(column)];
+        let cell = &mut Vec::new();
+
+        if column.is_right_aligned() {
+            cell.write_all(&padding)?;
+        }
+
+        match column {
+            Column::Size(section) => section.write_size(color, cell, total)?,
+            Column::Name(_) if color => writev!(cell, [b"total"] in BrightBlack)?,
+            Column::Name(_) => cell.write_all(b"total")?,
+            _ => cell.write_all(&vec![b' '; column.width().unwrap_or(0)])?,
+        }
+
+        if let Column::Name(Some(width)) = column {
+            cell.write_all(&vec![b' '; width.saturating_sub(b"total".len())])?;
+        }
+
+        if !column.is_right_aligned() {
+            cell.write_all(&padding)?;
+        }
+
+        self::write_cell(f, column, index + 1 == shown, cell)?;
+    }
+
+    f.write_all(b"\n")
+}
+
+/// Writes a full line for the given entry, using color if `color` is `true`.
+///
+/// Each section is separated by a single space, and is widened to fit its header label if headers are shown.
+///
+/// # Examples
+///
+/// ```
+/// use std::fs::Metadata;
+/// use std::path::Path;
+///
+/// use fvr::arguments::model::{ListArguments, SizeVisibility};
+/// use fvr::files::Entry;
+/// use fvr::render::{Columns, render_line};
+///
+/// let arguments = ListArguments { size: SizeVisibility::Simple, ..ListArguments::default() };
+/// let columns = Columns::new(&arguments, None);
+///
+/// let path = std::fs::canonicalize("Cargo.toml")?;
+/// let data = std::fs::symlink_metadata(&path)?;
+/// let size = data.len().to_string();
+/// let filter = recomposition::filter::from_fn(|_: &(Box<Path>, Metadata)| true);
+/// let entry = Entry::root(path.into_boxed_path(), Some(data), &filter);
+/// let mut buffer = Vec::new();
+///
+/// render_line(&columns, false, &mut buffer, &[], &entry)?;
+///
+/// let line = String::from_utf8_lossy(&buffer);
+///
+/// assert!(line.starts_with(&size) && line.ends_with(" Cargo.toml\n"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if any section fails to write.
+pub fn render_line<F>(
+    columns: &Columns,
+    color: bool,
+    f: &mut impl Write,
+    parents: &[&Entry<F>],
+    entry: &Entry<F>,
+) -> Result<()>
+where
+    F: Filter<(Box<Path>, Metadata)>,
+{
+    let target = columns.link_target(entry);
+    let data_entry = target.as_ref().unwrap_or(entry);
+
+    for (index, column) in columns.sections.iter().enumerate() {
+        if index > 0 {
+            f.write_all(b" ")?;
+        }
+
+        let padding = vec![b' '; columns.padding(column)];
+        let cell = &mut Vec::new();
+
+        if column.is_right_aligned() {
+            cell.write_all(&padding)?;
+        }
+
+        match column {
+            Column::Mode(section) => section.write_resolved(color, cell, parents, data_entry)?,
+            Column::Size(section) => section.write_resolved(color, cell, parents, data_entry)?,
+            Column::Blocks(section) => section.write_resolved(color, cell, parents, data_entry)?,
+            Column::Disk(section) => section.write_resolved(color, cell, parents, data_entry)?,
+            Column::Time(section) => section.write_resolved(color, cell, parents, data_entry)?,
+            Column::User(section) => section.write_resolved(color, cell, parents, data_entry)?,
+            Column::Group(section) => section.write_resolved(color, cell, parents, data_entry)?,
+            Column::Context(section) => section.write_resolved(color, cell, parents, data_entry)?,
+            Column::Name(width) => {
+                columns.write_name(color, cell, parents, entry)?;
+
+                if let Some(width) = width {
+                    cell.write_all(&vec![b' '; width.saturating_sub(columns.name_width(parents, entry))])?;
+                }
+            }
+        }
+
+        if !column.is_right_aligned() {
+            cell.write_all(&padding)?;
+        }
+
+        self::write_cell(f, column, index + 1 == columns.sections.len(), cell)?;
+    }
+
+    // Hard links are matched by the entry's own identity, so links are never grouped with their targets.
+    let group = entry.data.as_ref().and_then(|data| columns.hardlinks.as_ref()?.get(&(data.dev(), data.ino())));
+
+    if let Some(group) = group {
+        let marker = format!(" [hardlink group {group}]");
+
+        if color {
+            writev!(f, [marker.as_bytes()] in BrightBlack)?;
+        } else {
+            f.write_all(marker.as_bytes())?;
+        }
+    }
+
+    f.write_all(b"\n")
+}
+
+/// Writes a single cell of a line, dropping any padding at its end if it is the last on that line.
+///
+/// Names are never padded when they end a line, so they are written as-is to keep any spaces that they end with.
+fn write_cell(f: &mut impl Write, column: &Column, is_last: bool, cell: &[u8]) -> Result<()> {
+    if !is_last || column.is_right_aligned() || matches!(column, Column::Name(_)) {
+        return f.write_all(cell);
+    }
+
+    // Padding may be written within a color, so any escape sequences after the last visible byte are kept.
+    let (mut end, mut index) = (0, 0);
+    let mut escapes = Vec::new();
+
+    while index < cell.len() {
+        if cell[index] == 0x1B {
+            let length = cell[index ..].iter().skip(2).position(|byte| (0x40 ..= 0x7E).contains(byte));
+            let length = length.map_or(cell.len() - index, |position| position + 3);
+
+            escapes.push(index .. index + length);
+            index += length;
+        } else {
+            if cell[index] != b' ' {
+                end = index + 1;
+                escapes.clear();
+            }
+
+            index += 1;
+        }
+    }
+
+    f.write_all(&cell[.. end])?;
+
+    escapes.into_iter().try_for_each(|range| f.write_all(&cell[range]))
+}
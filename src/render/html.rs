@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright © 2025 Jaxydog
+//
+// This file is part of fvr.
+//
+// fvr is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public
+// License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later
+// version.
+//
+// fvr is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License along with fvr. If not,
+// see <https://www.gnu.org/licenses/>.
+
+//! Converts colored output into HTML, for embedding listings within web pages.
+
+use std::io::{Result, Write};
+
+/// The byte that begins an escape sequence.
+const ESCAPE: u8 = 0x1B;
+
+/// A writer that converts the color escape sequences written by sections into HTML.
+///
+/// Each color is written as a `<span>` with an inline style, and any text is escaped so that it is never interpreted as
+/// markup. Escape sequences other than colors are removed. Whitespace is significant for alignment, so the output is
+/// best placed within a `<pre>` element.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+///
+/// use fvr::color_bytes;
+/// use fvr::render::html::HtmlWriter;
+///
+/// let mut writer = HtmlWriter::new(Vec::new());
+///
+/// writer.write_all(color_bytes!(BrightBlue))?;
+/// writer.write_all(b"<src>")?;
+/// writer.write_all(color_bytes!(Default))?;
+///
+/// let buffer = writer.finish()?;
+///
+/// assert_eq!(String::from_utf8_lossy(&buffer), r#"<span style="color:blue">&lt;src&gt;</span>"#);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct HtmlWriter<W: Write> {
+    /// The writer that HTML is written into.
+    f: W,
+    /// The bytes of an escape sequence that has not been fully written yet.
+    sequence: Vec<u8>,
+    /// Whether a `<span>` is currently open.
+    open: bool,
+}
+
+impl<W: Write> HtmlWriter<W> {
+    /// Creates a new [`HtmlWriter`].
+    #[inline]
+    #[must_use]
+    pub const fn new(f: W) -> Self {
+        Self { f, sequence: Vec::new(), open: false }
+    }
+
+    /// Closes any open `<span>` and returns the inner writer.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if writing fails.
+    pub fn finish(mut self) -> Result<W> {
+        if self.open {
+            self.f.write_all(b"</span>")?;
+        }
+
+        self.f.flush()?;
+
+        Ok(self.f)
+    }
+
+    /// Applies the given select graphic rendition parameters, opening or closing spans as needed.
+    fn apply(&mut self, parameters: &[u8], output: &mut Vec<u8>) {
+        for parameter in parameters.split(|byte| *byte == b';') {
+            let color = match parameter {
+                b"" | b"0" | b"39" => None,
+                v => match self::css_color(v) {
+                    Some(color) => Some(color),
+                    // Anything that is not a foreground color, such as bold text, is left out.
+                    None => continue,
+                },
+            };
+
+            if self.open {
+                output.extend_from_slice(b"</span>");
+            }
+
+            self.open = color.is_some();
+
+            if let Some(color) = color {
+                output.extend_from_slice(b"<span style=\"color:");
+                output.extend_from_slice(color.as_bytes());
+                output.extend_from_slice(b"\">");
+            }
+        }
+    }
+}
+
+impl<W: Write> Write for HtmlWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let mut output = Vec::with_capacity(buf.len());
+
+        for &byte in buf {
+            if !self.sequence.is_empty() {
+                self.sequence.push(byte);
+
+                // Control sequences end with a byte in this range, while any other kind of sequence is dropped.
+                let finished = self.sequence.len() > 2 && (0x40 ..= 0x7E).contains(&byte);
+
+                if self.sequence[1] != b'[' || finished {
+                    let sequence = std::mem::take(&mut self.sequence);
+
+                    if let [ESCAPE, b'[', parameters @ .., b'm'] = &*sequence {
+                        self.apply(parameters, &mut output);
+                    }
+                }
+
+                continue;
+            }
+
+            match byte {
+                ESCAPE => self.sequence.push(byte),
+                b'&' => output.extend_from_slice(b"&amp;"),
+                b'<' => output.extend_from_slice(b"&lt;"),
+                b'>' => output.extend_from_slice(b"&gt;"),
+                b'"' => output.extend_from_slice(b"&quot;"),
+                byte => output.push(byte),
+            }
+        }
+
+        self.f.write_all(&output)?;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.f.flush()
+    }
+}
+
+/// Returns the CSS color that corresponds to the given foreground color parameter, if it is one.
+const fn css_color(parameter: &[u8]) -> Option<&'static str> {
+    Some(match parameter {
+        b"30" => "black",
+        b"31" => "maroon",
+        b"32" => "green",
+        b"33" => "olive",
+        b"34" => "navy",
+        b"35" => "purple",
+        b"36" => "teal",
+        b"37" => "silver",
+        b"90" => "gray",
+        b"91" => "red",
+        b"92" => "lime",
+        b"93" => "yellow",
+        b"94" => "blue",
+        b"95" => "fuchsia",
+        b"96" => "aqua",
+        b"97" => "white",
+        _ => return None,
+    })
+}
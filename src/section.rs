@@ -17,7 +17,7 @@
 //! Provides custom display implementations for various types of file entry data.
 
 use std::fs::Metadata;
-use std::io::{Result, StdoutLock};
+use std::io::{Result, Write};
 use std::path::Path;
 
 use recomposition::filter::Filter;
@@ -25,6 +25,9 @@ use recomposition::filter::Filter;
 use crate::arguments::model::ColorChoice;
 use crate::files::Entry;
 
+pub mod block;
+pub mod context;
+pub mod disk;
 pub mod mode;
 pub mod name;
 pub mod size;
@@ -40,7 +43,7 @@ pub trait Section {
     /// # Errors
     ///
     /// This function will return an error if the section fails to write for any reason.
-    fn write_plain<F>(&self, f: &mut StdoutLock<'_>, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    fn write_plain<F>(&self, f: &mut impl Write, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
     where
         F: Filter<(Box<Path>, Metadata)>;
 
@@ -49,32 +52,43 @@ pub trait Section {
     /// # Errors
     ///
     /// This function will return an error if the section fails to write for any reason.
-    fn write_color<F>(&self, f: &mut StdoutLock<'_>, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    fn write_color<F>(&self, f: &mut impl Write, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
     where
         F: Filter<(Box<Path>, Metadata)>;
 
+    /// Returns the width that this section takes up when written for each of the given entries.
+    ///
+    /// This is measured before any of the entries are written, so that their lines can be aligned. Sections that have
+    /// no fixed width, such as names, take up no width at all.
+    fn measure<F>(&self, _entries: &[&Entry<F>]) -> usize
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        0
+    }
+
     /// Writes this section into the given writer, determining whether to use color based on the given [`ColorChoice`].
     ///
     /// # Errors
     ///
     /// This function will return an error if the section fails to write for any reason.
-    fn write<F>(
-        &self,
-        color: ColorChoice,
-        f: &mut StdoutLock<'_>,
-        parents: &[&Entry<F>],
-        entry: &Entry<F>,
-    ) -> Result<()>
+    fn write<F>(&self, color: ColorChoice, f: &mut impl Write, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
     where
         F: Filter<(Box<Path>, Metadata)>,
     {
-        use supports_color::{Stream, on_cached};
+        self.write_resolved(color.resolve(), f, parents, entry)
+    }
 
-        if color.is_always() || (color.is_auto() && on_cached(Stream::Stdout).is_some_and(|v| v.has_basic)) {
-            self.write_color(f, parents, entry)
-        } else {
-            self.write_plain(f, parents, entry)
-        }
+    /// Writes this section into the given writer, using color if `color` is `true`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the section fails to write for any reason.
+    fn write_resolved<F>(&self, color: bool, f: &mut impl Write, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        if color { self.write_color(f, parents, entry) } else { self.write_plain(f, parents, entry) }
     }
 }
 
@@ -83,7 +97,9 @@ pub trait Section {
 /// # Examples
 ///
 /// ```
-/// color_bytes!(BrightRed);
+/// use fvr::color_bytes;
+///
+/// assert_eq!(color_bytes!(BrightRed), b"\x1b[91m");
 /// ```
 #[macro_export]
 macro_rules! color_bytes {
@@ -97,8 +113,14 @@ macro_rules! color_bytes {
 /// # Examples
 ///
 /// ```
+/// # #![feature(can_vector, write_all_vectored)]
+/// use fvr::writev;
+///
+/// let f = &mut Vec::new();
+///
 /// writev!(f, [b"some bytes", b"and more bytes"])?;
-/// writev!(f, [b"and even more bytes"] in BrightRed)
+/// writev!(f, [b"and even more bytes"] in BrightRed)?;
+/// # Ok::<(), std::io::Error>(())
 /// ```
 #[macro_export]
 macro_rules! writev {
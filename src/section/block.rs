@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright © 2025 Jaxydog
+//
+// This file is part of fvr.
+//
+// fvr is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public
+// License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later
+// version.
+//
+// fvr is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License along with fvr. If not,
+// see <https://www.gnu.org/licenses/>.
+
+//! Implements a section that displays an entry's allocated blocks.
+
+use std::fs::Metadata;
+use std::io::{Result, Write};
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+use recomposition::filter::Filter;
+
+use super::Section;
+use super::size::SizeSection;
+use crate::arguments::model::BlockSize;
+use crate::files::Entry;
+use crate::writev;
+
+/// A [`Section`] that writes the number of blocks allocated to an entry.
+///
+/// Unlike [`SizeSection`], this reflects the space an entry actually occupies on disk, meaning that sparse files will
+/// show fewer blocks than their logical size would suggest. Blocks are counted in units of the given [`BlockSize`],
+/// rounding up.
+#[derive(Clone, Copy, Debug)]
+pub struct BlockSection {
+    /// The unit that blocks are counted in.
+    pub block_size: BlockSize,
+    /// The width that each block count is padded to, which is the assumed maximum width until it is measured.
+    pub width: usize,
+}
+
+impl BlockSection {
+    /// The size of a single block as reported by the system, in bytes.
+    pub const BLOCK_SIZE: u64 = 512;
+    /// The byte that represents a lack of blocks.
+    pub const CHAR_BLANK: u8 = b'-';
+    /// The byte used for padding.
+    pub const CHAR_PADDING: u8 = b' ';
+    /// The label shown above this section within a header row.
+    pub const HEADER: &[u8] = b"BLOCKS";
+    /// The assumed maximum width of a block count.
+    pub const MAX_WIDTH: usize = 20;
+
+    /// Creates a new [`BlockSection`].
+    #[inline]
+    #[must_use]
+    pub const fn new(block_size: BlockSize) -> Self {
+        Self { block_size, width: Self::MAX_WIDTH }
+    }
+
+    /// Returns the number of blocks that the given entry occupies, counted in this section's unit.
+    #[must_use]
+    pub fn blocks(&self, data: &Metadata) -> u64 {
+        data.blocks().saturating_mul(Self::BLOCK_SIZE).div_ceil(self.block_size.bytes())
+    }
+}
+
+impl Section for BlockSection {
+    fn measure<F>(&self, entries: &[&Entry<F>]) -> usize
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        // Entries without metadata are shown as a single dash, so that is the minimum width.
+        entries
+            .iter()
+            .map(|entry| {
+                entry.data.as_ref().map_or(1, |data| self.blocks(data).checked_ilog10().map_or(1, |v| v + 1) as usize)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn write_plain<F>(&self, f: &mut impl Write, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        let Some(blocks) = entry.data.as_ref().map(|data| self.blocks(data)) else {
+            return writev!(f, [&vec![Self::CHAR_PADDING; self.width.saturating_sub(1)], &[Self::CHAR_BLANK]]);
+        };
+
+        let mut buffer = itoa::Buffer::new();
+        let bytes = buffer.format(blocks).as_bytes();
+        let padding = vec![Self::CHAR_PADDING; self.width.saturating_sub(bytes.len())];
+
+        writev!(f, [&padding, bytes])
+    }
+
+    fn write_color<F>(&self, f: &mut impl Write, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        let Some(blocks) = entry.data.as_ref().map(|data| self.blocks(data)) else {
+            return writev!(f, [&vec![Self::CHAR_PADDING; self.width.saturating_sub(1)], &[Self::CHAR_BLANK]] in BrightBlack);
+        };
+
+        let mut buffer = itoa::Buffer::new();
+        let bytes = buffer.format(blocks).as_bytes();
+        let padding = vec![Self::CHAR_PADDING; self.width.saturating_sub(bytes.len())];
+
+        match blocks.saturating_mul(self.block_size.bytes()) {
+            v if v < SizeSection::MEDIUM_THRESHOLD => writev!(f, [&padding, bytes] in BrightGreen),
+            v if v < SizeSection::LARGE_THRESHOLD => writev!(f, [&padding, bytes] in BrightYellow),
+            _ => writev!(f, [&padding, bytes] in BrightRed),
+        }
+    }
+}
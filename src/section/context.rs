@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright © 2025 Jaxydog
+//
+// This file is part of fvr.
+//
+// fvr is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public
+// License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later
+// version.
+//
+// fvr is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License along with fvr. If not,
+// see <https://www.gnu.org/licenses/>.
+
+//! Implements a section that displays an entry's security context.
+
+#[cfg(target_os = "linux")]
+use std::ffi::CString;
+use std::fs::Metadata;
+use std::io::{Result, Write};
+#[cfg(target_os = "linux")]
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+use recomposition::filter::Filter;
+
+use super::Section;
+use crate::files::Entry;
+use crate::writev;
+
+/// A [`Section`] that writes an entry's `SELinux` security context.
+///
+/// Contexts are read from each entry's `security.selinux` extended attribute. On systems without `SELinux`, or for
+/// entries whose context cannot be read, a dash is written instead.
+#[derive(Clone, Copy, Debug)]
+pub struct ContextSection {
+    /// The width that each context is padded to, which is the assumed maximum length until it is measured.
+    pub width: usize,
+}
+
+impl ContextSection {
+    /// The name of the extended attribute that holds an entry's security context.
+    pub const ATTRIBUTE: &[u8] = b"security.selinux\0";
+    /// The byte used when the context is missing.
+    pub const CHAR_MISSING: u8 = b'-';
+    /// The byte used for padding.
+    pub const CHAR_PADDING: u8 = b' ';
+    /// The label shown above this section within a header row.
+    pub const HEADER: &[u8] = b"CONTEXT";
+    /// The assumed maximum length of a security context.
+    pub const MAX_LEN: usize = 64;
+
+    /// Returns the security context of the entry at the given path, or [`None`] if it cannot be read.
+    ///
+    /// Symbolic links are not followed, so a link's own context is returned.
+    #[cfg(target_os = "linux")]
+    #[must_use]
+    pub fn context(path: &Path) -> Option<Box<[u8]>> {
+        let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+        let mut buffer = vec![0_u8; 256];
+
+        loop {
+            // SAFETY: Both strings are null-terminated, and at most `buffer.len()` bytes are written into the buffer,
+            // which outlives the call.
+            #[expect(unsafe_code, reason = "extended attributes can only be read through a system call")]
+            let length = unsafe {
+                libc::lgetxattr(
+                    path.as_ptr(),
+                    Self::ATTRIBUTE.as_ptr().cast(),
+                    buffer.as_mut_ptr().cast(),
+                    buffer.len(),
+                )
+            };
+
+            if let Ok(length) = usize::try_from(length) {
+                buffer.truncate(length);
+
+                break;
+            }
+
+            // The buffer was too small, so it is grown until the context fits.
+            if std::io::Error::last_os_error().raw_os_error() != Some(libc::ERANGE) || buffer.len() >= 4096 {
+                return None;
+            }
+
+            buffer.resize(buffer.len() * 2, 0);
+        }
+
+        // Contexts are usually stored with a trailing null byte, which should not be shown.
+        if buffer.last() == Some(&0) {
+            buffer.pop();
+        }
+
+        (!buffer.is_empty()).then(|| buffer.into_boxed_slice())
+    }
+
+    /// Returns the security context of the entry at the given path, or [`None`] if it cannot be read.
+    ///
+    /// Extended attributes are only read on Linux, so entries never have a context on any other system.
+    #[cfg(not(target_os = "linux"))]
+    #[must_use]
+    pub const fn context(_: &Path) -> Option<Box<[u8]>> {
+        None
+    }
+}
+
+impl Default for ContextSection {
+    fn default() -> Self {
+        Self { width: Self::MAX_LEN }
+    }
+}
+
+impl Section for ContextSection {
+    fn measure<F>(&self, entries: &[&Entry<F>]) -> usize
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        // Entries without a context are shown as a single dash, so that is the minimum width.
+        entries.iter().map(|entry| Self::context(&entry.path).map_or(1, |v| v.len())).max().unwrap_or(0)
+    }
+
+    fn write_plain<F>(&self, f: &mut impl Write, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        let Some(context) = Self::context(&entry.path) else {
+            return writev!(f, [&[Self::CHAR_MISSING], &vec![Self::CHAR_PADDING; self.width.saturating_sub(1)]]);
+        };
+
+        let padding = vec![Self::CHAR_PADDING; self.width.saturating_sub(context.len())];
+
+        writev!(f, [&context, &padding])
+    }
+
+    fn write_color<F>(&self, f: &mut impl Write, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        let Some(context) = Self::context(&entry.path) else {
+            return writev!(f, [&[Self::CHAR_MISSING], &vec![Self::CHAR_PADDING; self.width.saturating_sub(1)]]);
+        };
+
+        let padding = vec![Self::CHAR_PADDING; self.width.saturating_sub(context.len())];
+
+        writev!(f, [&context, &padding] in Cyan)
+    }
+}
@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright © 2025 Jaxydog
+//
+// This file is part of fvr.
+//
+// fvr is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public
+// License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later
+// version.
+//
+// fvr is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License along with fvr. If not,
+// see <https://www.gnu.org/licenses/>.
+
+//! Implements a section that displays an entry's disk usage, marking sparse files.
+
+use std::fs::Metadata;
+use std::io::{Result, Write};
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+use recomposition::filter::Filter;
+
+use super::Section;
+use super::block::BlockSection;
+use super::size::SizeSection;
+use crate::files::Entry;
+use crate::writev;
+
+/// A [`Section`] that writes the space an entry occupies on disk, followed by a marker if it is a sparse file.
+///
+/// Disk usage is computed from an entry's allocated blocks rather than its logical size, and files that occupy less
+/// space on disk than their logical size are considered sparse.
+#[derive(Clone, Copy, Debug)]
+pub struct DiskSection;
+
+impl DiskSection {
+    /// The byte that represents a lack of disk usage.
+    pub const CHAR_BLANK: u8 = b'-';
+    /// The byte used for padding.
+    pub const CHAR_PADDING: u8 = b' ';
+    /// The byte that marks a sparse file.
+    pub const CHAR_SPARSE: u8 = b'*';
+    /// The label shown above this section within a header row.
+    pub const HEADER: &[u8] = b"DISK";
+    /// The width of this section, including the sparse marker.
+    pub const WIDTH: usize = SizeSection::WIDTH_BASE_2 + 1;
+
+    /// Returns the given entry's disk usage in bytes, and whether it is a sparse file.
+    ///
+    /// This returns [`None`] if the entry has no metadata.
+    fn usage<F>(entry: &Entry<F>) -> Option<(u64, bool)>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        let data = entry.data.as_ref()?;
+        let usage = data.blocks().saturating_mul(BlockSection::BLOCK_SIZE);
+
+        Some((usage, data.is_file() && usage < data.size()))
+    }
+}
+
+impl Section for DiskSection {
+    fn measure<F>(&self, _: &[&Entry<F>]) -> usize
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        Self::WIDTH
+    }
+
+    fn write_plain<F>(&self, f: &mut impl Write, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        let Some((usage, sparse)) = Self::usage(entry) else {
+            return writev!(f, [&[Self::CHAR_PADDING; Self::WIDTH - 2], &[Self::CHAR_BLANK, Self::CHAR_PADDING]]);
+        };
+
+        let usage = super::size::units::format_base_2(usage);
+        let padding = vec![Self::CHAR_PADDING; (Self::WIDTH - 1).saturating_sub(usage.len())];
+        let marker = if sparse { Self::CHAR_SPARSE } else { Self::CHAR_PADDING };
+
+        writev!(f, [&padding, usage.as_bytes(), &[marker]])
+    }
+
+    fn write_color<F>(&self, f: &mut impl Write, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        let Some((usage, sparse)) = Self::usage(entry) else {
+            return writev!(f, [&[Self::CHAR_PADDING; Self::WIDTH - 2], &[Self::CHAR_BLANK, Self::CHAR_PADDING]] in BrightBlack);
+        };
+
+        let text = super::size::units::format_base_2(usage);
+        let padding = vec![Self::CHAR_PADDING; (Self::WIDTH - 1).saturating_sub(text.len())];
+
+        match usage {
+            v if v < SizeSection::MEDIUM_THRESHOLD => writev!(f, [&padding, text.as_bytes()] in BrightGreen)?,
+            v if v < SizeSection::LARGE_THRESHOLD => writev!(f, [&padding, text.as_bytes()] in BrightYellow)?,
+            _ => writev!(f, [&padding, text.as_bytes()] in BrightRed)?,
+        }
+
+        if sparse { writev!(f, [&[Self::CHAR_SPARSE]] in BrightCyan) } else { f.write_all(&[Self::CHAR_PADDING]) }
+    }
+}
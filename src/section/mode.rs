@@ -16,8 +16,12 @@
 
 //! Implements a section that displays an entry's file type and permissions.
 
+#[cfg(target_os = "linux")]
+use std::ffi::CString;
 use std::fs::Metadata;
-use std::io::{Result, StdoutLock};
+use std::io::{Result, Write};
+#[cfg(target_os = "linux")]
+use std::os::unix::ffi::OsStrExt;
 use std::os::unix::fs::MetadataExt;
 use std::path::Path;
 
@@ -110,9 +114,22 @@ pub mod permissions {
 pub struct ModeSection {
     /// Whether to use an extended permission format.
     pub extended: bool,
+    /// The width that each mode is padded to.
+    ///
+    /// This leaves room for the marker written for entries with an access control list until it is measured, after
+    /// which the marker is only written if there is room for it.
+    pub width: usize,
 }
 
 impl ModeSection {
+    /// The name of the extended attribute that holds an entry's access control list.
+    pub const ACL_ATTRIBUTE: &[u8] = b"system.posix_acl_access\0";
+    /// The byte that marks an entry with an access control list.
+    pub const CHAR_ACL: u8 = b'+';
+    /// The byte used for padding.
+    pub const CHAR_PADDING: u8 = b' ';
+    /// The label shown above this section within a header row.
+    pub const HEADER: &[u8] = b"MODE";
     /// The byte used to represent an empty permission.
     pub const PERM_EMPTY: u8 = b'-';
     /// The byte used to represent an execute permission.
@@ -148,26 +165,51 @@ impl ModeSection {
     #[inline]
     #[must_use]
     pub const fn new(extended: bool) -> Self {
-        Self { extended }
+        Self { extended, width: Self::base_width(extended) + 1 }
     }
 
-    /// Returns a series of bytes that represent the file type for the given mode.
+    /// Returns the width of a mode without the access control list marker.
+    const fn base_width(extended: bool) -> usize {
+        if extended { 15 } else { 12 }
+    }
+
+    /// Returns `true` if the entry at the given path has an access control list beyond its permission bits.
+    ///
+    /// This only checks whether the list is present, so entries whose list cannot be read are treated as having none.
+    #[cfg(target_os = "linux")]
     #[must_use]
-    pub const fn get_type(mode: u32) -> u8 {
-        use self::file_type::{
-            BLOCK_DEVICE, CHARACTER_DEVICE, DIRECTORY, FIFO_PIPE, FILE, MASK, SOCKET, SYMBOLIC_LINK,
+    pub fn has_acl(path: &Path) -> bool {
+        let Ok(path) = CString::new(path.as_os_str().as_bytes()) else {
+            return false;
         };
 
-        match mode & MASK {
-            FILE => Self::TYPE_FILE,
-            DIRECTORY => Self::TYPE_DIRECTORY,
-            SYMBOLIC_LINK => Self::TYPE_SYMBOLIC_LINK,
-            FIFO_PIPE => Self::TYPE_FIFO_PIPE,
-            SOCKET => Self::TYPE_SOCKET,
-            BLOCK_DEVICE => Self::TYPE_BLOCK_DEVICE,
-            CHARACTER_DEVICE => Self::TYPE_CHARACTER_DEVICE,
-            _ => Self::TYPE_UNKNOWN,
-        }
+        // SAFETY: Both strings are null-terminated, and a null buffer with a size of zero only queries the length of
+        // the attribute, so nothing is written.
+        #[expect(unsafe_code, reason = "extended attributes can only be read through a system call")]
+        let length =
+            unsafe { libc::lgetxattr(path.as_ptr(), Self::ACL_ATTRIBUTE.as_ptr().cast(), std::ptr::null_mut(), 0) };
+
+        length > 0
+    }
+
+    /// Returns `true` if the entry at the given path has an access control list beyond its permission bits.
+    ///
+    /// Extended attributes are only read on Linux, so entries never have a list on any other system.
+    #[cfg(not(target_os = "linux"))]
+    #[must_use]
+    pub const fn has_acl(_: &Path) -> bool {
+        false
+    }
+
+    /// Returns the marker and padding written after the given entry's permissions.
+    fn suffix<F>(self, entry: &Entry<F>) -> (bool, Vec<u8>)
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        let base_width = Self::base_width(self.extended);
+        let acl = self.width > base_width && entry.has_acl();
+
+        (acl, vec![Self::CHAR_PADDING; self.width.saturating_sub(base_width + usize::from(acl))])
     }
 
     /// Returns a series of bytes that represent the permissions for the given mode.
@@ -195,25 +237,36 @@ impl ModeSection {
 }
 
 impl Section for ModeSection {
-    fn write_plain<F>(&self, f: &mut StdoutLock<'_>, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    fn measure<F>(&self, entries: &[&Entry<F>]) -> usize
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        Self::base_width(self.extended) + usize::from(entries.iter().any(|entry| entry.has_acl()))
+    }
+
+    fn write_plain<F>(&self, f: &mut impl Write, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
     where
         F: Filter<(Box<Path>, Metadata)>,
     {
         let mode = entry.data.as_ref().map(MetadataExt::mode).unwrap_or_default();
         let permissions = Self::get_permissions(mode);
+        let (acl, padding) = self.suffix(entry);
 
-        writev!(f, [&[b'[', Self::get_type(mode)], if self.extended { &permissions } else { &permissions[3 ..] }, b"]"])
+        writev!(f, [
+            &[b'[', entry.file_type_char()],
+            if self.extended { &permissions } else { &permissions[3 ..] },
+            if acl { &[Self::CHAR_ACL, b']'] } else { b"]" },
+            &padding
+        ])
     }
 
-    fn write_color<F>(&self, f: &mut StdoutLock<'_>, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    fn write_color<F>(&self, f: &mut impl Write, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
     where
         F: Filter<(Box<Path>, Metadata)>,
     {
         writev!(f, [b"["] in White)?;
 
-        let mode = entry.data.as_ref().map(MetadataExt::mode).unwrap_or_default();
-
-        match Self::get_type(mode) {
+        match entry.file_type_char() {
             v @ Self::TYPE_DIRECTORY => writev!(f, [&[v]] in BrightBlue)?,
             v @ Self::TYPE_SYMBOLIC_LINK => writev!(f, [&[v]] in BrightCyan)?,
             v @ Self::TYPE_FIFO_PIPE => writev!(f, [&[v]] in BrightYellow)?,
@@ -224,6 +277,7 @@ impl Section for ModeSection {
             _ => unreachable!(),
         }
 
+        let mode = entry.data.as_ref().map(MetadataExt::mode).unwrap_or_default();
         let permissions = Self::get_permissions(mode);
         let mut buffer = Vec::<u8>::with_capacity(permissions.len() * 6);
 
@@ -243,6 +297,14 @@ impl Section for ModeSection {
         }
 
         writev!(f, [&buffer])?;
-        writev!(f, [b"]"] in White)
+
+        let (acl, padding) = self.suffix(entry);
+
+        if acl {
+            writev!(f, [&[Self::CHAR_ACL]] in BrightCyan)?;
+        }
+
+        writev!(f, [b"]"] in White)?;
+        writev!(f, [&padding])
     }
 }
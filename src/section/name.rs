@@ -17,86 +17,234 @@
 //! Implements sections related to entry names.
 
 use std::borrow::Cow;
+use std::ffi::{OsStr, OsString};
 use std::fs::Metadata;
-use std::io::{ErrorKind, Result, StdoutLock};
+use std::io::{ErrorKind, Result, Write};
+use std::num::NonZero;
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
 use std::path::Path;
+use std::rc::Rc;
 
 use recomposition::filter::Filter;
 
 use super::Section;
+use super::mode::ModeSection;
+use crate::arguments::model::Quoting;
 use crate::files::Entry;
+use crate::files::magic::Magic;
 use crate::writev;
 
 /// A [`Section`] that writes an entry's name.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct NameSection {
     /// Whether to trim the entry to just its name or to render the full path.
     pub trim_paths: bool,
     /// Whether to resolve the actual path of symbolic links.
     pub resolve_symlinks: bool,
+    /// The directory that rendered paths should be made relative to, taking precedence over trimming.
+    pub relative_to: Option<Rc<Path>>,
+    /// Whether to color regular files based on their contents.
+    pub mime: bool,
+    /// Determines how special characters within names are written.
+    pub quoting: Quoting,
+    /// The maximum number of columns that names may take up before being truncated.
+    pub max_width: Option<NonZero<usize>>,
 }
 
 impl NameSection {
+    /// The sequence written after names that contain ANSI escape sequences, resetting any colors that they set.
+    pub const ANSI_RESET: &[u8] = b"\x1b[0m";
+    /// The suffix used for entries that would cause a traversal cycle.
+    pub const CYCLE_SUFFIX: &[u8] = b" [cycle]";
     /// The suffix used for directories.
     pub const DIR_SUFFIX: &[u8] = b"/";
     /// The suffix used for executable files.
     pub const EXE_SUFFIX: &[u8] = b"*";
+    /// The label shown above this section within a header row.
+    pub const HEADER: &[u8] = b"NAME";
     /// The suffix used for symbolic links.
     pub const SYMLINK_SUFFIX: &[u8] = b"@";
+    /// The character written at the end of truncated names.
+    pub const TRUNCATION_MARKER: char = '…';
 
     /// Creates a new [`NameSection`].
     #[inline]
     #[must_use]
-    pub const fn new(trim_paths: bool, resolve_symlinks: bool) -> Self {
-        Self { trim_paths, resolve_symlinks }
+    pub const fn new(
+        trim_paths: bool,
+        resolve_symlinks: bool,
+        relative_to: Option<Rc<Path>>,
+        mime: bool,
+        quoting: Quoting,
+        max_width: Option<NonZero<usize>>,
+    ) -> Self {
+        Self { trim_paths, resolve_symlinks, relative_to, mime, quoting, max_width }
+    }
+
+    /// Returns the name that should be rendered for the given entry.
+    ///
+    /// Any width measured for a name must be measured from this, since escaped characters take up more than a single
+    /// byte. Names that are written with their escape sequences intact are followed by [`NameSection::ANSI_RESET`].
+    fn name<'e, F>(&self, entry: &'e Entry<F>) -> Cow<'e, OsStr>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        let name = self.relative_to.as_deref().map_or_else(
+            || {
+                Cow::Borrowed((if self.trim_paths { entry.file_name() } else { None }).unwrap_or_else(|| {
+                    // This is so that the directory suffix is only ever written once.
+                    entry.path.trim_trailing_sep().as_os_str()
+                }))
+            },
+            |base| match crate::files::relativize(base, &entry.path) {
+                Some(path) if path.as_os_str().is_empty() => Cow::Borrowed(OsStr::new(".")),
+                Some(path) => Cow::Owned(path.into_os_string()),
+                None => Cow::Borrowed(entry.path.as_os_str()),
+            },
+        );
+
+        let name = if name.as_encoded_bytes().iter().any(|byte| self.quoting.escapes(*byte)) {
+            Self::escape(&name, self.quoting)
+        } else {
+            name
+        };
+
+        let name = match self.max_width {
+            Some(max_width) => Self::truncate(name, max_width.get()),
+            None => name,
+        };
+
+        if self.quoting.is_raw() && name.as_encoded_bytes().contains(&0x1B) {
+            let mut name = name.into_owned();
+
+            name.push(OsStr::from_bytes(Self::ANSI_RESET));
+
+            Cow::Owned(name)
+        } else {
+            name
+        }
+    }
+
+    /// Returns the given name truncated to fit within the given number of columns, ending with
+    /// [`NameSection::TRUNCATION_MARKER`] if anything was removed.
+    ///
+    /// Names are truncated by whole characters, so any invalid UTF-8 within a truncated name is replaced.
+    fn truncate(name: Cow<'_, OsStr>, max_width: usize) -> Cow<'_, OsStr> {
+        let text = name.to_string_lossy();
+
+        if crate::terminal::display_width(&text) <= max_width {
+            return name;
+        }
+
+        let marker_width = crate::terminal::char_width(Self::TRUNCATION_MARKER);
+        let mut width = 0;
+        let mut truncated: String = text
+            .chars()
+            .take_while(|character| {
+                width += crate::terminal::char_width(*character);
+
+                width + marker_width <= max_width
+            })
+            .collect();
+
+        truncated.push(Self::TRUNCATION_MARKER);
+
+        Cow::Owned(OsString::from(truncated))
+    }
+
+    /// Returns the given name with each byte escaped by the given quoting replaced by an escape sequence, such as `\t`
+    /// or `\x1b`.
+    fn escape(name: &OsStr, quoting: Quoting) -> Cow<'static, OsStr> {
+        let mut bytes = Vec::with_capacity(name.len() + 1);
+
+        for byte in name.as_encoded_bytes() {
+            match *byte {
+                byte if !quoting.escapes(byte) => bytes.push(byte),
+                b'\t' => bytes.extend_from_slice(b"\\t"),
+                b'\n' => bytes.extend_from_slice(b"\\n"),
+                b'\r' => bytes.extend_from_slice(b"\\r"),
+                // Writing into a vector never fails.
+                byte => _ = write!(bytes, "\\x{byte:02x}"),
+            }
+        }
+
+        Cow::Owned(OsString::from_vec(bytes))
     }
 }
 
 impl Section for NameSection {
-    fn write_plain<F>(&self, f: &mut StdoutLock<'_>, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    fn write_plain<F>(&self, f: &mut impl Write, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
     where
         F: Filter<(Box<Path>, Metadata)>,
     {
-        let name = (if self.trim_paths { entry.file_name() } else { None }).unwrap_or_else(|| {
-            // This is so that the directory suffix is only ever written once.
-            entry.path.trim_trailing_sep().as_os_str()
-        });
-
-        if entry.is_symlink() {
-            writev!(f, [name.as_encoded_bytes(), Self::SYMLINK_SUFFIX])?;
-        } else if entry.is_dir() && !name.as_encoded_bytes().eq_ignore_ascii_case(b"/") {
-            writev!(f, [name.as_encoded_bytes(), Self::DIR_SUFFIX])?;
-        } else if entry.is_file() && entry.is_executable() {
-            writev!(f, [name.as_encoded_bytes(), Self::EXE_SUFFIX])?;
-        } else {
-            writev!(f, [name.as_encoded_bytes()])?;
+        let name = self.name(entry);
+        let file_type = entry.file_type_char();
+        let is_symlink = file_type == ModeSection::TYPE_SYMBOLIC_LINK;
+
+        match file_type {
+            ModeSection::TYPE_SYMBOLIC_LINK => writev!(f, [name.as_encoded_bytes(), Self::SYMLINK_SUFFIX])?,
+            ModeSection::TYPE_DIRECTORY if !name.as_encoded_bytes().eq_ignore_ascii_case(b"/") => {
+                writev!(f, [name.as_encoded_bytes(), Self::DIR_SUFFIX])?;
+            }
+            ModeSection::TYPE_FILE if entry.is_executable() => {
+                writev!(f, [name.as_encoded_bytes(), Self::EXE_SUFFIX])?;
+            }
+            _ => writev!(f, [name.as_encoded_bytes()])?,
+        }
+
+        if self.resolve_symlinks && is_symlink {
+            SymlinkSection::new(self.quoting, self.max_width).write_plain(f, parents, entry)?;
         }
 
-        if self.resolve_symlinks && entry.is_symlink() { SymlinkSection.write_plain(f, parents, entry) } else { Ok(()) }
+        if is_symlink && entry.is_cycle(parents) { writev!(f, [Self::CYCLE_SUFFIX]) } else { Ok(()) }
     }
 
-    fn write_color<F>(&self, f: &mut StdoutLock<'_>, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    fn write_color<F>(&self, f: &mut impl Write, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
     where
         F: Filter<(Box<Path>, Metadata)>,
     {
-        let name = (if self.trim_paths { entry.file_name() } else { None }).unwrap_or_else(|| {
-            // This is so that the directory suffix is only ever written once.
-            entry.path.trim_trailing_sep().as_os_str()
-        });
+        let name = self.name(entry);
         let name = name.as_encoded_bytes();
-
-        if entry.is_symlink() {
-            if entry.is_hidden() { writev!(f, [name] in Cyan) } else { writev!(f, [name] in BrightCyan) }?;
+        let file_type = entry.file_type_char();
+
+        if file_type == ModeSection::TYPE_SYMBOLIC_LINK {
+            // Checking for existence follows the link, so this only fails if its target is missing.
+            if !entry.path.try_exists().unwrap_or(true) {
+                if entry.is_hidden() { writev!(f, [name] in Red) } else { writev!(f, [name] in BrightRed) }?;
+            } else if entry.is_hidden() {
+                writev!(f, [name] in Cyan)?;
+            } else {
+                writev!(f, [name] in BrightCyan)?;
+            }
 
             writev!(f, [Self::SYMLINK_SUFFIX] in White)?;
 
-            if self.resolve_symlinks { SymlinkSection.write_color(f, parents, entry) } else { Ok(()) }
-        } else if entry.is_dir() {
+            if self.resolve_symlinks {
+                SymlinkSection::new(self.quoting, self.max_width).write_color(f, parents, entry)?;
+            }
+
+            if entry.is_cycle(parents) { writev!(f, [Self::CYCLE_SUFFIX] in Cyan) } else { Ok(()) }
+        } else if file_type == ModeSection::TYPE_DIRECTORY {
             if !name.eq_ignore_ascii_case(b"/") {
                 if entry.is_hidden() { writev!(f, [name] in Blue) } else { writev!(f, [name] in BrightBlue) }?;
             }
 
             writev!(f, [Self::DIR_SUFFIX] in White)
+        } else if self.mime
+            && let Some(kind) = entry.data.as_ref().and_then(|data| crate::files::magic::detect(&entry.path, data))
+        {
+            match kind {
+                Magic::Elf if entry.is_hidden() => writev!(f, [name] in Green),
+                Magic::Elf => writev!(f, [name] in BrightGreen),
+                Magic::Script if entry.is_hidden() => writev!(f, [name] in Yellow),
+                Magic::Script => writev!(f, [name] in BrightYellow),
+                Magic::Image if entry.is_hidden() => writev!(f, [name] in Magenta),
+                Magic::Image => writev!(f, [name] in BrightMagenta),
+                Magic::Archive if entry.is_hidden() => writev!(f, [name] in Red),
+                Magic::Archive => writev!(f, [name] in BrightRed),
+            }?;
+
+            if entry.is_executable() { writev!(f, [Self::EXE_SUFFIX] in White) } else { Ok(()) }
         } else if entry.is_executable() {
             if entry.is_hidden() { writev!(f, [name] in Green) } else { writev!(f, [name] in BrightGreen) }?;
 
@@ -111,7 +259,12 @@ impl Section for NameSection {
 
 /// A [`Section`] that writes an entry's resolved symbolic link.
 #[derive(Clone, Copy, Debug)]
-pub struct SymlinkSection;
+pub struct SymlinkSection {
+    /// How characters within the target are quoted.
+    pub quoting: Quoting,
+    /// The width that the target is truncated to, if any.
+    pub max_width: Option<NonZero<usize>>,
+}
 
 impl SymlinkSection {
     /// The arrow used when a symbolic link is broken.
@@ -120,14 +273,34 @@ impl SymlinkSection {
     pub const LINKED_ARROW: &[u8] = b"-->";
     /// The arrow used when a symbolic link is recursive.
     pub const RECURSIVE_ARROW: &[u8] = b"<->";
+
+    /// Creates a new [`SymlinkSection`].
+    #[must_use]
+    pub const fn new(quoting: Quoting, max_width: Option<NonZero<usize>>) -> Self {
+        Self { quoting, max_width }
+    }
+
+    /// Returns the section used to write the target, which is quoted and truncated just like the link's own name.
+    const fn target(&self) -> NameSection {
+        NameSection::new(false, false, None, false, self.quoting, self.max_width)
+    }
+
+    /// Returns the label written in place of an unreadable target, such as `[error: permission denied]`.
+    fn error_label(error: &std::io::Error) -> Vec<u8> {
+        format!("[error: {}]", error.kind()).into_bytes()
+    }
 }
 
 impl Section for SymlinkSection {
-    fn write_plain<F>(&self, f: &mut StdoutLock<'_>, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    fn write_plain<F>(&self, f: &mut impl Write, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
     where
         F: Filter<(Box<Path>, Metadata)>,
     {
-        let link_path = std::fs::read_link(&entry.path)?;
+        // Failing to read a single target should not prevent the rest of the entries from being written.
+        let link_path = match std::fs::read_link(&entry.path) {
+            Ok(link_path) => link_path,
+            Err(error) => return writev!(f, [b" ", Self::BROKEN_ARROW, b" ", &Self::error_label(&error)]),
+        };
         let real_path = if link_path.is_relative()
             && let Some(parent) = parents.last().map(|entry| &entry.path)
         {
@@ -142,13 +315,13 @@ impl Section for SymlinkSection {
             Err(error) if error.kind() == ErrorKind::FilesystemLoop => {
                 writev!(f, [b" ", Self::RECURSIVE_ARROW, b" "])?;
 
-                let path = crate::files::relativize(&entry.path, &link_path).unwrap_or_else(|| link_path.clone());
+                let path = link_path.clone();
                 let data = std::fs::symlink_metadata(real_path.as_ref()).ok();
                 let entry = Entry::root(path.into_boxed_path(), data, entry.filter);
 
-                return NameSection { trim_paths: false, resolve_symlinks: false }.write_plain(f, parents, &entry);
+                return self.target().write_plain(f, parents, &entry);
             }
-            Err(error) => return Err(error),
+            Err(error) => return writev!(f, [b" ", Self::BROKEN_ARROW, b" ", &Self::error_label(&error)]),
         };
 
         if data.is_some() {
@@ -157,17 +330,24 @@ impl Section for SymlinkSection {
             writev!(f, [b" ", Self::BROKEN_ARROW, b" "])?;
         }
 
-        let path = crate::files::relativize(&entry.path, &link_path).unwrap_or(link_path);
+        let path = link_path;
         let entry = Entry::root(path.into_boxed_path(), data, entry.filter);
 
-        NameSection { trim_paths: false, resolve_symlinks: false }.write_plain(f, parents, &entry)
+        self.target().write_plain(f, parents, &entry)
     }
 
-    fn write_color<F>(&self, f: &mut StdoutLock<'_>, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    fn write_color<F>(&self, f: &mut impl Write, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
     where
         F: Filter<(Box<Path>, Metadata)>,
     {
-        let link_path = std::fs::read_link(&entry.path)?;
+        let link_path = match std::fs::read_link(&entry.path) {
+            Ok(link_path) => link_path,
+            Err(error) => {
+                writev!(f, [b" ", Self::BROKEN_ARROW, b" "] in BrightRed)?;
+
+                return writev!(f, [&Self::error_label(&error)] in Red);
+            }
+        };
         let real_path = if link_path.is_relative()
             && let Some(parent) = parents.last().map(|entry| &entry.path)
         {
@@ -182,13 +362,17 @@ impl Section for SymlinkSection {
             Err(error) if error.kind() == ErrorKind::FilesystemLoop => {
                 writev!(f, [b" ", Self::RECURSIVE_ARROW, b" "] in Cyan)?;
 
-                let path = crate::files::relativize(&entry.path, &link_path).unwrap_or_else(|| link_path.clone());
+                let path = link_path.clone();
                 let data = std::fs::symlink_metadata(real_path.as_ref()).ok();
                 let entry = Entry::root(path.into_boxed_path(), data, entry.filter);
 
-                return NameSection { trim_paths: false, resolve_symlinks: false }.write_color(f, parents, &entry);
+                return self.target().write_color(f, parents, &entry);
+            }
+            Err(error) => {
+                writev!(f, [b" ", Self::BROKEN_ARROW, b" "] in BrightRed)?;
+
+                return writev!(f, [&Self::error_label(&error)] in Red);
             }
-            Err(error) => return Err(error),
         };
 
         if data.is_some() {
@@ -197,9 +381,9 @@ impl Section for SymlinkSection {
             writev!(f, [b" ", Self::BROKEN_ARROW, b" "] in BrightRed)?;
         }
 
-        let path = crate::files::relativize(&entry.path, &link_path).unwrap_or(link_path);
+        let path = link_path;
         let entry = Entry::root(path.into_boxed_path(), data, entry.filter);
 
-        NameSection { trim_paths: false, resolve_symlinks: false }.write_color(f, parents, &entry)
+        self.target().write_color(f, parents, &entry)
     }
 }
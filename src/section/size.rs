@@ -16,17 +16,15 @@
 
 //! Implements a section that displays an entry's size.
 
-use std::collections::HashMap;
 use std::fs::Metadata;
-use std::io::{Result, StdoutLock};
+use std::io::{Result, Write};
 use std::os::unix::fs::MetadataExt;
 use std::path::Path;
-use std::sync::Mutex;
 
 use recomposition::filter::Filter;
 
 use super::Section;
-use crate::arguments::model::SizeVisibility;
+use crate::arguments::model::{DirectorySize, SizeVisibility};
 use crate::files::Entry;
 use crate::writev;
 
@@ -89,32 +87,67 @@ pub mod units {
     }
 
     /// Returns the given size converted to a human-readable unit.
+    ///
+    /// Sizes that would be rounded up to a whole multiple of the next unit are converted to that unit instead, such
+    /// that `1048575` bytes is `1.00 MiB` rather than `1024 KiB`.
     #[must_use]
     pub const fn get_base_2(size: u64) -> (f64, Unit<3>) {
         match size {
             v if v < KIBIBYTES.divisor => (BYTES_2.convert(v), BYTES_2),
-            v if v < MEBIBYTES.divisor => (KIBIBYTES.convert(v), KIBIBYTES),
-            v if v < GIBIBYTES.divisor => (MEBIBYTES.convert(v), MEBIBYTES),
-            v if v < TEBIBYTES.divisor => (GIBIBYTES.convert(v), GIBIBYTES),
-            v if v < PEBIBYTES.divisor => (TEBIBYTES.convert(v), TEBIBYTES),
-            v if v < EXBIBYTES.divisor => (PEBIBYTES.convert(v), PEBIBYTES),
+            v if v < self::round_up_from(KIBIBYTES, MEBIBYTES) => (KIBIBYTES.convert(v), KIBIBYTES),
+            v if v < self::round_up_from(MEBIBYTES, GIBIBYTES) => (MEBIBYTES.convert(v), MEBIBYTES),
+            v if v < self::round_up_from(GIBIBYTES, TEBIBYTES) => (GIBIBYTES.convert(v), GIBIBYTES),
+            v if v < self::round_up_from(TEBIBYTES, PEBIBYTES) => (TEBIBYTES.convert(v), TEBIBYTES),
+            v if v < self::round_up_from(PEBIBYTES, EXBIBYTES) => (PEBIBYTES.convert(v), PEBIBYTES),
             v => (EXBIBYTES.convert(v), EXBIBYTES),
         }
     }
 
+    /// Returns the given size as a human-readable string in base 2, such as `1.48 KiB`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fvr::section::size::units::format_base_2;
+    ///
+    /// assert_eq!(format_base_2(512), "512 B");
+    /// assert_eq!(format_base_2(1_100), "1.07 KiB");
+    /// assert_eq!(format_base_2(1_048_575), "1.00 MiB");
+    /// ```
+    #[must_use]
+    pub fn format_base_2(size: u64) -> String {
+        let (scaled_size, unit) = self::get_base_2(size);
+        let number = super::SizeSection::format_scaled(scaled_size, if unit == BYTES_2 { 0 } else { 2 });
+
+        // Both the number and the suffix are always ASCII, so this never actually replaces anything.
+        let number = String::from_utf8_lossy(number.trim_ascii());
+        let suffix = String::from_utf8_lossy(unit.suffix.trim_ascii());
+
+        format!("{number} {suffix}")
+    }
+
     /// Returns the given size converted to a human-readable unit.
+    ///
+    /// Sizes that would be rounded up to a whole multiple of the next unit are converted to that unit instead, such
+    /// that `999999` bytes is `1.00 MB` rather than `1000 KB`.
     #[must_use]
     pub const fn get_base_10(size: u64) -> (f64, Unit<2>) {
         match size {
             v if v < KILOBYTES.divisor => (BYTES_10.convert(v), BYTES_10),
-            v if v < MEGABYTES.divisor => (KILOBYTES.convert(v), KILOBYTES),
-            v if v < GIGABYTES.divisor => (MEGABYTES.convert(v), MEGABYTES),
-            v if v < TERABYTES.divisor => (GIGABYTES.convert(v), GIGABYTES),
-            v if v < PETABYTES.divisor => (TERABYTES.convert(v), TERABYTES),
-            v if v < EXABYTES.divisor => (PETABYTES.convert(v), PETABYTES),
+            v if v < self::round_up_from(KILOBYTES, MEGABYTES) => (KILOBYTES.convert(v), KILOBYTES),
+            v if v < self::round_up_from(MEGABYTES, GIGABYTES) => (MEGABYTES.convert(v), MEGABYTES),
+            v if v < self::round_up_from(GIGABYTES, TERABYTES) => (GIGABYTES.convert(v), GIGABYTES),
+            v if v < self::round_up_from(TERABYTES, PETABYTES) => (TERABYTES.convert(v), TERABYTES),
+            v if v < self::round_up_from(PETABYTES, EXABYTES) => (PETABYTES.convert(v), PETABYTES),
             v => (EXABYTES.convert(v), EXABYTES),
         }
     }
+
+    /// Returns the smallest size that is rounded up to the next unit when shown without decimal places in the given
+    /// unit.
+    const fn round_up_from<const N: usize>(unit: Unit<N>, next: Unit<N>) -> u64 {
+        next.divisor - (unit.divisor / 2)
+    }
 }
 
 /// A [`Section`] that writes an entry's size.
@@ -122,6 +155,15 @@ pub mod units {
 pub struct SizeSection {
     /// Determines the size format to use.
     pub visibility: SizeVisibility,
+    /// Whether simple sizes are padded with leading zeros, rather than trailing spaces.
+    pub zero_pad: bool,
+    /// Determines what is written in place of a directory's size.
+    pub dir_size: DirectorySize,
+    /// The width that each size is padded to.
+    ///
+    /// Simple sizes are padded to the assumed maximum width until they are measured, while other formats always have a
+    /// fixed width.
+    pub width: usize,
 }
 
 impl SizeSection {
@@ -131,6 +173,10 @@ impl SizeSection {
     pub const CHAR_DECIMAL: u8 = b'.';
     /// The byte used for padding.
     pub const CHAR_PADDING: u8 = b' ';
+    /// The byte used for padding simple sizes with leading zeros.
+    pub const CHAR_ZERO: u8 = b'0';
+    /// The label shown above this section within a header row.
+    pub const HEADER: &[u8] = b"SIZE";
     /// Files above this are considered 'large'.
     pub const LARGE_THRESHOLD: u64 = 50 * self::units::MEBIBYTES.divisor;
     /// Files above this are considered 'medium'.
@@ -143,52 +189,173 @@ impl SizeSection {
     pub const WIDTH_BASE_10: usize = 8;
     /// The width of a base-2 output.
     pub const WIDTH_BASE_2: usize = 10;
+    /// The width of a scaled number within a base-2 or base-10 output.
+    pub const WIDTH_NUMBER: usize = 4;
     /// The width of a simple size output.
     pub const WIDTH_SIMPLE: usize = 20;
 
     /// Creates a new [`SizeSection`].
     #[inline]
     #[must_use]
-    pub const fn new(visibility: SizeVisibility) -> Self {
-        Self { visibility }
+    pub const fn new(visibility: SizeVisibility, zero_pad: bool, dir_size: DirectorySize) -> Self {
+        let width = match visibility {
+            SizeVisibility::Simple => Self::WIDTH_SIMPLE,
+            SizeVisibility::Base2 => Self::WIDTH_BASE_2,
+            SizeVisibility::Base10 => Self::WIDTH_BASE_10,
+            SizeVisibility::Hide => 0,
+        };
+
+        Self { visibility, zero_pad, dir_size, width }
+    }
+
+    /// Returns the size written for the given entry, or [`None`] if it is a directory, as their sizes are never shown.
+    #[must_use]
+    pub fn value<F>(&self, entry: &Entry<F>) -> Option<u64>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        (!entry.is_dir()).then(|| entry.data.as_ref().map_or(0, MetadataExt::size))
     }
 
-    /// Returns the maximum length that all simple size sections in the given directory will take up.
-    #[expect(clippy::unwrap_used, reason = "lock must not be poisoned")]
-    fn max_simple_len(parent: &Path) -> usize {
-        thread_local! {
-            static CACHE: Mutex<HashMap<Box<Path>, usize>> = Mutex::new(HashMap::new());
+    /// Writes the given size in this section's format, using color if `color` is `true`.
+    ///
+    /// Entry sizes and totals are both written through this, so that they are always formatted in the same way.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if writing fails.
+    pub fn write_size(&self, color: bool, f: &mut impl Write, size: u64) -> Result<()> {
+        if self.visibility.is_simple() {
+            let mut buffer = itoa::Buffer::new();
+            let bytes = buffer.format(size).as_bytes();
+
+            let padding = vec![
+                if self.zero_pad { Self::CHAR_ZERO } else { Self::CHAR_PADDING };
+                self.width.saturating_sub(bytes.len())
+            ];
+            let (left, right) = if self.zero_pad { (&padding[..], bytes) } else { (bytes, &padding[..]) };
+
+            return match size {
+                _ if !color => writev!(f, [left, right]),
+                v if v < Self::MEDIUM_THRESHOLD => writev!(f, [left, right] in BrightGreen),
+                v if v < Self::LARGE_THRESHOLD => writev!(f, [left, right] in BrightYellow),
+                _ => writev!(f, [left, right] in BrightRed),
+            };
         }
 
-        CACHE.with(|cache| {
-            *cache.lock().unwrap().entry(Box::from(parent)).or_insert_with(|| {
-                std::fs::read_dir(parent)
-                    .ok()
-                    .and_then(|read_result| {
-                        read_result
-                            .map_while(|entry_result| entry_result.and_then(|entry| entry.metadata()).ok())
-                            .map(|entry_metadata| entry_metadata.len())
-                            .map(|length| if length == 0 { 0 } else { length.ilog10() + 1 } as usize)
-                            .max()
-                    })
-                    .unwrap_or(Self::WIDTH_SIMPLE)
-            })
-        })
+        let (scaled_size, suffix, padding): (f64, &[u8], &[u8]) = if self.visibility.is_base2() {
+            let (scaled_size, unit) = self::units::get_base_2(size);
+
+            (scaled_size, unit.suffix, Self::PAD_BASE_2)
+        } else {
+            let (scaled_size, unit) = self::units::get_base_10(size);
+
+            (scaled_size, unit.suffix, Self::PAD_BASE_10)
+        };
+
+        let number = &Self::format_scaled(scaled_size, if suffix.starts_with(b"B") { 0 } else { 2 });
+        let padding = &padding[.. padding.len() - (number.len() + 1 + suffix.len())];
+
+        match size {
+            _ if !color => writev!(f, [padding, number, &[Self::CHAR_PADDING], suffix]),
+            v if v < Self::MEDIUM_THRESHOLD => {
+                writev!(f, [padding, number, &[Self::CHAR_PADDING], suffix] in BrightGreen)
+            }
+            v if v < Self::LARGE_THRESHOLD => {
+                writev!(f, [padding, number, &[Self::CHAR_PADDING], suffix] in BrightYellow)
+            }
+            _ => writev!(f, [padding, number, &[Self::CHAR_PADDING], suffix] in BrightRed),
+        }
+    }
+
+    /// Returns the child count written in place of the given directory's size, padded to the given length.
+    ///
+    /// Simple sizes are aligned just like file sizes, while other formats right-align the count before the unit suffix.
+    /// This returns [`None`] if child counts are not shown or if the directory cannot be read.
+    fn child_count<F>(self, entry: &Entry<F>, length: usize) -> Option<Vec<u8>>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        if !self.dir_size.is_child_count() {
+            return None;
+        }
+
+        let mut buffer = itoa::Buffer::new();
+        let count = buffer.format(entry.child_count()?).as_bytes();
+        let suffix_length = match self.visibility {
+            SizeVisibility::Base2 => self::units::BYTES_2.suffix.len() + 1,
+            SizeVisibility::Base10 => self::units::BYTES_10.suffix.len() + 1,
+            SizeVisibility::Simple | SizeVisibility::Hide => 0,
+        };
+        let padding = length.saturating_sub(suffix_length + count.len());
+        let mut bytes = Vec::with_capacity(padding + count.len() + suffix_length);
+
+        if self.visibility.is_simple() && !self.zero_pad {
+            bytes.extend_from_slice(count);
+            bytes.resize(bytes.len() + padding, Self::CHAR_PADDING);
+        } else {
+            bytes.resize(padding, if self.zero_pad { Self::CHAR_ZERO } else { Self::CHAR_PADDING });
+            bytes.extend_from_slice(count);
+            bytes.resize(bytes.len() + suffix_length, Self::CHAR_PADDING);
+        }
+
+        Some(bytes)
+    }
+
+    /// Formats the given scaled size, right-aligned, using as many decimal places as fit up to the given maximum.
+    ///
+    /// This shows two decimal places below 10 units, one below 100 units, and none otherwise, rounding as needed.
+    fn format_scaled(scaled_size: f64, max_precision: usize) -> [u8; Self::WIDTH_NUMBER] {
+        let mut buffer = [Self::CHAR_PADDING; Self::WIDTH_NUMBER];
+
+        for precision in (0 ..= max_precision).rev() {
+            // Writing fails once the number no longer fits, in which case we retry with fewer decimal places.
+            if write!(&mut buffer[..], "{scaled_size:>width$.precision$}", width = Self::WIDTH_NUMBER).is_ok() {
+                break;
+            }
+        }
+
+        buffer
     }
 }
 
 impl Section for SizeSection {
-    fn write_plain<F>(&self, f: &mut StdoutLock<'_>, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    fn measure<F>(&self, entries: &[&Entry<F>]) -> usize
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        if !self.visibility.is_simple() {
+            return self.width;
+        }
+
+        entries
+            .iter()
+            .map(|entry| {
+                // Directories are shown as a single dash unless their child count is written in its place.
+                let value = if entry.is_dir() {
+                    self.dir_size.is_child_count().then(|| entry.child_count()).flatten().map(|count| count as u64)
+                } else {
+                    Some(entry.data.as_ref().map_or(0, MetadataExt::size))
+                };
+
+                value.map_or(1, |value| value.checked_ilog10().map_or(1, |digits| digits + 1) as usize)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn write_plain<F>(&self, f: &mut impl Write, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
     where
         F: Filter<(Box<Path>, Metadata)>,
     {
         if entry.is_dir() {
-            let parent_path = parents.last().map_or_else(|| entry.path.parent(), |parent| Some(&parent.path));
-            let length = parent_path.map_or(Self::WIDTH_SIMPLE, Self::max_simple_len);
+            if let Some(count) = self.child_count(entry, self.width) {
+                return writev!(f, [&count]);
+            }
 
             return match self.visibility {
                 SizeVisibility::Simple => {
-                    writev!(f, [&[Self::CHAR_BLANK], &vec![Self::CHAR_PADDING; length - 1]])
+                    writev!(f, [&[Self::CHAR_BLANK], &vec![Self::CHAR_PADDING; self.width.saturating_sub(1)]])
                 }
                 SizeVisibility::Base2 => writev!(f, [
                     &[Self::CHAR_PADDING; 3],
@@ -205,51 +372,21 @@ impl Section for SizeSection {
             };
         }
 
-        let size = entry.data.as_ref().map_or(0, MetadataExt::size);
-
-        if self.visibility.is_simple() {
-            let mut buffer = itoa::Buffer::new();
-            let bytes = buffer.format(size).as_bytes();
-
-            let parent_path = parents.last().map_or_else(|| entry.path.parent(), |parent| Some(&parent.path));
-            let length = parent_path.map_or(Self::WIDTH_SIMPLE, Self::max_simple_len);
-            let padding = vec![Self::CHAR_PADDING; length];
-            let padding = &padding[.. length - bytes.len()];
-
-            return writev!(f, [bytes, padding]);
-        }
-
-        let (scaled_size, suffix, padding): (f64, &[u8], &[u8]) = if self.visibility.is_base2() {
-            let (scaled_size, unit) = self::units::get_base_2(size);
-
-            (scaled_size, unit.suffix, Self::PAD_BASE_2)
-        } else {
-            let (scaled_size, unit) = self::units::get_base_10(size);
-
-            (scaled_size, unit.suffix, Self::PAD_BASE_10)
-        };
-
-        let mut buffer = zmij::Buffer::new();
-        let bytes = buffer.format(scaled_size).as_bytes();
-        let Some((whole, decimal)) = bytes.split_once(|b| *b == Self::CHAR_DECIMAL) else { unreachable!() };
-        let decimal = &[Self::CHAR_DECIMAL, decimal[0], Self::CHAR_PADDING];
-
-        let padding = &padding[.. padding.len() - (whole.len() + 3 + suffix.len())];
-
-        writev!(f, [padding, whole, decimal, suffix])
+        self.write_size(false, f, entry.data.as_ref().map_or(0, MetadataExt::size))
     }
 
-    fn write_color<F>(&self, f: &mut StdoutLock<'_>, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    fn write_color<F>(&self, f: &mut impl Write, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
     where
         F: Filter<(Box<Path>, Metadata)>,
     {
         if entry.is_dir() {
-            let parent_path = parents.last().map_or_else(|| entry.path.parent(), |parent| Some(&parent.path));
-            let length = parent_path.map_or(Self::WIDTH_SIMPLE, Self::max_simple_len);
+            if let Some(count) = self.child_count(entry, self.width) {
+                return writev!(f, [&count] in BrightBlack);
+            }
 
             return match self.visibility {
                 SizeVisibility::Simple => {
-                    writev!(f, [&[Self::CHAR_BLANK], &vec![Self::CHAR_PADDING; length - 1]] in BrightBlack)
+                    writev!(f, [&[Self::CHAR_BLANK], &vec![Self::CHAR_PADDING; self.width.saturating_sub(1)]] in BrightBlack)
                 }
                 SizeVisibility::Base2 => writev!(f, [
                     &[Self::CHAR_PADDING; 3],
@@ -266,45 +403,6 @@ impl Section for SizeSection {
             };
         }
 
-        let size = entry.data.as_ref().map_or(0, MetadataExt::size);
-
-        if self.visibility.is_simple() {
-            let mut buffer = itoa::Buffer::new();
-            let bytes = buffer.format(size).as_bytes();
-
-            let parent_path = parents.last().map_or_else(|| entry.path.parent(), |parent| Some(&parent.path));
-            let length = parent_path.map_or(Self::WIDTH_SIMPLE, Self::max_simple_len);
-            let padding = vec![Self::CHAR_PADDING; length];
-            let padding = &padding[.. length - bytes.len()];
-
-            return match size {
-                v if v < Self::MEDIUM_THRESHOLD => writev!(f, [bytes, padding] in BrightGreen),
-                v if v < Self::LARGE_THRESHOLD => writev!(f, [bytes, padding] in BrightYellow),
-                _ => writev!(f, [bytes, padding] in BrightRed),
-            };
-        }
-
-        let (scaled_size, suffix, padding): (f64, &[u8], &[u8]) = if self.visibility.is_base2() {
-            let (scaled_size, unit) = self::units::get_base_2(size);
-
-            (scaled_size, unit.suffix, Self::PAD_BASE_2)
-        } else {
-            let (scaled_size, unit) = self::units::get_base_10(size);
-
-            (scaled_size, unit.suffix, Self::PAD_BASE_10)
-        };
-
-        let mut buffer = zmij::Buffer::new();
-        let bytes = buffer.format(scaled_size).as_bytes();
-        let Some((whole, decimal)) = bytes.split_once(|b| *b == Self::CHAR_DECIMAL) else { unreachable!() };
-        let decimal = &[Self::CHAR_DECIMAL, decimal[0], Self::CHAR_PADDING];
-
-        let padding = &padding[.. padding.len() - (whole.len() + 3 + suffix.len())];
-
-        match size {
-            v if v < Self::MEDIUM_THRESHOLD => writev!(f, [padding, whole, decimal, suffix] in BrightGreen),
-            v if v < Self::LARGE_THRESHOLD => writev!(f, [padding, whole, decimal, suffix] in BrightYellow),
-            _ => writev!(f, [padding, whole, decimal, suffix] in BrightRed),
-        }
+        self.write_size(true, f, entry.data.as_ref().map_or(0, MetadataExt::size))
     }
 }
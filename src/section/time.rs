@@ -17,7 +17,8 @@
 //! Implements sections related to entry timestamps.
 
 use std::fs::Metadata;
-use std::io::{Result, StdoutLock};
+use std::io::{Result, Write};
+use std::os::unix::fs::MetadataExt;
 use std::path::Path;
 
 use recomposition::filter::Filter;
@@ -26,7 +27,7 @@ use time::format_description::well_known::Iso8601;
 use time::{OffsetDateTime, UtcOffset};
 
 use super::Section;
-use crate::arguments::model::TimeVisibility;
+use crate::arguments::model::{TimeStyle, TimeVisibility};
 use crate::files::Entry;
 use crate::writev;
 
@@ -43,10 +44,70 @@ pub const SIMPLE_FORMAT: &[BorrowedFormatItem<'static>] = time::macros::format_d
     version = 2,
     "[day padding:space] [month repr:short] '[year repr:last_two] [hour padding:space repr:24]:[minute padding:zero]"
 );
+/// The format used to print dates in the long ISO style.
+pub const LONG_ISO_FORMAT: &[BorrowedFormatItem<'static>] =
+    time::macros::format_description!(version = 2, "[year]-[month]-[day] [hour]:[minute]");
+/// The format used to print dates in the full ISO style.
+pub const FULL_ISO_FORMAT: &[BorrowedFormatItem<'static>] = time::macros::format_description!(
+    version = 2,
+    "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond digits:9] [offset_hour sign:mandatory][offset_minute]"
+);
+/// The format used to print simple dates alongside their day of the week.
+pub const WEEKDAY_FORMAT: &[BorrowedFormatItem<'static>] = time::macros::format_description!(
+    version = 2,
+    "[weekday repr:short] [day padding:space] [month repr:short] '[year repr:last_two] [hour padding:space \
+     repr:24]:[minute padding:zero]"
+);
+
+/// A format used for dates shown in the simple format, alongside the width of every date that it produces.
+#[derive(Clone, Copy, Debug)]
+pub struct Preset {
+    /// The format description.
+    pub format: &'static [BorrowedFormatItem<'static>],
+    /// The width of every formatted date.
+    pub width: usize,
+}
+
+impl Preset {
+    /// Returns the preset for the given time style.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fvr::arguments::model::TimeStyle;
+    /// use fvr::section::time::Preset;
+    /// use time::OffsetDateTime;
+    ///
+    /// for style in [TimeStyle::Default, TimeStyle::LongIso, TimeStyle::FullIso, TimeStyle::Weekday] {
+    ///     let preset = Preset::of(style);
+    ///
+    ///     for timestamp in [OffsetDateTime::UNIX_EPOCH, OffsetDateTime::now_utc()] {
+    ///         assert_eq!(
+    ///             timestamp.format(preset.format).ok().map(|date| date.len()),
+    ///             Some(preset.width)
+    ///         );
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub const fn of(style: TimeStyle) -> Self {
+        match style {
+            TimeStyle::Default => Self { format: SIMPLE_FORMAT, width: SIZE_SIMPLE + 1 },
+            TimeStyle::LongIso => Self { format: LONG_ISO_FORMAT, width: 16 },
+            TimeStyle::FullIso => Self { format: FULL_ISO_FORMAT, width: 35 },
+            TimeStyle::Weekday => Self { format: WEEKDAY_FORMAT, width: 20 },
+        }
+    }
+}
 
-thread_local! {
-    /// Caches the system's offset to save repeated computation.
-    static OFFSET: UtcOffset = UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC);
+/// Returns the system's local offset from UTC, falling back to UTC if it cannot be determined.
+///
+/// Determining the local offset is only sound while the process has a single thread, as another thread may modify the
+/// environment at the same time; `time` reports an error in that case rather than risk it. This should therefore be
+/// called once, before any other threads are spawned, and the result passed to each [`TimeSection`].
+#[must_use]
+pub fn local_offset() -> UtcOffset {
+    UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC)
 }
 
 /// Determines what type of time section is shown.
@@ -58,6 +119,8 @@ pub enum TimeSectionType {
     Accessed,
     /// Modified timestamp.
     Modified,
+    /// Status change timestamp.
+    Changed,
 }
 
 /// A [`Section`] that writes an entry's extracted date.
@@ -65,60 +128,104 @@ pub enum TimeSectionType {
 pub struct TimeSection {
     /// Determines how the date is rendered.
     pub visibility: TimeVisibility,
+    /// Determines the format used when the date is rendered in the simple format.
+    pub style: TimeStyle,
     /// The time section type.
     pub kind: TimeSectionType,
+    /// The offset that timestamps are displayed in.
+    pub offset: UtcOffset,
 }
 
 impl TimeSection {
     /// Creates a new [`TimeSection`].
     #[inline]
     #[must_use]
-    pub const fn new(visibility: TimeVisibility, kind: TimeSectionType) -> Self {
-        Self { visibility, kind }
+    pub const fn new(visibility: TimeVisibility, style: TimeStyle, kind: TimeSectionType, offset: UtcOffset) -> Self {
+        Self { visibility, style, kind, offset }
     }
 
     /// Creates a new [`TimeSection`] for a creation date timestamp.
     #[inline]
     #[must_use]
-    pub const fn created(visibility: TimeVisibility) -> Self {
-        Self::new(visibility, TimeSectionType::Created)
+    pub const fn created(visibility: TimeVisibility, style: TimeStyle, offset: UtcOffset) -> Self {
+        Self::new(visibility, style, TimeSectionType::Created, offset)
     }
 
     /// Creates a new [`TimeSection`] for an access date timestamp.
     #[inline]
     #[must_use]
-    pub const fn accessed(visibility: TimeVisibility) -> Self {
-        Self::new(visibility, TimeSectionType::Accessed)
+    pub const fn accessed(visibility: TimeVisibility, style: TimeStyle, offset: UtcOffset) -> Self {
+        Self::new(visibility, style, TimeSectionType::Accessed, offset)
     }
 
     /// Creates a new [`TimeSection`] for a modification date timestamp.
     #[inline]
     #[must_use]
-    pub const fn modified(visibility: TimeVisibility) -> Self {
-        Self::new(visibility, TimeSectionType::Modified)
+    pub const fn modified(visibility: TimeVisibility, style: TimeStyle, offset: UtcOffset) -> Self {
+        Self::new(visibility, style, TimeSectionType::Modified, offset)
+    }
+
+    /// Creates a new [`TimeSection`] for a status change date timestamp.
+    #[inline]
+    #[must_use]
+    pub const fn changed(visibility: TimeVisibility, style: TimeStyle, offset: UtcOffset) -> Self {
+        Self::new(visibility, style, TimeSectionType::Changed, offset)
+    }
+
+    /// Returns the label shown above this section within a header row.
+    #[inline]
+    #[must_use]
+    pub const fn header(self) -> &'static [u8] {
+        match self.kind {
+            TimeSectionType::Created => b"CREATED",
+            TimeSectionType::Accessed => b"ACCESSED",
+            TimeSectionType::Modified => b"MODIFIED",
+            TimeSectionType::Changed => b"CHANGED",
+        }
+    }
+
+    /// Returns the width of this section.
+    #[inline]
+    #[must_use]
+    pub const fn width(self) -> usize {
+        if self.visibility.is_simple() { Preset::of(self.style).width } else { SIZE_ISO_8601 + 1 }
+    }
+
+    /// Returns the timestamp of the given metadata that should be displayed, if available.
+    fn timestamp(self, data: &Metadata) -> Option<OffsetDateTime> {
+        match self.kind {
+            TimeSectionType::Created => data.created().ok().map(OffsetDateTime::from),
+            TimeSectionType::Accessed => data.accessed().ok().map(OffsetDateTime::from),
+            TimeSectionType::Modified => data.modified().ok().map(OffsetDateTime::from),
+            TimeSectionType::Changed => {
+                let nanoseconds = i128::from(data.ctime()) * 1_000_000_000 + i128::from(data.ctime_nsec());
+
+                OffsetDateTime::from_unix_timestamp_nanos(nanoseconds).ok()
+            }
+        }
     }
 }
 
 #[expect(clippy::expect_used, reason = "formatting only fails if the defined formats are somehow invalid")]
 impl Section for TimeSection {
-    fn write_plain<F>(&self, f: &mut StdoutLock<'_>, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    fn measure<F>(&self, _: &[&Entry<F>]) -> usize
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        self.width()
+    }
+
+    fn write_plain<F>(&self, f: &mut impl Write, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
     where
         F: Filter<(Box<Path>, Metadata)>,
     {
-        let Some(timestamp) = entry.data.as_ref().and_then(|v| match self.kind {
-            TimeSectionType::Created => v.created().ok(),
-            TimeSectionType::Accessed => v.accessed().ok(),
-            TimeSectionType::Modified => v.modified().ok(),
-        }) else {
-            return writev!(f, [
-                &[CHAR_MISSING],
-                if self.visibility.is_simple() { &[CHAR_PADDING; SIZE_SIMPLE] } else { &[CHAR_PADDING; SIZE_ISO_8601] }
-            ]);
+        let Some(timestamp) = entry.data.as_ref().and_then(|data| self.timestamp(data)) else {
+            return writev!(f, [&[CHAR_MISSING], &vec![CHAR_PADDING; self.width() - 1]]);
         };
 
-        let timestamp = OFFSET.with(|v| OffsetDateTime::from(timestamp).to_offset(*v));
+        let timestamp = timestamp.to_offset(self.offset);
         let formatted = match self.visibility {
-            TimeVisibility::Simple => timestamp.format(SIMPLE_FORMAT),
+            TimeVisibility::Simple => timestamp.format(Preset::of(self.style).format),
             TimeVisibility::Iso8601 => timestamp.format(&Iso8601::DEFAULT),
             TimeVisibility::Hide => unreachable!(),
         }
@@ -127,24 +234,17 @@ impl Section for TimeSection {
         writev!(f, [formatted.as_bytes()])
     }
 
-    fn write_color<F>(&self, f: &mut StdoutLock<'_>, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    fn write_color<F>(&self, f: &mut impl Write, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
     where
         F: Filter<(Box<Path>, Metadata)>,
     {
-        let Some(timestamp) = entry.data.as_ref().and_then(|v| match self.kind {
-            TimeSectionType::Created => v.created().ok(),
-            TimeSectionType::Accessed => v.accessed().ok(),
-            TimeSectionType::Modified => v.modified().ok(),
-        }) else {
-            return writev!(f, [
-                &[CHAR_MISSING],
-                if self.visibility.is_simple() { &[CHAR_PADDING; SIZE_SIMPLE] } else { &[CHAR_PADDING; SIZE_ISO_8601] }
-            ] in BrightBlack);
+        let Some(timestamp) = entry.data.as_ref().and_then(|data| self.timestamp(data)) else {
+            return writev!(f, [&[CHAR_MISSING], &vec![CHAR_PADDING; self.width() - 1]] in BrightBlack);
         };
 
-        let timestamp = OFFSET.with(|v| OffsetDateTime::from(timestamp).to_offset(*v));
+        let timestamp = timestamp.to_offset(self.offset);
         let formatted = match self.visibility {
-            TimeVisibility::Simple => timestamp.format(SIMPLE_FORMAT),
+            TimeVisibility::Simple => timestamp.format(Preset::of(self.style).format),
             TimeVisibility::Iso8601 => timestamp.format(&Iso8601::DEFAULT),
             TimeVisibility::Hide => unreachable!(),
         }
@@ -154,6 +254,7 @@ impl Section for TimeSection {
             TimeSectionType::Created => writev!(f, [formatted.as_bytes()] in BrightGreen),
             TimeSectionType::Accessed => writev!(f, [formatted.as_bytes()] in BrightCyan),
             TimeSectionType::Modified => writev!(f, [formatted.as_bytes()] in BrightBlue),
+            TimeSectionType::Changed => writev!(f, [formatted.as_bytes()] in BrightMagenta),
         }
     }
 }
@@ -17,20 +17,29 @@
 //! Implements a section that provides branches for tree-based views.
 
 use std::fs::Metadata;
-use std::io::{Result, StdoutLock};
+use std::io::{Result, Write};
 use std::path::Path;
 
 use recomposition::filter::Filter;
 
 use super::Section;
+use crate::arguments::model::{TreeColor, TreeStyle};
 use crate::files::Entry;
-use crate::writev;
+use crate::{color_bytes, writev};
 
 /// A [`Section`] that writes branches for tree-based views.
 #[derive(Clone, Copy, Debug)]
 pub struct TreeSection {
     /// The number of directories deep that should be displayed.
     pub max_depth: usize,
+    /// Whether to indent each level by a single column.
+    pub narrow: bool,
+    /// Determines how branches are colored.
+    pub color: TreeColor,
+    /// Whether the root entry is omitted, making its children the top of the tree.
+    pub no_root: bool,
+    /// Determines which characters branches are drawn with.
+    pub style: TreeStyle,
 }
 
 impl TreeSection {
@@ -44,6 +53,14 @@ impl TreeSection {
     pub const LINE_VERTICAL: &[u8] = "│".as_bytes();
     /// The bytes used for padding.
     pub const PADDING: &[u8] = b" ";
+    /// The colors that branches cycle through by depth.
+    pub const PALETTE: &[&[u8]] = &[
+        color_bytes!(BrightBlue),
+        color_bytes!(BrightMagenta),
+        color_bytes!(BrightCyan),
+        color_bytes!(BrightGreen),
+        color_bytes!(BrightYellow),
+    ];
     /// The bytes used for a horizontal split line.
     pub const SPLIT_HORIZONTAL: &[u8] = "┬".as_bytes();
     /// The bytes used for a vertical split line.
@@ -51,73 +68,150 @@ impl TreeSection {
 
     /// Creates a new [`TreeSection`].
     #[must_use]
-    pub const fn new(max_depth: usize) -> Self {
-        Self { max_depth }
+    pub const fn new(max_depth: usize, narrow: bool, color: TreeColor, no_root: bool, style: TreeStyle) -> Self {
+        Self { max_depth, narrow, color, no_root, style }
     }
-}
 
-impl Section for TreeSection {
-    fn write_plain<F>(&self, f: &mut StdoutLock<'_>, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    /// Returns the characters that branches are drawn with.
+    #[must_use]
+    pub const fn glyphs(&self) -> Glyphs {
+        match self.style {
+            TreeStyle::Unicode => Glyphs::UNICODE,
+            TreeStyle::Ascii => Glyphs::ASCII,
+            TreeStyle::Indent => Glyphs::INDENT,
+        }
+    }
+
+    /// Returns whether the given entry's children are drawn beneath it.
+    pub fn is_branch<F>(&self, parents: &[&Entry<F>], entry: &Entry<F>) -> bool
     where
         F: Filter<(Box<Path>, Metadata)>,
     {
-        let depth = parents.len();
+        parents.len() < self.max_depth && entry.has_children() && !entry.is_cycle(parents)
+    }
+
+    /// Returns the branches that precede the given entry.
+    ///
+    /// If a palette is given, each column is preceded by the color at its depth.
+    fn branches<F>(&self, parents: &[&Entry<F>], entry: &Entry<F>, palette: &[&[u8]]) -> Vec<u8>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        let glyphs = self.glyphs();
+        let mut buffer = Vec::with_capacity((parents.len() + 2) * 2 * glyphs.line_vertical.len());
+        let set_color = |buffer: &mut Vec<u8>, depth: usize| {
+            if !palette.is_empty() {
+                buffer.extend_from_slice(palette[depth % palette.len()]);
+            }
+        };
+
+        if entry.is_first() && parents.is_empty() {
+            set_color(&mut buffer, 0);
+
+            buffer.extend_from_slice(glyphs.corner_top);
+            buffer.extend_from_slice(glyphs.line_horizontal);
 
-        if entry.is_first() && depth == 0 {
-            return writev!(f, [Self::CORNER_TOP, Self::LINE_HORIZONTAL]);
+            return buffer;
         }
 
-        let join = if entry.is_last() { Self::CORNER_BOTTOM } else { Self::SPLIT_VERTICAL };
-        let connect = if parents.len() < self.max_depth && entry.has_children() {
-            Self::SPLIT_HORIZONTAL
-        } else {
-            Self::LINE_HORIZONTAL
+        let join = match (entry.is_first(), entry.is_last()) {
+            // Without a root, the first child takes its place at the top of the tree.
+            (true, true) if self.no_root && parents.len() == 1 => glyphs.line_horizontal,
+            (true, false) if self.no_root && parents.len() == 1 => glyphs.corner_top,
+            (_, true) => glyphs.corner_bottom,
+            (_, false) => glyphs.split_vertical,
         };
+        let connect = if self.is_branch(parents, entry) { glyphs.split_horizontal } else { glyphs.line_horizontal };
+
+        for (depth, parent) in parents.iter().skip(1).enumerate() {
+            set_color(&mut buffer, depth);
 
-        let mut buffer = Vec::with_capacity(parents.len() * 2);
+            buffer.extend_from_slice(if parent.is_last() { Self::PADDING } else { glyphs.line_vertical });
 
-        for parent in parents.iter().skip(1) {
-            if parent.is_last() {
+            if !self.narrow {
                 buffer.extend_from_slice(Self::PADDING);
-            } else {
-                buffer.extend_from_slice(Self::LINE_VERTICAL);
             }
-
-            buffer.extend_from_slice(Self::PADDING);
         }
 
-        writev!(f, [&buffer, join, Self::LINE_HORIZONTAL, connect, Self::LINE_HORIZONTAL])
-    }
+        set_color(&mut buffer, parents.len().saturating_sub(1));
 
-    fn write_color<F>(&self, f: &mut StdoutLock<'_>, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
-    where
-        F: Filter<(Box<Path>, Metadata)>,
-    {
-        let depth = parents.len();
+        buffer.extend_from_slice(join);
 
-        if entry.is_first() && depth == 0 {
-            return writev!(f, [Self::CORNER_TOP, Self::LINE_HORIZONTAL] in BrightBlack);
+        // Narrow trees drop the horizontal lines, leaving a single column for every level.
+        if self.narrow {
+            buffer.extend_from_slice(connect);
+        } else {
+            buffer.extend_from_slice(glyphs.line_horizontal);
+            buffer.extend_from_slice(connect);
+            buffer.extend_from_slice(glyphs.line_horizontal);
         }
 
-        let join = if entry.is_last() { Self::CORNER_BOTTOM } else { Self::SPLIT_VERTICAL };
-        let connect = if parents.len() < self.max_depth && entry.has_children() {
-            Self::SPLIT_HORIZONTAL
-        } else {
-            Self::LINE_HORIZONTAL
-        };
+        buffer
+    }
+}
 
-        let mut buffer = Vec::with_capacity(parents.len() * 2);
+/// The characters that a tree's branches are drawn with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Glyphs {
+    /// The bytes used for a bottom corner.
+    pub corner_bottom: &'static [u8],
+    /// The bytes used for a top corner.
+    pub corner_top: &'static [u8],
+    /// The bytes used for a horizontal line.
+    pub line_horizontal: &'static [u8],
+    /// The bytes used for a vertical line.
+    pub line_vertical: &'static [u8],
+    /// The bytes used for a horizontal split line.
+    pub split_horizontal: &'static [u8],
+    /// The bytes used for a vertical split line.
+    pub split_vertical: &'static [u8],
+}
 
-        for parent in parents.iter().skip(1) {
-            if parent.is_last() {
-                buffer.extend_from_slice(Self::PADDING);
-            } else {
-                buffer.extend_from_slice(Self::LINE_VERTICAL);
-            }
+impl Glyphs {
+    /// Glyphs drawn using only ASCII characters, for terminals that cannot show box-drawing characters.
+    pub const ASCII: Self = Self {
+        corner_bottom: b"`",
+        corner_top: b"+",
+        line_horizontal: b"-",
+        line_vertical: b"|",
+        split_horizontal: b"+",
+        split_vertical: b"|",
+    };
+    /// Glyphs drawn using only spaces, leaving each entry's depth as its only indicator.
+    pub const INDENT: Self = Self {
+        corner_bottom: TreeSection::PADDING,
+        corner_top: TreeSection::PADDING,
+        line_horizontal: TreeSection::PADDING,
+        line_vertical: TreeSection::PADDING,
+        split_horizontal: TreeSection::PADDING,
+        split_vertical: TreeSection::PADDING,
+    };
+    /// Glyphs drawn using Unicode box-drawing characters.
+    pub const UNICODE: Self = Self {
+        corner_bottom: TreeSection::CORNER_BOTTOM,
+        corner_top: TreeSection::CORNER_TOP,
+        line_horizontal: TreeSection::LINE_HORIZONTAL,
+        line_vertical: TreeSection::LINE_VERTICAL,
+        split_horizontal: TreeSection::SPLIT_HORIZONTAL,
+        split_vertical: TreeSection::SPLIT_VERTICAL,
+    };
+}
 
-            buffer.extend_from_slice(Self::PADDING);
-        }
+impl Section for TreeSection {
+    fn write_plain<F>(&self, f: &mut impl Write, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        writev!(f, [&self.branches(parents, entry, &[])])
+    }
 
-        writev!(f, [&buffer, join, Self::LINE_HORIZONTAL, connect, Self::LINE_HORIZONTAL] in BrightBlack)
+    fn write_color<F>(&self, f: &mut impl Write, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
+        match self.color {
+            TreeColor::Default => writev!(f, [&self.branches(parents, entry, &[])] in BrightBlack),
+            TreeColor::Cycle => writev!(f, [&self.branches(parents, entry, Self::PALETTE), color_bytes!(Default)]),
+        }
     }
 }
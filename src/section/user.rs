@@ -20,7 +20,7 @@ use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::ffi::OsStr;
 use std::fs::Metadata;
-use std::io::{Result, StdoutLock};
+use std::io::{Result, Write};
 use std::os::unix::fs::MetadataExt;
 use std::path::Path;
 use std::rc::Rc;
@@ -40,9 +40,15 @@ pub const MAX_LEN: usize = 32;
 
 /// A [`Section`] that writes an entry's owner username.
 #[derive(Clone, Copy, Debug)]
-pub struct UserSection;
+pub struct UserSection {
+    /// The width that each name is padded to, which is the assumed maximum length until it is measured.
+    pub width: usize,
+}
 
 impl UserSection {
+    /// The label shown above this section within a header row.
+    pub const HEADER: &[u8] = b"USER";
+
     /// Returns the username associated with the given user identifier.
     fn name(uid: u32) -> Option<Rc<OsStr>> {
         thread_local! {
@@ -53,63 +59,49 @@ impl UserSection {
             v.borrow_mut().entry(uid).or_insert_with(|| uzers::get_user_by_uid(uid).map(|v| v.name().into())).clone()
         })
     }
+}
 
-    /// Returns the maximum length that all user sections in the given directory will take up.
-    fn max_len(parent: &Path) -> usize {
-        thread_local! {
-            static CACHE: RefCell<BTreeMap<Box<OsStr>, usize>> = RefCell::new(BTreeMap::default());
-        }
-
-        CACHE.with(|cache| {
-            if let Some(len) = cache.borrow().get(parent.as_os_str()).copied() {
-                return len;
-            }
-
-            let len = std::fs::read_dir(parent)
-                .ok()
-                .and_then(|v| {
-                    v.map_while(|v| v.and_then(|v| v.metadata()).ok())
-                        .map_while(|v| Self::name(v.uid()).map(|v| v.len()))
-                        .max()
-                })
-                .unwrap_or(MAX_LEN);
-
-            cache.borrow_mut().insert(Box::from(parent.as_os_str()), len);
-
-            len
-        })
+impl Default for UserSection {
+    fn default() -> Self {
+        Self { width: MAX_LEN }
     }
 }
 
 impl Section for UserSection {
-    fn write_plain<F>(&self, f: &mut StdoutLock<'_>, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    fn measure<F>(&self, entries: &[&Entry<F>]) -> usize
     where
         F: Filter<(Box<Path>, Metadata)>,
     {
-        let parent_path = parents.last().map_or_else(|| entry.path.parent(), |parent| Some(&parent.path));
-        let length = parent_path.map_or(MAX_LEN, Self::max_len);
+        // Entries without a user are shown as a single dash, so that is the minimum width.
+        entries
+            .iter()
+            .map(|entry| entry.data.as_ref().and_then(|v| Self::name(v.uid())).map_or(1, |v| v.len()))
+            .max()
+            .unwrap_or(0)
+    }
 
+    fn write_plain<F>(&self, f: &mut impl Write, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
         let Some(user) = entry.data.as_ref().and_then(|v| Self::name(v.uid())) else {
-            return writev!(f, [&[CHAR_MISSING], &vec![b' '; length - 1]]);
+            return writev!(f, [&[CHAR_MISSING], &vec![CHAR_PADDING; self.width.saturating_sub(1)]]);
         };
 
-        let padding = vec![CHAR_PADDING; length.saturating_sub(user.len())];
+        let padding = vec![CHAR_PADDING; self.width.saturating_sub(user.len())];
 
         writev!(f, [user.as_encoded_bytes(), &padding])
     }
 
-    fn write_color<F>(&self, f: &mut StdoutLock<'_>, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    fn write_color<F>(&self, f: &mut impl Write, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
     where
         F: Filter<(Box<Path>, Metadata)>,
     {
-        let parent_path = parents.last().map_or_else(|| entry.path.parent(), |parent| Some(&parent.path));
-        let length = parent_path.map_or(MAX_LEN, Self::max_len);
-
         let Some(user) = entry.data.as_ref().and_then(|v| Self::name(v.uid())) else {
-            return writev!(f, [&[CHAR_MISSING], &vec![b' '; length - 1]]);
+            return writev!(f, [&[CHAR_MISSING], &vec![CHAR_PADDING; self.width.saturating_sub(1)]]);
         };
 
-        let padding = vec![CHAR_PADDING; length.saturating_sub(user.len())];
+        let padding = vec![CHAR_PADDING; self.width.saturating_sub(user.len())];
 
         writev!(f, [user.as_encoded_bytes(), &padding] in BrightGreen)
     }
@@ -117,9 +109,15 @@ impl Section for UserSection {
 
 /// A [`Section`] that writes an entry's owner username.
 #[derive(Clone, Copy, Debug)]
-pub struct GroupSection;
+pub struct GroupSection {
+    /// The width that each name is padded to, which is the assumed maximum length until it is measured.
+    pub width: usize,
+}
 
 impl GroupSection {
+    /// The label shown above this section within a header row.
+    pub const HEADER: &[u8] = b"GROUP";
+
     /// Returns the group name associated with the given group identifier.
     fn name(gid: u32) -> Option<Rc<OsStr>> {
         thread_local! {
@@ -130,63 +128,49 @@ impl GroupSection {
             v.borrow_mut().entry(gid).or_insert_with(|| uzers::get_group_by_gid(gid).map(|v| v.name().into())).clone()
         })
     }
+}
 
-    /// Returns the maximum length that all group sections in the given directory will take up.
-    fn max_len(parent: &Path) -> usize {
-        thread_local! {
-            static CACHE: RefCell<BTreeMap<Box<OsStr>, usize>> = RefCell::new(BTreeMap::default());
-        }
-
-        CACHE.with(|cache| {
-            if let Some(len) = cache.borrow().get(parent.as_os_str()).copied() {
-                return len;
-            }
-
-            let len = std::fs::read_dir(parent)
-                .ok()
-                .and_then(|v| {
-                    v.map_while(|v| v.and_then(|v| v.metadata()).ok())
-                        .map_while(|v| Self::name(v.gid()).map(|v| v.len()))
-                        .max()
-                })
-                .unwrap_or(MAX_LEN);
-
-            cache.borrow_mut().insert(Box::from(parent.as_os_str()), len);
-
-            len
-        })
+impl Default for GroupSection {
+    fn default() -> Self {
+        Self { width: MAX_LEN }
     }
 }
 
 impl Section for GroupSection {
-    fn write_plain<F>(&self, f: &mut StdoutLock<'_>, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    fn measure<F>(&self, entries: &[&Entry<F>]) -> usize
     where
         F: Filter<(Box<Path>, Metadata)>,
     {
-        let parent_path = parents.last().map_or_else(|| entry.path.parent(), |parent| Some(&parent.path));
-        let length = parent_path.map_or(MAX_LEN, Self::max_len);
+        // Entries without a group are shown as a single dash, so that is the minimum width.
+        entries
+            .iter()
+            .map(|entry| entry.data.as_ref().and_then(|v| Self::name(v.gid())).map_or(1, |v| v.len()))
+            .max()
+            .unwrap_or(0)
+    }
 
+    fn write_plain<F>(&self, f: &mut impl Write, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    where
+        F: Filter<(Box<Path>, Metadata)>,
+    {
         let Some(group) = entry.data.as_ref().and_then(|v| Self::name(v.gid())) else {
-            return writev!(f, [&[CHAR_MISSING], &vec![b' '; length - 1]]);
+            return writev!(f, [&[CHAR_MISSING], &vec![CHAR_PADDING; self.width.saturating_sub(1)]]);
         };
 
-        let padding = vec![CHAR_PADDING; length.saturating_sub(group.len())];
+        let padding = vec![CHAR_PADDING; self.width.saturating_sub(group.len())];
 
         writev!(f, [group.as_encoded_bytes(), &padding])
     }
 
-    fn write_color<F>(&self, f: &mut StdoutLock<'_>, parents: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
+    fn write_color<F>(&self, f: &mut impl Write, _: &[&Entry<F>], entry: &Entry<F>) -> Result<()>
     where
         F: Filter<(Box<Path>, Metadata)>,
     {
-        let parent_path = parents.last().map_or_else(|| entry.path.parent(), |parent| Some(&parent.path));
-        let length = parent_path.map_or(MAX_LEN, Self::max_len);
-
         let Some(group) = entry.data.as_ref().and_then(|v| Self::name(v.gid())) else {
-            return writev!(f, [&[CHAR_MISSING], &vec![b' '; length - 1]]);
+            return writev!(f, [&[CHAR_MISSING], &vec![CHAR_PADDING; self.width.saturating_sub(1)]]);
         };
 
-        let padding = vec![CHAR_PADDING; length.saturating_sub(group.len())];
+        let padding = vec![CHAR_PADDING; self.width.saturating_sub(group.len())];
 
         writev!(f, [group.as_encoded_bytes(), &padding] in BrightYellow)
     }
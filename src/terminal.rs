@@ -0,0 +1,313 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright © 2025 Jaxydog
+//
+// This file is part of fvr.
+//
+// fvr is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public
+// License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later
+// version.
+//
+// fvr is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License along with fvr. If not,
+// see <https://www.gnu.org/licenses/>.
+
+//! Provides information about the terminal that the application is writing into.
+
+use std::io::{ErrorKind, IsTerminal, Result, Stderr, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// The width assumed by layouts that require one when the output is not a terminal, such as when it is piped.
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// Returns the width that output should be wrapped to, or [`None`] if it is not being written into a terminal.
+///
+/// An explicitly given width takes precedence, followed by a positive width given through the `COLUMNS` environment
+/// variable, and finally the size reported by the terminal connected to the standard output stream. Callers decide how
+/// to handle a missing width, either by not wrapping at all or by assuming [`DEFAULT_WIDTH`].
+///
+/// # Examples
+///
+/// ```
+/// use fvr::terminal::{DEFAULT_WIDTH, terminal_width};
+///
+/// let width = terminal_width(None).unwrap_or(DEFAULT_WIDTH);
+///
+/// assert!(width > 0);
+/// assert_eq!(terminal_width(Some(120)), Some(120));
+/// ```
+///
+/// ```
+/// use fvr::terminal::terminal_width;
+///
+/// // SAFETY: This example does not spawn any other threads that could read the environment.
+/// unsafe { std::env::set_var("COLUMNS", "40") };
+///
+/// assert_eq!(terminal_width(None), Some(40));
+/// assert_eq!(terminal_width(Some(120)), Some(120));
+/// ```
+#[must_use]
+pub fn terminal_width(explicit: Option<usize>) -> Option<usize> {
+    explicit
+        .or_else(self::columns_width)
+        .or_else(|| self::window_size().map(|size| usize::from(size.ws_col)).filter(|width| *width > 0))
+}
+
+/// Returns the width given through the `COLUMNS` environment variable, or [`None`] if it is unset or not positive.
+fn columns_width() -> Option<usize> {
+    std::env::var("COLUMNS").ok().and_then(|columns| columns.trim_ascii().parse().ok()).filter(|width| *width > 0)
+}
+
+/// Returns the number of columns that the given text takes up when written into a terminal.
+///
+/// Wide characters, such as those used by Chinese, Japanese, and Korean scripts, take up two columns, while control
+/// characters and combining marks take up none.
+///
+/// # Examples
+///
+/// ```
+/// use fvr::terminal::display_width;
+///
+/// assert_eq!(display_width("fvr"), 3);
+/// assert_eq!(display_width("日本語"), 6);
+/// assert_eq!(display_width("e\u{301}"), 1);
+/// ```
+#[must_use]
+pub fn display_width(text: &str) -> usize {
+    text.chars().map(self::char_width).sum()
+}
+
+/// Returns the number of columns that the given character takes up when written into a terminal.
+#[must_use]
+pub fn char_width(character: char) -> usize {
+    match u32::from(character) {
+        // Control characters and combining marks, including zero-width spaces and variation selectors.
+        0x00 ..= 0x1F
+        | 0x7F ..= 0x9F
+        | 0x0300 ..= 0x036F
+        | 0x200B ..= 0x200F
+        | 0x20D0 ..= 0x20FF
+        | 0xFE00 ..= 0xFE0F => 0,
+        // East Asian wide and fullwidth characters, as well as most emoji.
+        0x1100 ..= 0x115F
+        | 0x2E80 ..= 0x303E
+        | 0x3041 ..= 0x33FF
+        | 0x3400 ..= 0x4DBF
+        | 0x4E00 ..= 0x9FFF
+        | 0xA000 ..= 0xA4CF
+        | 0xAC00 ..= 0xD7A3
+        | 0xF900 ..= 0xFAFF
+        | 0xFE30 ..= 0xFE4F
+        | 0xFF00 ..= 0xFF60
+        | 0xFFE0 ..= 0xFFE6
+        | 0x1F300 ..= 0x1F64F
+        | 0x1F900 ..= 0x1F9FF
+        | 0x20000 ..= 0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// Returns the height of the terminal connected to the standard output stream, or [`None`] if it is not a terminal.
+#[must_use]
+pub fn terminal_height() -> Option<usize> {
+    self::window_size().map(|size| usize::from(size.ws_row)).filter(|height| *height > 0)
+}
+
+/// Returns the size of the terminal connected to the standard output stream, or [`None`] if it is not a terminal.
+fn window_size() -> Option<libc::winsize> {
+    let mut size = libc::winsize { ws_row: 0, ws_col: 0, ws_xpixel: 0, ws_ypixel: 0 };
+
+    // SAFETY: `TIOCGWINSZ` only writes into the given `winsize`, which outlives the call.
+    #[expect(unsafe_code, reason = "the terminal's size can only be queried through a system call")]
+    let result = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &raw mut size) };
+
+    (result == 0).then_some(size)
+}
+
+/// Writes the given output through the pager named by `$PAGER`, or `less -R` if it is unset.
+///
+/// If the pager cannot be started, the output is written into the given writer instead.
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+pub fn write_paged(f: &mut impl Write, output: &[u8]) -> Result<()> {
+    let pager = std::env::var("PAGER").ok().filter(|pager| !pager.trim_ascii().is_empty());
+    let pager = pager.as_deref().unwrap_or("less -R");
+    let mut words = pager.split_ascii_whitespace();
+    let mut command = Command::new(words.next().unwrap_or("less"));
+
+    command.args(words).stdin(Stdio::piped());
+
+    // `less` only shows colors as-is if asked to, which is done through its environment to leave other options alone.
+    if std::env::var_os("LESS").is_none() {
+        command.env("LESS", "R");
+    }
+
+    let Ok(mut child) = command.spawn() else {
+        return f.write_all(output);
+    };
+
+    let result = child.stdin.take().map_or(Ok(()), |mut stdin| stdin.write_all(output));
+
+    child.wait()?;
+
+    // The pager closes its input when it is exited early, which is not an error.
+    match result {
+        Err(error) if error.kind() == ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    }
+}
+
+/// Writes an escape sequence that sets the terminal's title to the given paths, separated by spaces.
+///
+/// Control characters are removed from each path, as they could otherwise end the sequence early. This should only be
+/// written into a terminal, since anything else would receive the raw escape sequence.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use fvr::terminal::write_title;
+///
+/// let mut buffer = Vec::new();
+///
+/// write_title(&mut buffer, [Path::new("/usr"), Path::new("/tmp/\x07")])?;
+///
+/// assert_eq!(buffer, b"\x1b]2;/usr /tmp/\x07");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if writing fails.
+pub fn write_title<I, P>(f: &mut impl Write, paths: I) -> Result<()>
+where
+    I: IntoIterator<Item = P>,
+    P: AsRef<Path>,
+{
+    let mut title = b"\x1b]2;".to_vec();
+
+    for (index, path) in paths.into_iter().enumerate() {
+        if index > 0 {
+            title.push(b' ');
+        }
+
+        title.extend(path.as_ref().as_os_str().as_encoded_bytes().iter().filter(|byte| !byte.is_ascii_control()));
+    }
+
+    title.push(b'\x07');
+
+    f.write_all(&title)
+}
+
+/// Reports how many entries have been scanned during a traversal.
+///
+/// To avoid slowing the traversal down, the count is only written once every [`Progress::INTERVAL`] entries. When
+/// redrawing, each report replaces the last and the line is cleared once the progress is dropped; otherwise, each
+/// report is written on its own line.
+///
+/// # Examples
+///
+/// ```
+/// use fvr::terminal::Progress;
+///
+/// let mut buffer = Vec::new();
+/// let mut progress = Progress::new(&mut buffer, false);
+///
+/// for _ in 0 .. 2_500 {
+///     progress.tick();
+/// }
+///
+/// drop(progress);
+///
+/// assert_eq!(buffer, b"scanned 1,000 entries...\nscanned 2,000 entries...\n");
+/// ```
+#[derive(Debug)]
+pub struct Progress<W: Write> {
+    /// The writer that progress is reported into.
+    f: W,
+    /// Whether each report replaces the last, rather than being written on its own line.
+    redraw: bool,
+    /// The number of entries scanned so far.
+    scanned: usize,
+}
+
+impl Progress<Stderr> {
+    /// Creates a new [`Progress`] that reports into standard error, redrawing in place if it is a terminal.
+    #[must_use]
+    pub fn stderr() -> Self {
+        let f = std::io::stderr();
+        let redraw = f.is_terminal();
+
+        Self::new(f, redraw)
+    }
+}
+
+impl<W: Write> Progress<W> {
+    /// The number of entries scanned between each report.
+    pub const INTERVAL: usize = 1_000;
+
+    /// Creates a new [`Progress`].
+    #[inline]
+    #[must_use]
+    pub const fn new(f: W, redraw: bool) -> Self {
+        Self { f, redraw, scanned: 0 }
+    }
+
+    /// Returns the number of entries scanned so far.
+    #[inline]
+    #[must_use]
+    pub const fn scanned(&self) -> usize {
+        self.scanned
+    }
+
+    /// Records a single scanned entry, reporting the total if another interval has passed.
+    ///
+    /// Failing to report progress never interrupts a traversal, so any errors are ignored.
+    pub fn tick(&mut self) {
+        self.scanned += 1;
+
+        if !self.scanned.is_multiple_of(Self::INTERVAL) {
+            return;
+        }
+
+        let count = self::group_digits(self.scanned);
+        let _ = if self.redraw {
+            write!(self.f, "\r\x1b[Kscanned {count} entries...")
+        } else {
+            writeln!(self.f, "scanned {count} entries...")
+        };
+        let _ = self.f.flush();
+    }
+}
+
+impl<W: Write> Drop for Progress<W> {
+    fn drop(&mut self) {
+        if self.redraw && self.scanned >= Self::INTERVAL {
+            let _ = self.f.write_all(b"\r\x1b[K");
+            let _ = self.f.flush();
+        }
+    }
+}
+
+/// Returns the given number with its digits separated into groups of three, such as `12,340`.
+fn group_digits(value: usize) -> String {
+    let mut buffer = itoa::Buffer::new();
+    let digits = buffer.format(value).as_bytes();
+    let mut grouped = String::with_capacity(digits.len() + (digits.len() / 3));
+
+    for (index, digit) in digits.iter().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push(',');
+        }
+
+        grouped.push(char::from(*digit));
+    }
+
+    grouped
+}
@@ -0,0 +1,302 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright © 2025 Jaxydog
+//
+// This file is part of fvr.
+//
+// fvr is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public
+// License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later
+// version.
+//
+// fvr is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License along with fvr. If not,
+// see <https://www.gnu.org/licenses/>.
+
+//! Tests how command-line arguments are parsed.
+
+use std::error::Error;
+use std::path::Path;
+
+use fvr::arguments::model::{Arguments, ColorChoice, ListArguments, ModeVisibility, SizeVisibility, SubCommand};
+use fvr::arguments::schema::{
+    ArgumentSchema, ArgumentSchemaBuilder, CommandSchema, CommandSchemaBuilder, ValueSchema, ValueSchemaBuilder,
+};
+use fvr::arguments::{ParseResult, parse_arguments_from};
+
+use self::common::{Fixture, TestResult};
+
+mod common;
+
+/// Parses the given arguments, failing if the program would have exited instead.
+fn parse(arguments: &[&str]) -> Result<Arguments, Box<dyn Error>> {
+    match parse_arguments_from(arguments.iter().copied()) {
+        ParseResult::Ok(arguments) => Ok(arguments),
+        ParseResult::Exit(code) => Err(format!("parsing exited with code {code}").into()),
+    }
+}
+
+/// Parses the given arguments for the list sub-command, failing if another sub-command was selected.
+fn parse_list(arguments: &[&str]) -> Result<ListArguments, Box<dyn Error>> {
+    match self::parse(&[&["list"], arguments].concat())?.command {
+        Some(SubCommand::List(list_arguments)) => Ok(list_arguments),
+        _ => Err("the list sub-command was not selected".into()),
+    }
+}
+
+#[test]
+fn parses_arguments_before_the_sub_command() -> TestResult {
+    let arguments = self::parse(&["--color", "never", "list", "."])?;
+
+    assert_eq!(arguments.color, ColorChoice::Never);
+    assert!(arguments.command.as_ref().and_then(SubCommand::as_list).is_some());
+
+    Ok(())
+}
+
+#[test]
+fn parses_values_from_the_next_argument() -> TestResult {
+    let list_arguments = self::parse_list(&["--mode", "show", "--size", "base-10", "."])?;
+
+    assert_eq!(list_arguments.mode, ModeVisibility::Show);
+    assert_eq!(list_arguments.size, SizeVisibility::Base10);
+
+    Ok(())
+}
+
+#[test]
+fn parses_values_assigned_to_long_arguments() -> TestResult {
+    let list_arguments = self::parse_list(&["--mode=extended", "--size=simple", "."])?;
+
+    assert_eq!(list_arguments.mode, ModeVisibility::Extended);
+    assert_eq!(list_arguments.size, SizeVisibility::Simple);
+
+    Ok(())
+}
+
+#[test]
+fn aliases_size_visibilities() -> TestResult {
+    let aliases = [
+        (&["-H", "."][..], SizeVisibility::Base2),
+        (&["--human", "."], SizeVisibility::Base2),
+        (&["--si", "."], SizeVisibility::Base10),
+        (&["--si", "--size", "simple", "."], SizeVisibility::Simple),
+        (&["--size", "base-10", "-H", "."], SizeVisibility::Base2),
+    ];
+
+    for (arguments, visibility) in aliases {
+        assert_eq!(self::parse_list(arguments)?.size, visibility, "{arguments:?}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn parses_clusters_of_short_flags() -> TestResult {
+    let list_arguments = self::parse_list(&["-ar", "."])?;
+
+    assert!(list_arguments.show_hidden && list_arguments.resolve_symlinks);
+
+    Ok(())
+}
+
+#[test]
+fn rejects_missing_values() {
+    assert!(self::parse_list(&["--mode"]).is_err());
+    assert!(self::parse_list(&[".", "--sort"]).is_err());
+}
+
+#[test]
+fn parses_values_from_the_rest_of_a_cluster() -> TestResult {
+    let list_arguments = self::parse_list(&["-mshow", "-sbase-2", "-d3", "."])?;
+
+    assert_eq!(list_arguments.mode, ModeVisibility::Show);
+    assert_eq!(list_arguments.size, SizeVisibility::Base2);
+    assert_eq!(list_arguments.max_depth, Some(3));
+
+    Ok(())
+}
+
+#[test]
+fn accepts_both_spellings_of_every_value() -> TestResult {
+    let fixture = Fixture::new("arguments-spellings")?;
+
+    fixture.file("inner/file", "abc")?;
+    fixture.file("outer/file", "abcdef")?;
+    fixture.file("paths", fixture.path("inner"))?;
+
+    let [inner, paths, output] = [fixture.path("inner"), fixture.path("paths"), fixture.path("output")];
+    let values = [
+        ("color", "never"),
+        ("width", "40"),
+        ("hide-pattern", "inner"),
+        ("name", "file"),
+        ("paths-from", &paths),
+        ("exclude", &inner),
+        ("exclude-from", &paths),
+        ("include", &inner),
+        ("quoting", "escape"),
+        ("max-name-width", "3"),
+        ("sort", "size,name"),
+        ("seed", "7"),
+        ("collation", "bytes"),
+        ("mode", "show"),
+        ("size", "base-2"),
+        ("dir-size", "child-count"),
+        ("block-size", "512"),
+        ("created", "hide"),
+        ("accessed", "hide"),
+        ("modified", "iso8601"),
+        ("changed", "simple"),
+        ("time-style", "long-iso"),
+        ("since", "2000-01-01"),
+        ("until", "2000-01-01"),
+        ("columns", "size,name"),
+        ("depth", "1"),
+        ("max-entries", "1"),
+        ("pager", "never"),
+        ("format", "csv"),
+        ("output", &output),
+    ];
+
+    for (name, value) in values {
+        let assigned = common::list(&[&format!("--{name}={value}"), &fixture.root()])?;
+        let separate = common::list(&[&format!("--{name}"), value, &fixture.root()])?;
+
+        assert_eq!(assigned, separate, "`--{name}` was parsed differently");
+    }
+
+    let assigned = common::tree(&["--tree-color=cycle", &fixture.root()])?;
+    let separate = common::tree(&["--tree-color", "cycle", &fixture.root()])?;
+
+    assert_eq!(assigned, separate, "`--tree-color` was parsed differently");
+
+    Ok(())
+}
+
+#[test]
+fn reads_paths_from_a_file() -> TestResult {
+    let fixture = Fixture::new("arguments-paths-from")?;
+
+    for name in ["a", "b", "c"] {
+        fixture.dir(name)?;
+    }
+
+    let [a, b, c, missing] = ["a", "b", "c", "missing"].map(|name| fixture.path(name));
+
+    // Blank lines are ignored, duplicates are only listed once, and missing paths are skipped with a warning.
+    fixture.file("paths", format!("{b}\n\n{a}\n{missing}\n{b}\n"))?;
+
+    let paths = fixture.path("paths");
+    let list_arguments = self::parse_list(&[&c, "--paths-from", &paths])?;
+
+    assert_eq!(list_arguments.paths, [c, b, a].map(|path| Box::from(Path::new(&path))));
+
+    let Some(SubCommand::Tree(tree_arguments)) = self::parse(&["tree", "--paths-from", &paths])?.command else {
+        return Err("the tree sub-command was not selected".into());
+    };
+
+    assert_eq!(tree_arguments.paths.len(), 2);
+    assert!(self::parse_list(&["--paths-from", &fixture.path("unreadable")]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn suggests_the_closest_name() -> TestResult {
+    let suggestions = [
+        (&["lst"][..], "unknown sub-command `lst`, did you mean `list`?"),
+        (&["qwertyuiop"], "unknown sub-command `qwertyuiop`"),
+        (&["list", "--sotr", "name"], "unexpected argument `--sotr`, did you mean `--sort`?"),
+        (&["tree", "--no-rot"], "unexpected argument `--no-rot`, did you mean `--no-root`?"),
+        (&["list", "--qwertyuiop"], "unexpected argument `--qwertyuiop`"),
+    ];
+
+    for (arguments, message) in suggestions {
+        let output = common::binary().args(arguments).output()?;
+
+        assert_eq!(String::from_utf8(output.stderr)?.trim_end(), message);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn wraps_help_to_narrow_widths() -> TestResult {
+    const ORDER_VALUE: ValueSchema<'static> = ValueSchemaBuilder::new("ORDER")
+        .required()
+        .default("name")
+        .options(&["name", "size", "created", "modified", "accessed"])
+        .build();
+    const ARGUMENTS: &[ArgumentSchema<'static>] = &[
+        ArgumentSchemaBuilder::new("sort", "Control the order that entries are listed in within each directory")
+            .short('s')
+            .value(ORDER_VALUE)
+            .build(),
+        ArgumentSchemaBuilder::new("all", "Include hidden files and directories").build(),
+    ];
+    const SCHEMA: CommandSchema<'static> =
+        CommandSchemaBuilder::new("list", "List entries").arguments(ARGUMENTS).build();
+
+    let mut buffer = Vec::new();
+
+    fvr::arguments::schema::write_help(SCHEMA, 60, &mut buffer)?;
+
+    let expected = concat!(
+        "list\n",
+        "  List entries\n",
+        "\n",
+        "Usage: list [ARGUMENTS]\n",
+        "\n",
+        "Arguments:\n",
+        "  -s, --sort [ORDER]             Control the order that\n",
+        "                                 entries are listed in\n",
+        "                                 within each directory\n",
+        "                                 - default: name\n",
+        "                                 - options: name, size,\n",
+        "                                   created, modified,\n",
+        "                                   accessed\n",
+        "      --all                      Include hidden files and\n",
+        "                                 directories\n",
+    );
+    let help = String::from_utf8(buffer)?;
+
+    assert_eq!(help, expected);
+    assert!(help.lines().all(|line| line.len() <= 60));
+
+    Ok(())
+}
+
+#[test]
+fn wraps_piped_help_to_the_columns_width() -> TestResult {
+    let default = common::binary().args(["list", "--help"]).env_remove("COLUMNS").output()?;
+    let narrow = common::binary().args(["list", "--help"]).env("COLUMNS", "40").output()?;
+    let [default, narrow] = [default.stdout, narrow.stdout].map(String::from_utf8);
+    let (default, narrow) = (default?, narrow?);
+
+    // Output that is not written into a terminal is still wrapped, assuming the default width unless one is given.
+    assert!(default.lines().all(|line| line.chars().count() <= fvr::terminal::DEFAULT_WIDTH));
+    assert!(narrow.lines().count() > default.lines().count());
+
+    Ok(())
+}
+
+#[test]
+fn shows_build_details_in_the_verbose_version() -> TestResult {
+    let version = format!("fvr v{}", env!("CARGO_PKG_VERSION"));
+    let plain = common::binary().arg("--version").output()?;
+
+    assert_eq!(String::from_utf8(plain.stdout)?, format!("{version}\n"));
+
+    for arguments in [["--version", "--verbose"], ["--verbose", "--version"], ["-V", "-V"]] {
+        let verbose = String::from_utf8(common::binary().args(arguments).output()?.stdout)?;
+        let mut lines = verbose.lines();
+
+        assert_eq!(lines.next(), Some(&*version));
+        assert!(lines.next().is_some_and(|line| line.starts_with("commit: ")));
+        assert!(lines.next().is_some_and(|line| line.starts_with("built: ")));
+    }
+
+    Ok(())
+}
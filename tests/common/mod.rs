@@ -0,0 +1,196 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright © 2025 Jaxydog
+//
+// This file is part of fvr.
+//
+// fvr is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public
+// License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later
+// version.
+//
+// fvr is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License along with fvr. If not,
+// see <https://www.gnu.org/licenses/>.
+
+//! Provides the fixtures and helpers shared by each integration test.
+
+#![allow(dead_code, reason = "each test only uses some of the shared helpers")]
+
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use fvr::arguments::model::SubCommand;
+use fvr::arguments::{ParseResult, parse_arguments_from};
+
+/// The result returned by each test, which allows any error to be propagated.
+pub type TestResult = std::result::Result<(), Box<dyn std::error::Error>>;
+
+/// A temporary directory that tests create their files within, which is removed once dropped.
+#[derive(Debug)]
+pub struct Fixture {
+    /// The fixture's canonical path.
+    root: PathBuf,
+}
+
+impl Fixture {
+    /// Creates a new, empty fixture whose name is unique to the given test and process.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the directory cannot be created.
+    pub fn new(name: &str) -> Result<Self> {
+        let root = std::env::temp_dir().join(format!("fvr-{name}-{}", std::process::id()));
+
+        if root.try_exists()? {
+            std::fs::remove_dir_all(&root)?;
+        }
+
+        std::fs::create_dir_all(&root)?;
+
+        Ok(Self { root: std::fs::canonicalize(root)? })
+    }
+
+    /// Returns the path of the given file within the fixture.
+    #[must_use]
+    pub fn join(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.root.join(path)
+    }
+
+    /// Returns the fixture's path as an argument.
+    #[must_use]
+    pub fn root(&self) -> String {
+        self.root.to_string_lossy().into_owned()
+    }
+
+    /// Returns the path of the given file within the fixture as an argument.
+    #[must_use]
+    pub fn path(&self, path: impl AsRef<Path>) -> String {
+        self.join(path).to_string_lossy().into_owned()
+    }
+
+    /// Returns the fixture directory's own name.
+    #[must_use]
+    pub fn name(&self) -> String {
+        self.root.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()
+    }
+
+    /// Creates the given directory within the fixture, alongside any of its missing parents.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the directory cannot be created.
+    pub fn dir(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::create_dir_all(self.join(path))
+    }
+
+    /// Creates the given file within the fixture with the given contents, alongside any of its missing parents.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file cannot be written.
+    pub fn file(&self, path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Result<()> {
+        let path = self.join(path);
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(path, contents)
+    }
+
+    /// Creates a symbolic link at the given path within the fixture, pointing to the given target as-is.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the link cannot be created.
+    pub fn symlink(&self, target: impl AsRef<Path>, path: impl AsRef<Path>) -> Result<()> {
+        std::os::unix::fs::symlink(target, self.join(path))
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        // Failing to clean up should never hide the result of the test itself.
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
+
+/// Runs the sub-command named by the given arguments, returning whether any path was skipped and everything written.
+///
+/// # Errors
+///
+/// This function will return an error if the arguments cannot be parsed or the command fails.
+pub fn invoke<I, S>(arguments: I) -> Result<(bool, Vec<u8>)>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let ParseResult::Ok(arguments) = parse_arguments_from(arguments) else {
+        return Err(Error::new(ErrorKind::InvalidInput, "the arguments could not be parsed"));
+    };
+    let mut buffer = Vec::new();
+
+    let skipped_paths = match arguments.command {
+        Some(SubCommand::List(_)) => fvr::command::list::invoke_into(arguments, &mut buffer)?,
+        Some(SubCommand::Tree(_)) => fvr::command::tree::invoke_into(arguments, &mut buffer)?,
+        _ => return Err(Error::new(ErrorKind::InvalidInput, "the arguments do not name a listing sub-command")),
+    };
+
+    Ok((skipped_paths, buffer))
+}
+
+/// Runs the sub-command named by the given arguments, returning everything written as bytes.
+///
+/// # Errors
+///
+/// This function will return an error if the arguments cannot be parsed or the command fails.
+pub fn output_bytes<I, S>(arguments: I) -> Result<Vec<u8>>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    self::invoke(arguments).map(|(_, buffer)| buffer)
+}
+
+/// Runs the sub-command named by the given arguments, returning everything written as text.
+///
+/// # Errors
+///
+/// This function will return an error if the arguments cannot be parsed, the command fails, or the output is not
+/// valid UTF-8.
+pub fn output<I, S>(arguments: I) -> Result<String>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    String::from_utf8(self::output_bytes(arguments)?).map_err(|error| Error::new(ErrorKind::InvalidData, error))
+}
+
+/// Runs the list sub-command without color using the given arguments, returning everything written as text.
+///
+/// # Errors
+///
+/// This function will return an error if the arguments cannot be parsed, the command fails, or the output is not
+/// valid UTF-8.
+pub fn list<S: AsRef<str>>(arguments: &[S]) -> Result<String> {
+    self::output(["list", "--color", "never"].into_iter().chain(arguments.iter().map(AsRef::as_ref)))
+}
+
+/// Runs the tree sub-command without color using the given arguments, returning everything written as text.
+///
+/// # Errors
+///
+/// This function will return an error if the arguments cannot be parsed, the command fails, or the output is not
+/// valid UTF-8.
+pub fn tree<S: AsRef<str>>(arguments: &[S]) -> Result<String> {
+    self::output(["tree", "--color", "never"].into_iter().chain(arguments.iter().map(AsRef::as_ref)))
+}
+
+/// Returns a command that runs the compiled `fvr` binary.
+#[must_use]
+pub fn binary() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_fvr"))
+}
@@ -0,0 +1,972 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright © 2025 Jaxydog
+//
+// This file is part of fvr.
+//
+// fvr is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public
+// License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later
+// version.
+//
+// fvr is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License along with fvr. If not,
+// see <https://www.gnu.org/licenses/>.
+
+//! Tests the output of the list sub-command.
+
+use std::ffi::CString;
+use std::fs::{File, FileTimes, Permissions};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::time::{Duration, UNIX_EPOCH};
+
+use self::common::{Fixture, TestResult};
+
+mod common;
+
+#[test]
+fn lists_a_single_file() -> TestResult {
+    let output = common::list(&["--size", "simple", "Cargo.toml"])?;
+    let size = std::fs::metadata("Cargo.toml")?.len().to_string();
+
+    assert_eq!(output.lines().count(), 1);
+    assert!(output.starts_with(&size) && output.trim_end().ends_with(" Cargo.toml"));
+
+    Ok(())
+}
+
+#[test]
+fn lists_directories_themselves() -> TestResult {
+    let fixture = Fixture::new("list-dir-only")?;
+
+    fixture.file("inner/file", [])?;
+
+    assert_eq!(common::list(&["--dir-only", &fixture.path("inner")])?, "inner/\n");
+
+    Ok(())
+}
+
+#[test]
+fn aligns_the_header_row() -> TestResult {
+    let output = common::list(&["--header", "--blocks", "--mode", "show", "."])?;
+    let header = output.lines().next().ok_or("missing header")?;
+    let name = header.find("NAME").ok_or("missing name header")?;
+    let blocks = header.find("BLOCKS").ok_or("missing blocks header")? + "BLOCKS".len();
+
+    assert!(header.starts_with("MODE"));
+    assert!(output.lines().any(|line| line.get(name ..) == Some("Cargo.toml")));
+    assert!(output.lines().skip(1).all(|line| line.as_bytes()[blocks - 1].is_ascii_digit()));
+
+    Ok(())
+}
+
+#[test]
+fn never_ends_lines_with_a_space() -> TestResult {
+    let fixture = Fixture::new("list-trailing-space")?;
+
+    fixture.dir("directory")?;
+    fixture.file("long-file-name", [0; 1_000])?;
+    fixture.file("short", [])?;
+
+    let columns = ["--mode", "show", "--size", "base-2", "--modified", "simple", "--user", "--group"];
+    let output = common::list(&[&columns[..], &["--header", &fixture.root()]].concat())?;
+
+    assert_eq!(output.lines().count(), 4);
+    assert!(output.lines().all(|line| !line.ends_with(' ')), "{output:?}");
+
+    Ok(())
+}
+
+#[test]
+fn counts_blocks_in_the_given_unit() -> TestResult {
+    let fixture = Fixture::new("list-block-size")?;
+
+    fixture.file("file", [1; 8_192])?;
+
+    let mut counts = Vec::new();
+
+    for block_size in ["512", "1K"] {
+        let output = common::list(&["--blocks", "--block-size", block_size, &fixture.root()])?;
+
+        counts.push(output.split_whitespace().next().ok_or("missing block count")?.parse::<u64>()?);
+    }
+
+    assert!(counts[1] > 0);
+    assert_eq!(counts[0], counts[1] * 2);
+
+    Ok(())
+}
+
+#[test]
+fn counts_the_blocks_allocated_to_each_file() -> TestResult {
+    let fixture = Fixture::new("list-blocks")?;
+
+    fixture.file("dense", [1; 65_536])?;
+    File::create(fixture.join("sparse"))?.set_len(65_536)?;
+
+    let output = common::list(&["--blocks", "--block-size", "512", "--sort", "name", &fixture.root()])?;
+    let lines = output.lines().collect::<Vec<_>>();
+    let [dense, sparse] =
+        ["dense", "sparse"].map(|name| std::fs::metadata(fixture.join(name)).map(|data| data.blocks()));
+    let (dense, sparse) = (dense?, sparse?);
+
+    // Every block of the dense file is written, while the sparse file has a size but was never written to at all.
+    assert!(dense >= 65_536 / 512 && sparse < dense);
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].split_whitespace().collect::<Vec<_>>(), [&*dense.to_string(), "dense"]);
+    assert_eq!(lines[1].split_whitespace().collect::<Vec<_>>(), [&*sparse.to_string(), "sparse"]);
+    assert_eq!(lines[0].find("dense"), lines[1].find("sparse"));
+
+    Ok(())
+}
+
+#[test]
+fn lists_multiple_paths_in_the_given_order() -> TestResult {
+    let fixture = Fixture::new("list-given-order")?;
+
+    for name in ["a", "b", "c"] {
+        fixture.dir(name)?;
+    }
+
+    let output = common::list(&[fixture.path("c"), fixture.path("a"), fixture.path("b")])?;
+    let headers = output.lines().filter_map(|line| line.strip_suffix("/:")).collect::<Vec<_>>();
+
+    assert_eq!(headers, ["c", "a", "b"]);
+
+    Ok(())
+}
+
+#[test]
+fn lists_multiple_paths_in_sorted_order() -> TestResult {
+    let fixture = Fixture::new("list-sorted")?;
+
+    for name in ["a", "b", "c"] {
+        fixture.dir(name)?;
+    }
+
+    let [a, b, c] = [fixture.path("a"), fixture.path("b"), fixture.path("c")];
+
+    for paths in [[&c, &a, &b], [&b, &c, &a]] {
+        let output = common::list(&["--sort-paths", paths[0], paths[1], paths[2]])?;
+        let headers = output.lines().filter_map(|line| line.strip_suffix("/:")).collect::<Vec<_>>();
+
+        assert_eq!(headers, ["a", "b", "c"]);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn omits_headers_and_separators() -> TestResult {
+    let fixture = Fixture::new("list-no-headers")?;
+
+    fixture.file("a/first", [])?;
+    fixture.file("b/second", [])?;
+
+    let [a, b] = [fixture.path("a"), fixture.path("b")];
+
+    assert_eq!(common::list(&["--no-headers", &a, &b])?, "first\n\nsecond\n");
+    assert_eq!(common::list(&["--no-headers", "--no-separators", &a, &b])?, "first\nsecond\n");
+
+    Ok(())
+}
+
+#[test]
+fn pads_simple_sizes_with_zeros() -> TestResult {
+    let fixture = Fixture::new("list-zero-pad")?;
+
+    for (name, size) in [("a", 300), ("b", 5), ("c", 20)] {
+        fixture.file(name, vec![0; size])?;
+    }
+
+    let output = common::list(&["--size", "simple", "--zero-pad", &fixture.root()])?;
+    let mut lines = output.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines, ["300 a", "005 b", "020 c"]);
+
+    lines.sort_unstable();
+
+    assert_eq!(lines, ["005 b", "020 c", "300 a"]);
+
+    Ok(())
+}
+
+#[test]
+fn shows_child_counts_in_place_of_directory_sizes() -> TestResult {
+    let fixture = Fixture::new("list-dir-size")?;
+
+    for name in ["a", "b", "c"] {
+        fixture.file(format!("inner/{name}"), [])?;
+    }
+
+    let output = common::list(&["--size", "simple", "--dir-size", "child-count", &fixture.root()])?;
+
+    assert!(output.lines().any(|line| line.starts_with("3 ") && line.ends_with(" inner/")));
+
+    Ok(())
+}
+
+#[test]
+fn follows_only_linked_paths_given_as_arguments() -> TestResult {
+    let fixture = Fixture::new("list-linked-argument")?;
+
+    fixture.file("target/file", [])?;
+    fixture.dir("target/inner")?;
+    fixture.symlink("inner", "target/inner-link")?;
+    fixture.symlink("target", "link")?;
+
+    assert_eq!(common::list(&[fixture.path("link")])?, "inner/\nfile\ninner-link@\n");
+    // Only the real directory is descended into, as links found while traversing are never followed.
+    assert_eq!(
+        common::list(&["--recursive", &fixture.path("link")])?,
+        "target/:\ninner/\nfile\ninner-link@\n\ntarget/inner/:\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn describes_linked_paths_by_their_targets() -> TestResult {
+    let fixture = Fixture::new("list-linked-argument-metadata")?;
+
+    fixture.dir("target")?;
+    fixture.symlink("target", "link")?;
+    std::fs::set_permissions(fixture.join("target"), Permissions::from_mode(0o750))?;
+
+    // Named links are shown as the directory that they lead to, both on their own and within the heading.
+    assert_eq!(common::list(&["--dir-only", "--mode", "show", &fixture.path("link")])?, "[drwxr-x---] target/\n");
+    assert_eq!(common::list(&["--recursive", &fixture.path("link")])?, "target/:\n");
+
+    Ok(())
+}
+
+#[test]
+fn describes_links_using_their_targets() -> TestResult {
+    let fixture = Fixture::new("list-link-target-meta")?;
+
+    fixture.file("large", vec![0; 65_536])?;
+    fixture.symlink("large", "link")?;
+
+    let output = common::list(&["--size", "simple", "--link-target-meta", &fixture.root()])?;
+
+    assert!(output.lines().any(|line| line.starts_with("65536 ") && line.ends_with(" link@")));
+
+    Ok(())
+}
+
+#[test]
+fn writes_comma_separated_values() -> TestResult {
+    let fixture = Fixture::new("list-csv")?;
+
+    fixture.file("a,b", "abc")?;
+
+    let root = fixture.root();
+    let output = common::output(["list", "--format", "csv", &root])?;
+    let mut lines = output.lines();
+
+    assert_eq!(lines.next(), Some("path,size,mode,uid,gid,mtime,is_dir"));
+    assert!(lines.next().is_some_and(|line| line.starts_with(&format!("\"{root}/a,b\",3,"))));
+    assert!(lines.next().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn terminates_paths_with_null_bytes() -> TestResult {
+    let fixture = Fixture::new("list-print0")?;
+
+    fixture.file("first", [])?;
+    fixture.file("second", [])?;
+    fixture.file("inner/third", [])?;
+
+    let root = fixture.root();
+    let output = common::output_bytes(["list", "--print0", &root])?;
+
+    assert!(!output.contains(&b'\n'));
+    assert_eq!(output.iter().filter(|byte| **byte == 0).count(), 3);
+
+    let output = common::output_bytes(["list", "--print0", "--recursive", "--max-entries", "3", &root])?;
+
+    assert_eq!(output.iter().filter(|byte| **byte == 0).count(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn terminates_nested_paths_with_null_bytes() -> TestResult {
+    let fixture = Fixture::new("list-print0-recursive")?;
+
+    fixture.file("outer/inner/file", [])?;
+
+    let output = common::output_bytes(["list", "--recursive", "--print0", &fixture.root()])?;
+    let paths = output.split(|byte| *byte == 0).filter(|path| !path.is_empty()).collect::<Vec<_>>();
+
+    assert_eq!(paths, [
+        fixture.path("outer").as_bytes(),
+        fixture.path("outer/inner").as_bytes(),
+        fixture.path("outer/inner/file").as_bytes(),
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn writes_colors_as_html() -> TestResult {
+    let fixture = Fixture::new("list-html")?;
+
+    fixture.dir("inner")?;
+
+    assert_eq!(
+        common::output(["list", "--color", "html", &fixture.root()])?,
+        "<span style=\"color:blue\">inner</span><span style=\"color:silver\">/</span>\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn lists_only_matching_names() -> TestResult {
+    let fixture = Fixture::new("list-name")?;
+
+    fixture.dir("logs")?;
+    fixture.file("boot.log", [])?;
+    fixture.file("notes.txt", [])?;
+
+    assert_eq!(common::list(&["--name", "*.log", &fixture.root()])?, "boot.log\n");
+
+    Ok(())
+}
+
+#[test]
+fn hides_matching_dotfiles_alongside_all() -> TestResult {
+    let fixture = Fixture::new("list-hide-pattern")?;
+
+    fixture.dir(".git")?;
+    fixture.file(".config", [])?;
+    fixture.file("visible", [])?;
+
+    let root = fixture.root();
+
+    assert_eq!(common::list(&["--all", &root])?, ".git/\n.config\nvisible\n");
+    assert_eq!(common::list(&["-A", &root])?, ".git/\n.config\nvisible\n");
+    assert_eq!(common::list(&["--almost-all", "--hide-pattern", ".git", &root])?, ".config\nvisible\n");
+    assert_eq!(common::list(&["--hide-pattern", "*.conf*", "-a", &root])?, ".git/\nvisible\n");
+    assert_eq!(common::list(&["--hide-pattern", ".git", &root])?, "visible\n");
+
+    Ok(())
+}
+
+#[test]
+fn marks_sparse_files() -> TestResult {
+    let fixture = Fixture::new("list-sparse")?;
+
+    fixture.file("dense", "contents")?;
+    File::create(fixture.join("sparse"))?.set_len(1 << 20)?;
+
+    let output = common::list(&["--sparse", &fixture.root()])?;
+
+    assert!(output.lines().any(|line| line.ends_with("  dense")));
+    assert!(output.lines().any(|line| line.ends_with("* sparse")));
+
+    Ok(())
+}
+
+#[test]
+fn escapes_tabs_to_keep_columns_aligned() -> TestResult {
+    let fixture = Fixture::new("list-escape-tabs")?;
+
+    fixture.file("a\tb", [])?;
+    fixture.file("wxyz", [])?;
+
+    let output = common::list(&["--escape-tabs", "--columns", "name,mode", &fixture.root()])?;
+    let lines = output.lines().collect::<Vec<_>>();
+
+    assert!(!output.contains('\t'));
+    assert!(lines[0].starts_with("a\\tb [") && lines[1].starts_with("wxyz ["));
+    assert_eq!(lines[0].len(), lines[1].len());
+
+    Ok(())
+}
+
+#[test]
+fn only_escapes_tabs_when_asked() -> TestResult {
+    let fixture = Fixture::new("list-escape-only-tabs")?;
+
+    fixture.file("bell\x07\ttab", [])?;
+
+    assert_eq!(
+        common::output_bytes(["list", "--color", "never", "--escape-tabs", &fixture.root()])?,
+        b"bell\x07\\ttab\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn shuffles_the_same_way_for_a_seed() -> TestResult {
+    let fixture = Fixture::new("list-random")?;
+
+    for name in ["a", "b", "c", "d", "e", "f", "g", "h"] {
+        fixture.file(name, [])?;
+    }
+
+    let root = fixture.root();
+    let first = common::list(&["--sort", "random", "--seed", "7", &root])?;
+    let second = common::list(&["--seed", "7", "--sort", "random", &root])?;
+    let mut names = first.lines().collect::<Vec<_>>();
+
+    names.sort_unstable();
+
+    assert_eq!(first, second);
+    assert_eq!(names, ["a", "b", "c", "d", "e", "f", "g", "h"]);
+
+    Ok(())
+}
+
+#[test]
+fn lists_entries_in_directory_order_when_unsorted() -> TestResult {
+    let fixture = Fixture::new("list-unsorted")?;
+
+    for index in (0 .. 50).rev() {
+        fixture.file(format!("{index:02}"), [])?;
+    }
+
+    // Without a comparator, entries are written in exactly the order that the file system returns them in.
+    let order = std::fs::read_dir(fixture.join(""))?
+        .map(|entry| entry.map(|entry| entry.file_name().to_string_lossy().into_owned()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for sort in ["none", "first-seen"] {
+        assert_eq!(common::list(&["--sort", sort, &fixture.root()])?.lines().collect::<Vec<_>>(), order);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn collates_names_when_requested() -> TestResult {
+    let fixture = Fixture::new("list-collation")?;
+
+    for name in ["Fabian", "Émile", "Eve"] {
+        fixture.file(name, [])?;
+    }
+
+    let root = fixture.root();
+    let bytes = common::list(&["--sort", "name", &root])?;
+    let unicode = common::list(&["--sort", "name", "--collation", "unicode", &root])?;
+    let locale = common::list(&["--sort", "name", "--collation", "locale", &root])?;
+
+    assert_eq!(bytes.lines().collect::<Vec<_>>(), ["Eve", "Fabian", "Émile"]);
+    assert_eq!(unicode.lines().collect::<Vec<_>>(), ["Émile", "Eve", "Fabian"]);
+    assert_eq!(locale, unicode);
+
+    Ok(())
+}
+
+#[test]
+fn writes_literal_names_byte_for_byte() -> TestResult {
+    let fixture = Fixture::new("list-quoting")?;
+
+    fixture.file("bell\x07\x1b[31mred", [])?;
+
+    let root = fixture.root();
+    let escaped = common::output_bytes(["list", "--color", "never", "--escape-tabs", "--quoting", "escape", &root])?;
+    let literal = common::output_bytes(["list", "--color", "never", "--escape-tabs", "--quoting", "literal", &root])?;
+
+    assert_eq!(escaped, b"bell\\x07\\x1b[31mred\n");
+    assert_eq!(literal, b"bell\x07\x1b[31mred\n");
+
+    Ok(())
+}
+
+#[test]
+fn escapes_the_escape_character_by_default() -> TestResult {
+    let fixture = Fixture::new("list-quoting-safe")?;
+
+    fixture.file("bell\x07\x1b[31mred", [])?;
+
+    let root = fixture.root();
+    let default = common::output_bytes(["list", "--color", "never", &root])?;
+    let safe = common::output_bytes(["list", "--color", "never", "--quoting", "safe", &root])?;
+
+    // Only the escape character is escaped, so names cannot change the terminal's colors.
+    assert_eq!(default, b"bell\x07\\x1b[31mred\n");
+    assert_eq!(safe, default);
+
+    Ok(())
+}
+
+#[test]
+fn quotes_and_truncates_link_targets_like_names() -> TestResult {
+    let fixture = Fixture::new("list-quoting-targets")?;
+
+    fixture.symlink("first\nsecond", "link")?;
+
+    let root = fixture.root();
+    let escaped = common::list(&["--resolve-symlinks", "--quoting", "escape", &root])?;
+    let truncated = common::list(&["--resolve-symlinks", "--max-name-width", "4", &root])?;
+
+    assert_eq!(escaped, "link@ -/> first\\nsecond\n");
+    assert_eq!(truncated, "link@ -/> fir…\n");
+
+    Ok(())
+}
+
+#[test]
+fn pads_the_context_column() -> TestResult {
+    let fixture = Fixture::new("list-context")?;
+
+    fixture.file("file", [])?;
+    fixture.file("other-file", [])?;
+
+    let output = common::list(&["--header", "-Z", &fixture.root()])?;
+    let mut lines = output.lines();
+    let name = lines.next().and_then(|header| header.find("NAME")).ok_or("missing name header")?;
+
+    assert!(output.starts_with("CONTEXT"));
+    assert!(lines.all(|line| line[.. name].ends_with(' ') && !line[name ..].starts_with(' ')));
+
+    Ok(())
+}
+
+#[test]
+fn groups_hard_links() -> TestResult {
+    let fixture = Fixture::new("list-hardlinks")?;
+
+    fixture.file("a", "first")?;
+    fixture.file("b", "second")?;
+    std::fs::hard_link(fixture.join("a"), fixture.join("c"))?;
+
+    assert_eq!(
+        common::list(&["--find-hardlinks", &fixture.root()])?,
+        "a [hardlink group 1]\nb\nc [hardlink group 1]\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sorts_directories_before_files_by_size() -> TestResult {
+    let fixture = Fixture::new("list-size-sort")?;
+
+    fixture.dir("directory")?;
+    fixture.file("large", [0; 10_000])?;
+    fixture.file("small", [0; 10])?;
+
+    assert_eq!(common::list(&["--sort", "size", &fixture.root()])?, "directory/\nsmall\nlarge\n");
+
+    Ok(())
+}
+
+#[test]
+fn rounds_sizes_near_unit_boundaries() -> TestResult {
+    let fixture = Fixture::new("list-size-boundaries")?;
+
+    for size in [1_023, 1_024, 10_240, 999_999, 1_048_575] {
+        fixture.file(size.to_string(), vec![0; size])?;
+    }
+
+    let root = fixture.root();
+
+    assert_eq!(
+        common::list(&["--size", "base-2", "--sort", "size", &root])?,
+        concat!(
+            "  1023 B   1023\n",
+            "  1.00 KiB 1024\n",
+            "  10.0 KiB 10240\n",
+            "   977 KiB 999999\n",
+            "  1.00 MiB 1048575\n",
+        )
+    );
+    assert_eq!(
+        common::list(&["--size", "base-10", "--sort", "size", &root])?,
+        concat!(" 1.02 KB 1023\n", " 1.02 KB 1024\n", " 10.2 KB 10240\n", " 1.00 MB 999999\n", " 1.05 MB 1048575\n")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sorts_directories_by_the_bytes_they_contain() -> TestResult {
+    let fixture = Fixture::new("list-total-size-sort")?;
+
+    fixture.file("large/inner/file", [0; 10_000])?;
+    fixture.file("medium/file", [0; 1_000])?;
+    fixture.file("small/file", [0; 10])?;
+
+    let root = fixture.root();
+
+    assert_eq!(common::list(&["--sort", "total-size", &root])?, "small/\nmedium/\nlarge/\n");
+    assert_eq!(common::list(&["--sort", "reverse-total-size", &root])?, "large/\nmedium/\nsmall/\n");
+
+    Ok(())
+}
+
+#[test]
+fn colors_dangling_links_red() -> TestResult {
+    let fixture = Fixture::new("list-dangling-link")?;
+
+    fixture.file("target", [])?;
+    fixture.symlink("target", "valid")?;
+    fixture.symlink("missing", "dangling")?;
+
+    assert_eq!(
+        common::output(["list", "--color", "always", &fixture.root()])?,
+        concat!(
+            "\x1b[39mtarget\x1b[39m\n",
+            "\x1b[91mdangling\x1b[39m\x1b[37m@\x1b[39m\n",
+            "\x1b[96mvalid\x1b[39m\x1b[37m@\x1b[39m\n",
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn resets_colors_after_ansi_names() -> TestResult {
+    let fixture = Fixture::new("list-name-ansi")?;
+
+    fixture.file("\x1b[31mred", [])?;
+
+    let root = fixture.root();
+    let output =
+        common::output_bytes(["list", "--color", "never", "--allow-name-ansi", "--columns", "name,mode", &root])?;
+
+    assert!(output.starts_with(b"\x1b[31mred\x1b[0m ["));
+
+    Ok(())
+}
+
+#[test]
+fn limits_entries_to_a_range_of_dates() -> TestResult {
+    let fixture = Fixture::new("list-since")?;
+
+    for (name, seconds) in [("old", 1_577_836_800), ("edge", 1_704_067_200), ("new", 1_748_736_000)] {
+        File::create(fixture.join(name))?.set_modified(UNIX_EPOCH + Duration::from_secs(seconds))?;
+    }
+
+    let root = fixture.root();
+
+    assert_eq!(common::list(&["--since", "2024-01-01T00:00:00Z", &root])?, "edge\nnew\n");
+    assert_eq!(common::list(&["--until", "2024-01-01T00:00:00Z", &root])?, "old\n");
+
+    Ok(())
+}
+
+#[test]
+fn sorts_by_access_dates_newest_first() -> TestResult {
+    let fixture = Fixture::new("list-accessed-sort")?;
+
+    for (name, seconds) in [("old", 1_577_836_800), ("new", 1_748_736_000), ("middle", 1_704_067_200)] {
+        let time = UNIX_EPOCH + Duration::from_secs(seconds);
+
+        File::create(fixture.join(name))?.set_times(FileTimes::new().set_accessed(time).set_modified(time))?;
+    }
+
+    let root = fixture.root();
+
+    assert_eq!(common::list(&["--sort", "accessed", &root])?, "new\nmiddle\nold\n");
+    assert_eq!(common::list(&["--sort", "reverse-accessed", &root])?, "old\nmiddle\nnew\n");
+
+    Ok(())
+}
+
+#[test]
+fn sorts_by_status_change_dates() -> TestResult {
+    let fixture = Fixture::new("list-ctime-sort")?;
+
+    fixture.file("first", [])?;
+    fixture.file("second", [])?;
+
+    // Status change dates cannot be set directly, so the first file is changed again after the second is created.
+    std::thread::sleep(Duration::from_millis(20));
+    std::fs::set_permissions(fixture.join("first"), Permissions::from_mode(0o600))?;
+
+    let root = fixture.root();
+
+    assert_eq!(common::list(&["--sort", "ctime", &root])?, "first\nsecond\n");
+    assert_eq!(common::list(&["--sort", "reverse-ctime", &root])?, "second\nfirst\n");
+
+    Ok(())
+}
+
+#[test]
+fn shows_status_change_dates() -> TestResult {
+    let fixture = Fixture::new("list-changed")?;
+
+    fixture.file("file", [])?;
+
+    let root = fixture.root();
+    let columns = ["--color", "always", "--modified", "iso8601", "--changed", "iso8601", &root];
+    let output = common::output([&["list"][..], &columns].concat())?;
+    let (modified, rest) = output.split_once(' ').ok_or("missing modified column")?;
+    let (changed, name) = rest.split_once(' ').ok_or("missing changed column")?;
+
+    // Creating a file sets both dates at once, and status change dates are shown in their own color.
+    assert_eq!(changed.strip_prefix("\x1b[95m"), modified.strip_prefix("\x1b[94m"));
+    assert_eq!(name, "\x1b[39mfile\x1b[39m\n");
+
+    std::thread::sleep(Duration::from_millis(20));
+    std::fs::set_permissions(fixture.join("file"), Permissions::from_mode(0o600))?;
+
+    let output = common::list(&["--modified", "iso8601", "--changed", "iso8601", &root])?;
+    let dates = output.split_whitespace().take(2).collect::<Vec<_>>();
+
+    assert!(dates[0] < dates[1], "{dates:?}");
+    assert_eq!(
+        common::list(&["--header", "--changed", "simple", &root])?.lines().next(),
+        Some("CHANGED          NAME")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn shows_every_date_in_the_same_local_offset() -> TestResult {
+    let fixture = Fixture::new("list-offset")?;
+    let time = UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+
+    File::create(fixture.join("file"))?.set_times(FileTimes::new().set_accessed(time).set_modified(time))?;
+
+    // The binary is used so that the offset is read from `TZ` while the process still has a single thread.
+    let output = common::binary()
+        .env("TZ", "XYZ-05:30")
+        .args(["list", "--color", "never", "--accessed", "iso8601", "--modified", "iso8601", &fixture.root()])
+        .output()?;
+    let date = "2024-01-01T05:30:00.000000000+05:30";
+
+    assert_eq!(String::from_utf8(output.stdout)?, format!("{date} {date} file\n"));
+
+    Ok(())
+}
+
+#[test]
+fn marks_access_control_lists() -> TestResult {
+    let fixture = Fixture::new("list-acl")?;
+
+    fixture.file("plain", [])?;
+    fixture.file("shared", [])?;
+
+    // This grants read access to the `nobody` user, alongside the entries that mirror the file's permission bits.
+    let acl =
+        [(0x01_u16, 6_u16, u32::MAX), (0x02, 4, 65_534), (0x04, 4, u32::MAX), (0x10, 4, u32::MAX), (0x20, 4, u32::MAX)]
+            .into_iter()
+            .fold(2_u32.to_le_bytes().to_vec(), |mut acl, (tag, permissions, id)| {
+                acl.extend([&tag.to_le_bytes()[..], &permissions.to_le_bytes(), &id.to_le_bytes()].concat());
+                acl
+            });
+    let path = CString::new(fixture.join("shared").as_os_str().as_bytes())?;
+
+    // SAFETY: Both strings are null-terminated, and the value outlives the call.
+    let result = unsafe {
+        libc::lsetxattr(path.as_ptr(), c"system.posix_acl_access".as_ptr(), acl.as_ptr().cast(), acl.len(), 0)
+    };
+
+    let output = common::list(&["--mode", "show", &fixture.root()])?;
+
+    // File systems without support for access control lists cannot be given one, in which case nothing is marked.
+    if result == 0 {
+        assert!(output.contains("]  plain\n") && output.contains("+] shared\n"));
+    } else {
+        assert!(!output.contains('+'));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn totals_the_shown_sizes() -> TestResult {
+    let fixture = Fixture::new("list-total")?;
+
+    fixture.dir("directory")?;
+    fixture.file("large", [0; 100])?;
+    fixture.file("small", [0; 23])?;
+
+    let root = fixture.root();
+    let simple = common::list(&["--size", "simple", "--total", &root])?;
+    let base_10 = common::list(&["--size", "base-10", "--total", &root])?;
+
+    assert_eq!(simple.lines().last(), Some("123 total"));
+    assert_eq!(base_10.lines().last().map(str::trim_start), Some("123 B  total"));
+
+    Ok(())
+}
+
+#[test]
+fn lists_contents_as_a_tree() -> TestResult {
+    let fixture = Fixture::new("list-tree")?;
+
+    fixture.file("nested/inner", [0; 2048])?;
+    fixture.file("outer", [0; 100])?;
+
+    let output = common::list(&["--tree", "--size", "base-2", &fixture.root()])?;
+    let lines = output.lines().map(str::trim_start).collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("-.- -") && lines[0].ends_with(&format!("┌─{}/", fixture.name())));
+    assert!(lines[1].starts_with("-.- -") && lines[1].ends_with("├─┬─nested/"));
+    assert!(lines[2].starts_with("2.00 KiB") && lines[2].ends_with("│ └───inner"));
+    assert!(lines[3].starts_with("100 B") && lines[3].ends_with("└───outer"));
+
+    Ok(())
+}
+
+#[test]
+fn annotates_unreadable_link_targets() -> TestResult {
+    let fixture = Fixture::new("list-link-error")?;
+
+    fixture.file("locked/file", [])?;
+    fixture.file("file", [])?;
+    fixture.symlink("locked/file", "locked-link")?;
+    fixture.symlink("file/child", "nested-link")?;
+    std::fs::set_permissions(fixture.join("locked"), Permissions::from_mode(0o000))?;
+
+    let output = common::list(&["--resolve-symlinks", &fixture.root()]);
+
+    std::fs::set_permissions(fixture.join("locked"), Permissions::from_mode(0o755))?;
+
+    let output = output?;
+
+    assert!(output.lines().any(|line| line == "nested-link@ -/> [error: not a directory]"));
+    // Permissions are not enforced for privileged users, who are able to read the target regardless.
+    assert!(output.lines().any(|line| {
+        line == "locked-link@ -/> [error: permission denied]" || line == "locked-link@ --> locked/file"
+    }));
+
+    Ok(())
+}
+
+#[test]
+fn orders_columns_as_given() -> TestResult {
+    let fixture = Fixture::new("list-columns")?;
+
+    fixture.file("file", "abc")?;
+    std::fs::set_permissions(fixture.join("file"), Permissions::from_mode(0o644))?;
+
+    let root = fixture.root();
+
+    assert_eq!(
+        common::list(&["--header", "--columns", "size,mode", &root])?,
+        "SIZE MODE         NAME\n3    [-rw-r--r--] file\n"
+    );
+    assert_eq!(
+        common::list(&["--header", "--columns", "mode,size", &root])?,
+        "MODE         SIZE NAME\n[-rw-r--r--] 3    file\n"
+    );
+    assert_eq!(common::list(&["--columns", "name,mode", &root])?, "file [-rw-r--r--]\n");
+    // Columns that were not chosen are hidden, even if they were asked for separately.
+    assert_eq!(common::list(&["--mode", "show", "--columns", "size", &root])?, "3 file\n");
+
+    fixture.file("longer-name", "abcdefghijkl")?;
+
+    // Names are padded to align the columns after them, while the last column is never padded.
+    assert_eq!(
+        common::list(&["--header", "--columns", "name,size", &root])?,
+        "NAME        SIZE\nfile        3\nlonger-name 12\n"
+    );
+    assert_eq!(
+        common::list(&["--total", "--columns", "name,size,mode", &root])?,
+        concat!("file        3  [-rw-r--r--]\n", "longer-name 12 [-rw-r--r--]\n", "total       15\n")
+    );
+
+    assert!(common::list(&["--columns", "size,unknown", &root]).is_err());
+    assert!(common::list(&["--columns", "size,size", &root]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn groups_by_extension_with_the_largest_first() -> TestResult {
+    let fixture = Fixture::new("list-ext-size")?;
+
+    for (name, size) in [("a.png", 1), ("b.png", 3), ("c.mp4", 2), ("d.mp4", 5), ("e.png", 2), ("notes", 4)] {
+        fixture.file(name, vec![0; size])?;
+    }
+
+    let output = common::list(&["--sort", "ext-size", &fixture.root()])?;
+
+    assert_eq!(output.lines().collect::<Vec<_>>(), ["notes", "d.mp4", "c.mp4", "b.png", "e.png", "a.png"]);
+
+    Ok(())
+}
+
+#[test]
+fn reports_progress_outside_of_the_listing() -> TestResult {
+    let output = common::list(&["--progress", "."])?;
+
+    assert!(output.lines().any(|line| line == "Cargo.toml"));
+    assert!(!output.contains("scanned"));
+
+    Ok(())
+}
+
+#[test]
+fn lists_readable_paths_alongside_missing_ones() -> TestResult {
+    let fixture = Fixture::new("list-partial")?;
+
+    fixture.file("readable/file", [])?;
+
+    let (readable, missing) = (fixture.path("readable"), fixture.path("missing"));
+
+    for command in ["list", "tree"] {
+        let output = common::binary().args([command, "--color", "never", &readable, &missing]).output()?;
+
+        assert_eq!(output.status.code(), Some(i32::from(fvr::exit_codes::ERROR_PARTIAL)));
+        assert!(String::from_utf8(output.stdout)?.lines().any(|line| line.ends_with("file")));
+        assert!(String::from_utf8(output.stderr)?.starts_with(&format!("{missing}: ")));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn limits_entries_across_the_listing() -> TestResult {
+    let fixture = Fixture::new("list-max-entries")?;
+
+    for index in 0 .. 100 {
+        fixture.file(format!("{index:03}"), [])?;
+    }
+
+    let output = common::list(&["--max-entries", "10", &fixture.root()])?;
+    let lines = output.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 11);
+    assert_eq!(lines.last(), Some(&"... (90 more)"));
+
+    Ok(())
+}
+
+#[test]
+fn marks_links_back_into_a_listed_directory() -> TestResult {
+    let fixture = Fixture::new("list-cycle")?;
+
+    fixture.dir("a")?;
+    fixture.symlink("..", "a/link")?;
+
+    let output = common::list(&["--recursive", &fixture.root()])?;
+
+    assert!(output.lines().any(|line| line == "link@ [cycle]"));
+
+    Ok(())
+}
+
+#[test]
+fn limits_the_depth_of_recursive_listings() -> TestResult {
+    let fixture = Fixture::new("list-recursive-depth")?;
+
+    fixture.file("a/b/c/file", [])?;
+
+    let root = fixture.root();
+    let name = fixture.name();
+    let shallow = common::list(&["--recursive", "--depth", "1", &root])?;
+    let deeper = common::list(&["--recursive", "--depth", "2", &root])?;
+    let plain = common::output_bytes(["list", "--print0", "--recursive", "--depth", "2", &root])?;
+
+    assert_eq!(shallow, format!("{name}/:\na/\n"));
+    assert_eq!(deeper, format!("{name}/:\na/\n\n{name}/a/:\nb/\n"));
+    assert_eq!(plain.split(|&byte| byte == 0).filter(|path| !path.is_empty()).count(), 2);
+
+    Ok(())
+}
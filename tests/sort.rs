@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright © 2025 Jaxydog
+//
+// This file is part of fvr.
+//
+// fvr is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public
+// License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later
+// version.
+//
+// fvr is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License along with fvr. If not,
+// see <https://www.gnu.org/licenses/>.
+
+//! Tests how entries are ordered.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use fvr::files::compare_dates;
+
+#[test]
+fn sorts_missing_dates_last_in_a_stable_order() {
+    let [old, new] = [UNIX_EPOCH, UNIX_EPOCH + Duration::from_secs(1)].map(Some);
+    let entries: [(&str, Option<SystemTime>); 6] =
+        [("a", old), ("b", None), ("c", new), ("d", None), ("e", old), ("f", None)];
+
+    // A comparator that is not a total order could be given different results for each starting order.
+    for rotation in 0 .. entries.len() {
+        for reversed in [false, true] {
+            let mut sorted = entries.to_vec();
+
+            sorted.rotate_left(rotation);
+
+            if reversed {
+                sorted.reverse();
+            }
+
+            sorted.sort_unstable_by(|lhs, rhs| compare_dates(lhs.1, rhs.1).then_with(|| lhs.0.cmp(rhs.0)));
+
+            assert_eq!(sorted.iter().map(|(name, _)| *name).collect::<Vec<_>>(), ["c", "a", "e", "b", "d", "f"]);
+        }
+    }
+
+    for lhs in entries {
+        for rhs in entries {
+            assert_eq!(compare_dates(lhs.1, rhs.1), compare_dates(rhs.1, lhs.1).reverse());
+        }
+    }
+}
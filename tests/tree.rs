@@ -0,0 +1,274 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright © 2025 Jaxydog
+//
+// This file is part of fvr.
+//
+// fvr is free software: you can redistribute it and/or modify it under the terms of the GNU Affero General Public
+// License as published by the Free Software Foundation, either version 3 of the License, or (at your option) any later
+// version.
+//
+// fvr is distributed in the hope that it will be useful, but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License along with fvr. If not,
+// see <https://www.gnu.org/licenses/>.
+
+//! Tests the output of the tree sub-command.
+
+use self::common::{Fixture, TestResult};
+
+mod common;
+
+#[test]
+fn summarizes_shown_entries() -> TestResult {
+    let fixture = Fixture::new("tree-stats")?;
+
+    fixture.file("first", [0; 100])?;
+    fixture.file("inner/second", [0; 1_000])?;
+    fixture.file(".hidden", [0; 10_000])?;
+
+    let output = common::tree(&["--stats", &fixture.root()])?;
+
+    assert_eq!(output.lines().last(), Some("1 directory, 2 files, 1.07 KiB"));
+
+    Ok(())
+}
+
+#[test]
+fn omits_the_root() -> TestResult {
+    let fixture = Fixture::new("tree-no-root")?;
+
+    fixture.file("first", [])?;
+    fixture.file("inner/second", [])?;
+
+    assert_eq!(common::tree(&["--no-root", &fixture.root()])?, "┌─┬─inner/\n│ └───second\n└───first\n");
+
+    Ok(())
+}
+
+#[test]
+fn shows_only_leaves() -> TestResult {
+    let fixture = Fixture::new("tree-leaves-only")?;
+
+    fixture.dir("empty")?;
+    fixture.file("outer/first", [])?;
+    fixture.file("outer/inner/second", [])?;
+
+    assert_eq!(
+        common::tree(&["--no-root", "--leaves-only", &fixture.root()])?,
+        "┌───empty/\n├───outer/inner/second\n└───outer/first\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn shows_full_paths() -> TestResult {
+    let fixture = Fixture::new("tree-full-path")?;
+
+    fixture.file("outer/inner/file", [])?;
+    fixture.symlink("inner/file", "outer/link")?;
+
+    assert_eq!(
+        common::tree(&["--no-root", "--full-path", "--resolve-symlinks", &fixture.root()])?,
+        "──┬─outer/\n  ├─┬─outer/inner/\n  │ └───outer/inner/file\n  └───outer/link@ --> inner/file\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn flattens_the_tree() -> TestResult {
+    let fixture = Fixture::new("tree-flat")?;
+
+    fixture.file("outer/first", [])?;
+    fixture.file("outer/inner/second", [])?;
+    fixture.file("top", [])?;
+
+    assert_eq!(
+        common::tree(&["--no-root", "--flat", "--depth", "2", &fixture.root()])?,
+        "outer/\nouter/inner/\nouter/first\ntop\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn draws_branches_in_other_styles() -> TestResult {
+    let fixture = Fixture::new("tree-style")?;
+
+    fixture.file("outer/inner", [])?;
+    fixture.file("top", [])?;
+
+    let (root, name) = (fixture.root(), fixture.name());
+
+    assert_eq!(common::tree(&["--ascii", &root])?, format!("+-{name}/\n|-+-outer/\n| `---inner\n`---top\n"));
+    assert_eq!(common::tree(&["--indent", &root])?, format!("  {name}/\n    outer/\n      inner\n    top\n"));
+
+    Ok(())
+}
+
+#[test]
+fn finds_matching_names_within_any_directory() -> TestResult {
+    let fixture = Fixture::new("tree-name")?;
+
+    fixture.file("outer/boot.log", [])?;
+    fixture.file("outer/notes.txt", [])?;
+    fixture.file("top.log", [])?;
+
+    let output = common::tree(&["--indent", "--name", "*.log", &fixture.root()])?;
+
+    assert!(output.lines().any(|line| line.trim_start() == "boot.log"));
+    assert!(output.lines().any(|line| line.trim_start() == "top.log"));
+    assert!(!output.contains("notes.txt") && !output.contains("outer"));
+
+    Ok(())
+}
+
+#[test]
+fn draws_branches_between_matching_names() -> TestResult {
+    let fixture = Fixture::new("tree-name-branches")?;
+
+    fixture.file("outer/boot.log", [])?;
+    fixture.file("outer/notes.txt", [])?;
+    fixture.file("top.log", [])?;
+    fixture.file("zzz.txt", [])?;
+
+    assert_eq!(
+        common::tree(&["--no-root", "--name", "*.log", "--name", "outer", &fixture.root()])?,
+        "┌─┬─outer/\n│ └───boot.log\n└───top.log\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn truncates_long_names() -> TestResult {
+    let fixture = Fixture::new("tree-max-name-width")?;
+
+    fixture.file("日本語のファイル名.txt", [])?;
+    fixture.file("short", [])?;
+
+    let output = common::tree(&["--no-root", "--max-name-width", "10", &fixture.root()])?;
+
+    assert!(output.lines().any(|line| line.ends_with("─日本語の…")));
+    assert!(output.lines().any(|line| line.ends_with("─short")));
+
+    Ok(())
+}
+
+#[test]
+fn sorts_by_depth() -> TestResult {
+    let fixture = Fixture::new("tree-depth")?;
+
+    fixture.file("a/b/c/file", [])?;
+    fixture.file("b/c/file", [])?;
+    fixture.file("c", [])?;
+
+    let output = common::tree(&["--no-root", "--sort", "depth,name", &fixture.root()])?;
+    let names = output.lines().map(|line| line.trim_start_matches(|c: char| !c.is_alphanumeric())).collect::<Vec<_>>();
+
+    assert_eq!(names, ["a/", "b/", "c/", "file", "b/", "c/", "file", "c"]);
+
+    Ok(())
+}
+
+#[test]
+fn counts_shown_entries_within_directories() -> TestResult {
+    let fixture = Fixture::new("tree-counts")?;
+
+    fixture.file("directory/file", [])?;
+    fixture.file(".hidden", [])?;
+
+    let root = fixture.root();
+    let hidden = common::tree(&["--counts", &root])?;
+    let shown = common::tree(&["--counts", "--all", &root])?;
+
+    assert!(hidden.lines().next().is_some_and(|line| line.ends_with("/ (1)")));
+    assert!(hidden.contains("directory/ (1)"));
+    assert!(shown.lines().next().is_some_and(|line| line.ends_with("/ (2)")));
+
+    Ok(())
+}
+
+#[test]
+fn limits_entries_across_the_tree() -> TestResult {
+    let fixture = Fixture::new("tree-max-entries")?;
+
+    for index in 0 .. 100 {
+        fixture.file(format!("{index:03}"), [])?;
+    }
+
+    let output = common::tree(&["--no-root", "--max-entries", "10", &fixture.root()])?;
+    let lines = output.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 11);
+    assert_eq!(lines.last(), Some(&"... (90 more)"));
+
+    Ok(())
+}
+
+#[test]
+fn lists_multiple_paths_in_sorted_order() -> TestResult {
+    let fixture = Fixture::new("tree-sorted")?;
+
+    for name in ["a", "b", "c"] {
+        fixture.dir(name)?;
+    }
+
+    let [a, b, c] = [fixture.path("a"), fixture.path("b"), fixture.path("c")];
+
+    assert_eq!(common::tree(&[&c, &a, &b])?, "┌─c/\n\n┌─a/\n\n┌─b/\n");
+    assert_eq!(common::tree(&["--sort-paths", &c, &a, &b])?, "┌─a/\n\n┌─b/\n\n┌─c/\n");
+
+    Ok(())
+}
+
+#[test]
+fn stops_at_links_back_into_the_tree() -> TestResult {
+    let fixture = Fixture::new("tree-cycle")?;
+
+    fixture.dir("a")?;
+    fixture.symlink("..", "a/link")?;
+
+    assert_eq!(common::tree(&["--no-root", &fixture.root()])?, "──┬─a/\n  └───link@ [cycle]\n");
+
+    Ok(())
+}
+
+#[test]
+fn narrows_deep_trees() -> TestResult {
+    let fixture = Fixture::new("tree-narrow")?;
+
+    fixture.file("a/b/c/d/e/f/g", [])?;
+    fixture.file("top", [])?;
+
+    assert_eq!(
+        common::tree(&["--no-root", "--narrow", &fixture.root()])?,
+        concat!("┌┬a/\n", "│└┬b/\n", "│ └┬c/\n", "│  └┬d/\n", "│   └┬e/\n", "│    └┬f/\n", "│     └─g\n", "└─top\n",)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cycles_branch_colors_by_depth() -> TestResult {
+    let fixture = Fixture::new("tree-color-cycle")?;
+
+    fixture.file("a/b/file", [])?;
+
+    let output = common::output(["tree", "--color", "always", "--tree-color", "cycle", "--no-root", &fixture.root()])?;
+
+    // Each level of indentation is drawn in the color of its depth, while names keep their usual colors.
+    assert_eq!(
+        output,
+        concat!(
+            "\x1b[94m──┬─\x1b[39m\x1b[94ma\x1b[39m\x1b[37m/\x1b[39m\n",
+            "\x1b[94m  \x1b[95m└─┬─\x1b[39m\x1b[94mb\x1b[39m\x1b[37m/\x1b[39m\n",
+            "\x1b[94m  \x1b[95m  \x1b[96m└───\x1b[39m\x1b[39mfile\x1b[39m\n",
+        )
+    );
+
+    Ok(())
+}